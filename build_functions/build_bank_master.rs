@@ -0,0 +1,57 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+use std::{
+    env,
+    fs::File,
+    io::Write,
+    path::PathBuf,
+};
+
+const OVERRIDE_VAR: &str = "SWISS_QRUST_BANK_MASTER";
+
+pub fn generate() {
+    println!("cargo:rerun-if-env-changed={OVERRIDE_VAR}");
+
+    // Without a real bank master nothing is embedded: made-up banks under
+    // real IIDs would be worse than no answer.
+    let (snapshot, format, sample) = match env::var(OVERRIDE_VAR) {
+        Ok(path) if !path.is_empty() => {
+            println!("cargo:rerun-if-changed={path}");
+            let path = PathBuf::from(&path)
+                .canonicalize()
+                .unwrap_or_else(|e| panic!("failed to find bank master {path}: {e}"));
+            let format = match path.extension().and_then(|e| e.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("json") => "Json",
+                _ => "Csv",
+            };
+            // The file is embedded with include_str! rather than parsed here,
+            // so the runtime parser is the only one and a file that loads via
+            // BankMaster::from_path also works when embedded.
+            (format!("include_str!({:?})", path.display().to_string()), format, false)
+        }
+        _ => ("\"\"".to_string(), "Csv", true),
+    };
+
+    let generated = format!(
+        r#"// AUTO-GENERATED BY build_functions/build_bank_master.rs
+// ⚠️ DO NOT EDIT BY HAND
+
+/// Bank master file embedded at build time (see [`crate::BankMaster::embedded`]).
+pub const BANK_MASTER_SNAPSHOT: &str = {snapshot};
+pub const BANK_MASTER_SNAPSHOT_FORMAT: crate::BankMasterFormat = crate::BankMasterFormat::{format};
+/// `true` if no bank master was given at build time; [`crate::BankMaster::embedded`]
+/// is then empty and every lookup fails.
+pub const BANK_MASTER_SNAPSHOT_IS_SAMPLE: bool = {sample};
+"#
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let dest_path = PathBuf::from(out_dir).join("bank_master.rs");
+    let mut file = File::create(dest_path).expect("failed to create bank_master.rs");
+    file.write_all(generated.as_bytes())
+        .expect("failed to write bank_master.rs");
+}
@@ -22,6 +22,10 @@ struct CountryRaw {
     name_de: String,
     name_fr: String,
     name_it: String,
+    /// Every spelling `Country::resolve` should accept: common and official
+    /// names in English, German, French, Italian and the native languages,
+    /// plus the alternative spellings from the data set.
+    names: Vec<String>,
     flag: Option<String>,
     status: Option<String>,
 }
@@ -33,6 +37,8 @@ struct CountryHelper {
     ccn3: Option<String>,
     name: Name,
     translations: Map<String, Translation>,
+    #[serde(rename = "altSpellings", default)]
+    alt_spellings: Vec<String>,
     flag: Option<String>,
     status: Option<String>,
 }
@@ -40,15 +46,31 @@ struct CountryHelper {
 #[derive(Debug, Deserialize)]
 struct Name {
     common: String,
+    official: String,
+    #[serde(default)]
+    native: Map<String, Translation>,
 }
 
-#[derive(Deserialize)]
-struct Translation { common: String }
+#[derive(Debug, Deserialize)]
+struct Translation { common: String, official: String }
 
 type Map<K, V> = std::collections::HashMap<K, V>;
 
 impl From<CountryHelper> for CountryRaw {
     fn from(helper: CountryHelper) -> Self {
+        let mut names = vec![helper.name.common.clone(), helper.name.official.clone()];
+        let translations = ["deu", "fra", "ita"]
+            .iter()
+            .filter_map(|lang| helper.translations.get(*lang))
+            .chain(helper.name.native.values());
+        for t in translations {
+            names.push(t.common.clone());
+            names.push(t.official.clone());
+        }
+        names.extend(helper.alt_spellings.iter().cloned());
+        names.sort();
+        names.dedup();
+
         Self {
             cca2: helper.cca2,
             cca3: helper.cca3,
@@ -58,6 +80,7 @@ impl From<CountryHelper> for CountryRaw {
             name_de: helper.translations.get("deu").map(|t| t.common.clone()).unwrap_or_default(),
             name_fr: helper.translations.get("fra").map(|t| t.common.clone()).unwrap_or_default(),
             name_it: helper.translations.get("ita").map(|t| t.common.clone()).unwrap_or_default(),
+            names,
             flag: helper.flag,
             status: helper.status,
         }
@@ -87,6 +110,7 @@ pub fn generate() {
     let mut enum_variants = String::new();
     let mut meta_arms = String::new();
     let mut from_str_arms = String::new();
+    let mut name_entries = String::new();
 
     for c in countries {
         let alpha2 = c.cca2;
@@ -135,6 +159,9 @@ pub fn generate() {
             a2 = alpha2
         ));
 
+        for name in &c.names {
+            name_entries.push_str(&format!("    ({name:?}, Country::{variant}),\n"));
+        }
     }
 
     let generated = format!(
@@ -180,6 +207,11 @@ impl Country {{
     }}
 }}
 
+/// Country names and alternative spellings as found in the data set, not
+/// normalized; see `Country::resolve`.
+pub const COUNTRY_NAMES: &[(&str, Country)] = &[
+{name_entries}];
+
 impl std::str::FromStr for Country {{
     type Err = CountryParseError;
 
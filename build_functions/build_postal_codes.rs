@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+use std::{
+    env,
+    fs::File,
+    io::Write,
+    path::PathBuf,
+};
+
+const BUNDLED: &str = "assets/data/postal_codes.csv";
+const OVERRIDE_VAR: &str = "SWISS_QRUST_POSTAL_DIRECTORY";
+const PARTIAL_MARKER: &str = "# partial snapshot";
+
+/// Reads a semicolon separated postal directory (the bundled snapshot or
+/// a full Swiss Post export) into `(plz, locality, canton)` rows. Columns
+/// are found by header name; `#` lines are comments.
+fn read_directory(content: &str) -> Vec<(u16, String, String)> {
+    let mut lines = content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'));
+
+    let header: Vec<String> = lines
+        .next()
+        .expect("postal directory has no header")
+        .split(';')
+        .map(|h| h.trim().trim_matches('"').to_ascii_lowercase())
+        .collect();
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .unwrap_or_else(|| panic!("postal directory has no '{name}' column"))
+    };
+    let (plz_col, city_col, canton_col) = (column("postleitzahl"), column("ortbez27"), column("kanton"));
+
+    let mut rows: Vec<(u16, String, String)> = lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(';').map(|f| f.trim().trim_matches('"')).collect();
+            let plz = fields.get(plz_col)?.parse().ok()?;
+            let city = fields.get(city_col)?.to_string();
+            let canton = fields.get(canton_col).unwrap_or(&"").to_string();
+            Some((plz, city, canton))
+        })
+        .collect();
+    rows.sort();
+    rows.dedup();
+    rows
+}
+
+pub fn generate() {
+    println!("cargo:rerun-if-changed={BUNDLED}");
+    println!("cargo:rerun-if-env-changed={OVERRIDE_VAR}");
+
+    let path = match env::var(OVERRIDE_VAR) {
+        Ok(path) if !path.is_empty() => {
+            println!("cargo:rerun-if-changed={path}");
+            path
+        }
+        _ => BUNDLED.to_string(),
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read postal directory {path}: {e}"));
+    // The Swiss Post export (as fetched by scripts/update_postal_directory.sh)
+    // is complete; a hand-picked snapshot says otherwise with a marker line.
+    let complete = !content.lines().any(|l| l.trim() == PARTIAL_MARKER);
+
+    let mut entries = String::new();
+    for (plz, city, canton) in read_directory(&content) {
+        entries.push_str(&format!("    ({plz}, {city:?}, {canton:?}),\n"));
+    }
+
+    let generated = format!(
+        r#"// AUTO-GENERATED FROM {path}
+// ⚠️ DO NOT EDIT BY HAND
+
+/// Swiss and Liechtenstein postal codes as `(plz, locality, canton)`,
+/// sorted by postal code. One postal code can serve several localities.
+pub const POSTAL_DIRECTORY: &[(u16, &str, &str)] = &[
+{entries}];
+
+/// Whether [`POSTAL_DIRECTORY`] lists every postal code (a full Swiss Post
+/// export) or is a partial snapshot.
+pub const POSTAL_DIRECTORY_COMPLETE: bool = {complete};
+"#
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let dest_path = PathBuf::from(out_dir).join("postal_codes.rs");
+    let mut file = File::create(dest_path).expect("failed to create postal_codes.rs");
+    file.write_all(generated.as_bytes())
+        .expect("failed to write postal_codes.rs");
+}
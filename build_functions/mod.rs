@@ -3,7 +3,9 @@
  * Licensed under MIT License
  * https://opensource.org/licenses/MIT
  */
+mod build_bank_master;
 mod build_countries;
+mod build_postal_codes;
 mod parse_svg;
 
 use std::env;
@@ -20,6 +22,8 @@ const SVG_ASSETS: &[(&str, &str, &str)] = &[
 pub fn run() {
     println!("cargo:warning=build_functions.rs is running");
     build_countries::generate();
+    build_postal_codes::generate();
+    build_bank_master::generate();
 
     let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
 
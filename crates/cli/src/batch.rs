@@ -0,0 +1,441 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! `swiss_qrust batch`: renders many bills in one run.
+//!
+//! Inputs are TOML/JSON files (one bill each), directories of those, simple
+//! wildcard patterns (`bills/*.toml`), JSON files holding an array of bills
+//! and JSON Lines files (`.jsonl`, one bill per line), as well as CSV files
+//! with one debtor per row (with `--profile` and `--mapping`). A bill's own
+//! `profile` is resolved relative to the file it is in. Bills are rendered
+//! in parallel; failures don't stop the batch but are listed in the
+//! report.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use rayon::prelude::*;
+use serde_json::json;
+use swiss_qrust::diagnostics::parse_and_validate;
+use swiss_qrust::pdf::render_bills_to_pdf;
+use swiss_qrust::{import_csv, parse_bill_file, BillData, CreditorProfile, CsvMapping, InputBill, Language, ReferenceType};
+
+use crate::{extension, parse_language, render_bytes, RenderFormat};
+
+/// Placeholders understood by `--name`.
+const PLACEHOLDERS: [&str; 7] = ["index", "stem", "creditor", "debtor", "reference", "amount", "currency"];
+
+#[derive(Args)]
+pub struct BatchArgs {
+    /// Input files, directories, wildcard patterns, JSON arrays, JSON Lines or CSV files
+    #[arg(required = true)]
+    inputs: Vec<String>,
+
+    /// Directory the bills are written to; created if missing
+    #[arg(short, long, default_value = ".")]
+    output_dir: PathBuf,
+
+    /// File name template; placeholders: {index}, {stem}, {creditor}, {debtor}, {reference},
+    /// {amount}, {currency}. The extension selects the format (pdf, svg, png, txt or md)
+    #[arg(short, long, default_value = "{stem}.pdf")]
+    name: String,
+
+    /// Language of the bills: de, fr, it or en
+    #[arg(short, long, default_value = "de", value_parser = parse_language)]
+    lang: Language,
+
+    /// Resolution for PNG output
+    #[arg(long, default_value_t = 300.0)]
+    dpi: f64,
+
+    /// Number of parallel jobs; all cores if omitted
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Additionally merge all rendered bills into this multi-page PDF
+    #[arg(long)]
+    merge: Option<PathBuf>,
+
+    /// Write a JSON report with the outcome per input to this file
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Creditor profile (TOML) for CSV inputs
+    #[arg(long, requires = "mapping")]
+    profile: Option<PathBuf>,
+
+    /// Column mapping (TOML) for CSV inputs
+    #[arg(long, requires = "profile")]
+    mapping: Option<PathBuf>,
+}
+
+/// Creditor profile and column mapping for CSV inputs.
+type CsvSetup = (CreditorProfile, CsvMapping);
+
+/// One bill of the batch.
+struct Entry {
+    /// Where the bill came from, e.g. `bills.jsonl:3`.
+    source: String,
+    /// File stem, plus the position for files with several bills.
+    stem: String,
+    bill: Result<BillData, String>,
+}
+
+/// Outcome of one entry, in input order.
+struct Outcome {
+    source: String,
+    output: Option<PathBuf>,
+    error: Option<String>,
+}
+
+pub fn run(args: BatchArgs) -> Result<ExitCode> {
+    check_template(&args.name)?;
+    let format = RenderFormat::from_path(&args.name);
+
+    let csv_setup = match (&args.profile, &args.mapping) {
+        (Some(profile), Some(mapping)) => Some((read_toml(profile)?, read_toml(mapping)?)),
+        _ => None,
+    };
+
+    let mut entries = Vec::new();
+    for path in expand_inputs(&args.inputs)? {
+        entries.extend(load_entries(&path, csv_setup.as_ref())?);
+    }
+    if entries.is_empty() {
+        bail!("no bills found in {}", args.inputs.join(", "));
+    }
+
+    fs::create_dir_all(&args.output_dir)
+        .with_context(|| format!("cannot create {}", args.output_dir.display()))?;
+
+    // Names are assigned up front so duplicates get a stable suffix
+    // regardless of the order in which the jobs finish.
+    let mut taken = HashSet::new();
+    let outputs: Vec<Option<PathBuf>> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let bill = entry.bill.as_ref().ok()?;
+            let name = unique_name(&fill_template(&args.name, i + 1, &entry.stem, bill), &mut taken);
+            Some(args.output_dir.join(name))
+        })
+        .collect();
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(args.jobs.unwrap_or(0)).build()?;
+    let outcomes: Vec<Outcome> = pool.install(|| {
+        entries
+            .par_iter()
+            .zip(&outputs)
+            .map(|(entry, output)| {
+                let error = match (&entry.bill, output) {
+                    (Ok(bill), Some(path)) => render_bytes(bill, format, args.lang, args.dpi)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|bytes| {
+                            fs::write(path, bytes).with_context(|| format!("cannot write {}", path.display()))
+                        })
+                        .err()
+                        .map(|e| e.to_string()),
+                    (Err(e), _) => Some(e.clone()),
+                    (Ok(_), None) => unreachable!("every valid bill has an output name"),
+                };
+                Outcome { source: entry.source.clone(), output: output.clone().filter(|_| error.is_none()), error }
+            })
+            .collect()
+    });
+
+    if let Some(merge) = &args.merge {
+        let bills: Vec<BillData> = entries.into_iter().filter_map(|entry| entry.bill.ok()).collect();
+        if bills.is_empty() {
+            eprintln!("warning: no valid bill to merge, {} not written", merge.display());
+        } else {
+            let pdf = render_bills_to_pdf(&bills, args.lang)?;
+            fs::write(merge, pdf).with_context(|| format!("cannot write {}", merge.display()))?;
+        }
+    }
+
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    for outcome in &outcomes {
+        if let Some(error) = &outcome.error {
+            eprintln!("error: {}: {error}", outcome.source);
+        }
+    }
+    if let Some(report) = &args.report {
+        fs::write(report, serde_json::to_string_pretty(&report_json(&outcomes))? + "\n")
+            .with_context(|| format!("cannot write {}", report.display()))?;
+    }
+    println!("{} bills rendered, {failed} failed", outcomes.len() - failed);
+
+    Ok(if failed == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn report_json(outcomes: &[Outcome]) -> serde_json::Value {
+    outcomes
+        .iter()
+        .map(|o| match &o.error {
+            None => json!({ "input": o.source, "status": "ok", "output": o.output.as_ref().map(|p| p.display().to_string()) }),
+            Some(error) => json!({ "input": o.source, "status": "error", "error": error }),
+        })
+        .collect()
+}
+
+/// Resolves directories and wildcard patterns into the files to read, in
+/// a stable (sorted) order.
+fn expand_inputs(inputs: &[String]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+
+        if file_name.contains(['*', '?']) {
+            let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+            files.extend(list_dir(dir, |name| wildcard_match(&file_name, name))?);
+        } else if path.is_dir() {
+            files.extend(list_dir(path, |name| {
+                matches!(extension(name).as_deref(), Some("toml" | "json" | "jsonl" | "ndjson" | "csv"))
+            })?);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+fn list_dir(dir: &Path, filter: impl Fn(&str) -> bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() && path.file_name().is_some_and(|name| filter(&name.to_string_lossy())) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// `*` matches any run of characters, `?` exactly one.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Reads the bills in `path`. A file that can't be read at all aborts the
+/// batch; invalid bills become failed entries.
+fn load_entries(path: &Path, csv_setup: Option<&CsvSetup>) -> Result<Vec<Entry>> {
+    let content = fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let file = path.display().to_string();
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = extension(&file).unwrap_or_default();
+    // Profiles named by the records are relative to the input file, as for
+    // single bills.
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+
+    let items: Vec<(String, String, Result<InputBill, String>)> = match ext.as_str() {
+        "jsonl" | "ndjson" => content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (format!("{file}:{}", i + 1), format!("{stem}-{}", i + 1), parse_record(line, base_dir)))
+            .collect(),
+        "csv" => {
+            let Some((profile, mapping)) = csv_setup else {
+                bail!("{file}: CSV input needs --profile and --mapping");
+            };
+            let rows = import_csv(&content, profile, mapping).with_context(|| format!("cannot import {file}"))?;
+            return Ok(rows
+                .into_iter()
+                .map(|row| Entry {
+                    source: format!("{file}:{}", row.line),
+                    stem: format!("{stem}-{}", row.line),
+                    bill: row.bill.map_err(|e| e.to_string()),
+                })
+                .collect());
+        }
+        "json" if content.trim_start().starts_with('[') => {
+            let values: Vec<serde_json::Value> =
+                serde_json::from_str(&content).with_context(|| format!("cannot parse {file}"))?;
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    (format!("{file}[{i}]"), format!("{stem}-{}", i + 1), parse_record(&value.to_string(), base_dir))
+                })
+                .collect()
+        }
+        // A single bill: the diagnostics name the offending field.
+        _ => {
+            let bill = parse_and_validate(&content, &ext, &file).map_err(|d| match d.field {
+                Some(field) => format!("{} ({field})", d.message),
+                None => d.message,
+            });
+            return Ok(vec![Entry { source: file, stem, bill }]);
+        }
+    };
+
+    Ok(items
+        .into_iter()
+        .map(|(source, stem, input)| Entry {
+            source,
+            stem,
+            bill: input.and_then(|input| BillData::try_from(input).map_err(|e| e.to_string())),
+        })
+        .collect())
+}
+
+/// Parses one JSON record of a JSONL file or JSON array, merging in its
+/// `profile`.
+fn parse_record(json: &str, base_dir: &Path) -> Result<InputBill, String> {
+    parse_bill_file(json, "json", base_dir).map_err(|e| e.to_string())
+}
+
+fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("cannot parse {}", path.display()))
+}
+
+fn check_template(template: &str) -> Result<()> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            bail!("unclosed placeholder in name template '{template}'");
+        };
+        let placeholder = &rest[start + 1..start + end];
+        if !PLACEHOLDERS.contains(&placeholder) {
+            bail!("unknown placeholder {{{placeholder}}} in name template, expected one of {}", PLACEHOLDERS.join(", "));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    Ok(())
+}
+
+fn fill_template(template: &str, index: usize, stem: &str, bill: &BillData) -> String {
+    let reference = match &bill.reference_type {
+        ReferenceType::NoRef => "",
+        ReferenceType::QrRef(reference) | ReferenceType::Creditor(reference) => reference,
+    };
+    let values = [
+        index.to_string(),
+        stem.to_string(),
+        bill.creditor_address.name.clone(),
+        bill.debtor_address.as_ref().map(|a| a.name.clone()).unwrap_or_default(),
+        reference.to_string(),
+        bill.amount.clone().unwrap_or_default(),
+        bill.currency.to_string(),
+    ];
+
+    PLACEHOLDERS
+        .iter()
+        .zip(values)
+        .fold(template.to_string(), |name, (placeholder, value)| {
+            name.replace(&format!("{{{placeholder}}}"), &file_name_safe(&value))
+        })
+}
+
+/// Keeps letters, digits, `.`, `-` and `_`; everything else becomes `_`.
+fn file_name_safe(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect()
+}
+
+/// Appends `-2`, `-3`, ... before the extension until `name` is unused.
+fn unique_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let (base, ext) = match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    };
+    let mut candidate = name.to_string();
+    let mut n = 1;
+    while !taken.insert(candidate.clone()) {
+        n += 1;
+        candidate = format!("{base}-{n}{ext}");
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*.toml", "bill.toml"));
+        assert!(wildcard_match("bill-?.json", "bill-1.json"));
+        assert!(wildcard_match("a*b*c", "axxbyyc"));
+        assert!(!wildcard_match("*.toml", "bill.json"));
+        assert!(!wildcard_match("bill-?.json", "bill-10.json"));
+    }
+
+    #[test]
+    fn templates() {
+        let bill = swiss_qrust::build_bill().unwrap();
+        assert!(check_template("{creditor}-{reference}.pdf").is_ok());
+        assert!(check_template("{creditr}.pdf").is_err());
+        assert!(check_template("{creditor.pdf").is_err());
+        assert_eq!(
+            fill_template("{index}-{creditor}-{reference}.pdf", 7, "x", &bill),
+            "7-Health_insurance_fit_kicking-000008207791225857421286694.pdf"
+        );
+
+        let mut taken = HashSet::new();
+        assert_eq!(unique_name("a.pdf", &mut taken), "a.pdf");
+        assert_eq!(unique_name("a.pdf", &mut taken), "a-2.pdf");
+        assert_eq!(unique_name("a.pdf", &mut taken), "a-3.pdf");
+    }
+
+    #[test]
+    fn records_merge_their_profile() {
+        let dir = std::env::temp_dir().join(format!("swiss_qrust_batch_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("creditor.toml"),
+            r#"
+            iban = "CH93 0076 2011 6238 5295 7"
+            creditor_address = { name = "Robert Schneider AG", plz = "2501", city = "Biel", country = "CH" }
+            "#,
+        )
+        .unwrap();
+        let record = r#"{"profile": "creditor.toml", "amount": "250.00"}"#;
+        fs::write(dir.join("bills.jsonl"), format!("{record}\n{{\"profile\": \"missing.toml\"}}\n")).unwrap();
+        fs::write(dir.join("bills.json"), format!("[{record}]")).unwrap();
+
+        let entries = load_entries(&dir.join("bills.jsonl"), None).unwrap();
+        let bill = entries[0].bill.as_ref().unwrap();
+        assert_eq!(bill.creditor_address.name, "Robert Schneider AG");
+        assert!(entries[1].bill.as_ref().unwrap_err().contains("missing.toml"));
+
+        let entries = load_entries(&dir.join("bills.json"), None).unwrap();
+        assert_eq!(entries[0].bill.as_ref().unwrap().creditor_address.city, "Biel");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
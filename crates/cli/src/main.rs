@@ -4,51 +4,403 @@
  * https://opensource.org/licenses/MIT
  */
 
+mod batch;
+mod preview;
+
 use std::fs;
-use clap::Parser;
-use anyhow::{Result};
-use swiss_qrust::{BillData};
-use swiss_qrust::pdf::create_pdf;
+use std::io::Read;
+use std::path::Path;
+use std::process::ExitCode;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use swiss_qrust::diagnostics::parse_and_validate_with_warnings;
+use swiss_qrust::pdf::{render_bill_to_pdf, RenderError};
+use swiss_qrust::png_renderers::render_bill_to_png;
+use swiss_qrust::svg::render_bill_to_svg;
+use swiss_qrust::text_summary::{render_bill_to_text, TextFormat};
+use swiss_qrust::{
+    decode_qr_text, generate_iso11649_with_checksum, generate_qrr_with_checksum, is_valid_iso11649_reference,
+    is_valid_qr_reference, BankMaster, BillData, Iban, Language, BANK_MASTER_SNAPSHOT_IS_SAMPLE,
+};
 
 #[derive(Parser)]
 #[command(name = "swiss_qrust")]
 #[command(about = "Swiss QR Bill generator CLI", long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Render a bill to PDF, SVG or PNG
+    Render(RenderArgs),
+    /// Print a bill as text, with the QR code drawn in Unicode blocks
+    Preview(PreviewArgs),
+    /// Render many bills in parallel
+    Batch(batch::BatchArgs),
+    /// Validate a bill; exits with 1 if it is invalid
+    Validate(ValidateArgs),
+    /// Decode a QR-bill payload into the input format
+    Decode(DecodeArgs),
+    /// Generate or check payment references
+    #[command(subcommand)]
+    Reference(ReferenceCommand),
+    /// Check or format IBANs
+    #[command(subcommand)]
+    Iban(IbanCommand),
+    /// Print the JSON Schema of the input format
+    Schema,
+}
+
+#[derive(Args)]
+struct RenderArgs {
     /// Input file (TOML or JSON)
     #[arg(short, long)]
     input: String,
 
-    /// Output PDF file
+    /// Output file
     #[arg(short, long)]
     output: String,
 
-    /// Language for PDF (e.g., "de", "fr", "it")
-    #[arg(short, long, default_value = "de")]
-    lang: Option<String>,
+    /// Output format; taken from the output file extension if omitted, PDF otherwise
+    #[arg(short, long, value_enum)]
+    format: Option<RenderFormat>,
+
+    /// Language of the bill: de, fr, it or en
+    #[arg(short, long, default_value = "de", value_parser = parse_language)]
+    lang: Language,
+
+    /// Resolution for PNG output
+    #[arg(long, default_value_t = 300.0)]
+    dpi: f64,
+
+    /// Warn about postal codes that don't match the town or the country's format
+    #[arg(long)]
+    check_postal_codes: bool,
+}
+
+#[derive(Args)]
+struct PreviewArgs {
+    /// Input file (TOML or JSON)
+    #[arg(short, long)]
+    input: String,
+
+    /// Language of the bill: de, fr, it or en
+    #[arg(short, long, default_value = "de", value_parser = parse_language)]
+    lang: Language,
+
+    /// Draw dark modules instead of light ones, for terminals with a light background
+    #[arg(long)]
+    invert: bool,
+
+    /// Warn about postal codes that don't match the town or the country's format
+    #[arg(long)]
+    check_postal_codes: bool,
+}
+
+#[derive(Args)]
+struct ValidateArgs {
+    /// Input file (TOML or JSON)
+    #[arg(short, long)]
+    input: String,
+
+    /// Warn about postal codes that don't match the town or the country's format
+    #[arg(long)]
+    check_postal_codes: bool,
+}
+
+#[derive(Args)]
+struct DecodeArgs {
+    /// File with the QR code payload; read from stdin if omitted
+    #[arg(short, long)]
+    input: Option<String>,
+
+    /// Output file; written to stdout if omitted
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Output format; taken from the output file extension if omitted, TOML otherwise
+    #[arg(short, long, value_enum)]
+    format: Option<DataFormat>,
+}
+
+#[derive(Subcommand)]
+enum ReferenceCommand {
+    /// Append the check digit(s) to a raw reference
+    Generate {
+        /// Reference type
+        #[arg(short, long, value_enum, default_value = "qrr")]
+        kind: ReferenceKind,
+        /// Digits (QRR) or alphanumeric characters (SCOR) without check digits; may be grouped
+        #[arg(required = true)]
+        raw: Vec<String>,
+    },
+    /// Check a QR reference or, if it starts with RF, a creditor reference
+    Check {
+        #[arg(required = true)]
+        reference: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum IbanCommand {
+    /// Check an IBAN and look up its bank
+    Check {
+        #[arg(required = true)]
+        iban: Vec<String>,
+        /// SIX bank master (CSV or JSON); required unless one was embedded at
+        /// build time with SWISS_QRUST_BANK_MASTER
+        #[arg(long)]
+        bank_master: Option<String>,
+    },
+    /// Print an IBAN in paper format
+    Format {
+        #[arg(required = true)]
+        iban: Vec<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RenderFormat {
+    Pdf,
+    Svg,
+    Png,
+    /// Plain text of the payment part, e.g. for an e-mail body
+    Txt,
+    /// Markdown of the payment part
+    Md,
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+impl RenderFormat {
+    /// The format of an output file, PDF if the extension says nothing.
+    fn from_path(path: &str) -> Self {
+        match extension(path).as_deref() {
+            Some("svg") => RenderFormat::Svg,
+            Some("png") => RenderFormat::Png,
+            Some("txt") => RenderFormat::Txt,
+            Some("md") => RenderFormat::Md,
+            _ => RenderFormat::Pdf,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DataFormat {
+    Toml,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReferenceKind {
+    /// QR reference (27 digits, mod 10 recursive)
+    Qrr,
+    /// Creditor reference (ISO 11649, RF…)
+    Scor,
+}
+
+fn parse_language(value: &str) -> Result<Language, String> {
+    value.parse().map_err(|e: swiss_qrust::LanguageError| format!("{e}, got '{value}'"))
+}
+
+/// The lower-case extension of `path`, if any.
+fn extension(path: &str) -> Option<String> {
+    Path::new(path).extension().map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+}
 
-    // --- 1. Load input file ---
-    let content = fs::read_to_string(&cli.input)?;
-    let ext = cli.input.split('.').last().unwrap_or("");
-    let input_bill = swiss_qrust::parse_bill_data(&content, ext)?;
+fn main() -> Result<ExitCode> {
+    match Cli::parse().command {
+        Command::Render(args) => render(args),
+        Command::Preview(args) => {
+            let Some(bill_data) = load(&args.input, args.check_postal_codes)? else {
+                return Ok(ExitCode::FAILURE);
+            };
+            print!("{}", preview::preview(&bill_data, args.lang, args.invert));
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Batch(args) => batch::run(args),
+        Command::Validate(args) => validate(args),
+        Command::Decode(args) => decode(args),
+        Command::Reference(command) => reference(command),
+        Command::Iban(command) => iban(command),
+        Command::Schema => {
+            println!("{}", serde_json::to_string_pretty(&swiss_qrust::input_bill_schema())?);
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
 
-    // --- 2. Convert to internal BillData ---
-    let bill_data: BillData = input_bill.try_into()?; // your TryFrom impl
+/// Loads and validates `input`, printing the diagnostic on failure.
+fn load(input: &str, check_postal_codes: bool) -> Result<Option<BillData>> {
+    let content = fs::read_to_string(input).with_context(|| format!("cannot read {input}"))?;
+    let ext = extension(input).unwrap_or_default();
 
-    // --- 3. Convert language ---
-    let language = match cli.lang.as_deref() {
-        Some("de") => swiss_qrust::Language::De,
-        Some("fr") => swiss_qrust::Language::Fr,
-        Some("it") => swiss_qrust::Language::It,
-        _ => swiss_qrust::Language::En,
+    let (bill_data, sanitized) = match parse_and_validate_with_warnings(&content, &ext, input) {
+        Ok(validated) => validated,
+        Err(diagnostic) => {
+            eprintln!("{diagnostic}");
+            return Ok(None);
+        }
     };
 
-    // --- 4. Generate PDF ---
-    create_pdf(&cli.output, language, &bill_data)?;
+    for field in &sanitized {
+        eprintln!("warning: {field}\n  --> {input} ({})", field.field);
+    }
+
+    if check_postal_codes {
+        for (field, warning) in bill_data.postal_code_warnings() {
+            eprintln!("warning: {warning}\n  --> {input} ({field})");
+        }
+    }
+    Ok(Some(bill_data))
+}
+
+fn render(args: RenderArgs) -> Result<ExitCode> {
+    let Some(bill_data) = load(&args.input, args.check_postal_codes)? else {
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let format = args.format.unwrap_or_else(|| RenderFormat::from_path(&args.output));
+
+    let bytes = render_bytes(&bill_data, format, args.lang, args.dpi)?;
+    fs::write(&args.output, bytes).with_context(|| format!("cannot write {}", args.output))?;
+
+    println!("Bill successfully written to {}", args.output);
+    Ok(ExitCode::SUCCESS)
+}
 
-    println!("PDF successfully written to {}", &cli.output);
-    Ok(())
-}
\ No newline at end of file
+fn render_bytes(bill: &BillData, format: RenderFormat, language: Language, dpi: f64) -> Result<Vec<u8>, RenderError> {
+    match format {
+        RenderFormat::Pdf => render_bill_to_pdf(bill, language),
+        RenderFormat::Svg => render_bill_to_svg(bill, language).map(String::into_bytes),
+        RenderFormat::Png => render_bill_to_png(bill, language, dpi),
+        RenderFormat::Txt => Ok(render_bill_to_text(bill, language, TextFormat::Plain).into_bytes()),
+        RenderFormat::Md => Ok(render_bill_to_text(bill, language, TextFormat::Markdown).into_bytes()),
+    }
+}
+
+fn validate(args: ValidateArgs) -> Result<ExitCode> {
+    Ok(match load(&args.input, args.check_postal_codes)? {
+        Some(_) => {
+            println!("{}: OK", args.input);
+            ExitCode::SUCCESS
+        }
+        None => ExitCode::FAILURE,
+    })
+}
+
+fn decode(args: DecodeArgs) -> Result<ExitCode> {
+    let payload = match &args.input {
+        Some(path) => fs::read_to_string(path).with_context(|| format!("cannot read {path}"))?,
+        None => {
+            let mut payload = String::new();
+            std::io::stdin().read_to_string(&mut payload)?;
+            payload
+        }
+    };
+
+    let input_bill = decode_qr_text(&payload)?;
+    // The payload is structurally fine; content errors are reported but the
+    // data is still written so it can be fixed by hand.
+    if let Err(e) = BillData::try_from(input_bill.clone()) {
+        eprintln!("warning: the decoded bill is not valid: {e}");
+    }
+
+    let format = args.format.unwrap_or(match args.output.as_deref().and_then(extension).as_deref() {
+        Some("json") => DataFormat::Json,
+        _ => DataFormat::Toml,
+    });
+    let text = match format {
+        DataFormat::Toml => toml::to_string(&input_bill)?,
+        DataFormat::Json => serde_json::to_string_pretty(&input_bill)? + "\n",
+    };
+
+    match &args.output {
+        Some(path) => fs::write(path, text).with_context(|| format!("cannot write {path}"))?,
+        None => print!("{text}"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn reference(command: ReferenceCommand) -> Result<ExitCode> {
+    match command {
+        ReferenceCommand::Generate { kind, raw } => {
+            let raw = raw.concat();
+            let reference = match kind {
+                ReferenceKind::Qrr => generate_qrr_with_checksum(&raw),
+                ReferenceKind::Scor => generate_iso11649_with_checksum(&raw),
+            }
+            .with_context(|| format!("cannot generate a reference from '{raw}'"))?;
+            println!("{reference}");
+            Ok(ExitCode::SUCCESS)
+        }
+        ReferenceCommand::Check { reference } => {
+            let reference = reference.concat();
+            Ok(match check_reference(&reference) {
+                Ok(kind) => {
+                    println!("{reference}: valid {kind}");
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("{reference}: {e}");
+                    ExitCode::FAILURE
+                }
+            })
+        }
+    }
+}
+
+/// Validates a QR or, if prefixed with RF, creditor reference and returns its kind.
+fn check_reference(reference: &str) -> Result<&'static str> {
+    if reference.trim_start().to_ascii_uppercase().starts_with("RF") {
+        is_valid_iso11649_reference(reference)?;
+        Ok("creditor reference (SCOR)")
+    } else {
+        is_valid_qr_reference(reference)?;
+        Ok("QR reference (QRR)")
+    }
+}
+
+fn iban(command: IbanCommand) -> Result<ExitCode> {
+    match command {
+        IbanCommand::Format { iban } => {
+            println!("{}", Iban::parse(&iban.concat())?);
+            Ok(ExitCode::SUCCESS)
+        }
+        IbanCommand::Check { iban, bank_master } => {
+            if bank_master.is_none() && BANK_MASTER_SNAPSHOT_IS_SAMPLE {
+                bail!("no bank master is embedded in this build; pass --bank-master with the SIX bank master");
+            }
+            let iban = iban.concat();
+            let parsed = match Iban::parse(&iban) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("{iban}: {e}");
+                    return Ok(ExitCode::FAILURE);
+                }
+            };
+            let kind = if parsed.is_qr_iban() { "QR-IBAN" } else { "IBAN" };
+            println!("{parsed}: valid {kind}");
+
+            let loaded;
+            let master = match bank_master {
+                Some(path) => {
+                    loaded = BankMaster::from_path(&path).with_context(|| format!("cannot load {path}"))?;
+                    &loaded
+                }
+                None => BankMaster::embedded(),
+            };
+            match master.lookup_iban(parsed.as_str()) {
+                Ok(bank) => {
+                    println!("  bank: {}", bank.name);
+                    if let Some(bic) = &bank.bic {
+                        println!("  BIC:  {bic}");
+                    }
+                }
+                Err(e) => eprintln!("warning: {e}"),
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! `swiss_qrust preview`: the bill as text, for terminals without a PDF
+//! viewer (e.g. over SSH).
+//!
+//! Receipt and payment part are printed side by side with the same labels
+//! and formatting as the graphical slip, followed by the QR code drawn with
+//! Unicode half blocks (two modules per character cell). The Swiss cross
+//! isn't drawn; the code scans without it.
+
+use swiss_qrust::bill::qr_bill::qr_code;
+use swiss_qrust::traits::{SliceExt, SwissQRFormatter};
+use swiss_qrust::{label, BillData, LabelKey, Language, ReferenceType};
+
+/// Stand-in for the corner-marked boxes left empty for handwriting.
+const BLANK: &str = "[                    ]";
+/// Light modules around the code, as required by the QR spec.
+const QUIET_ZONE: i32 = 4;
+
+fn text(key: LabelKey, language: Language) -> &'static str {
+    label(key, language).unwrap_or_default()
+}
+
+/// Label, value lines and a blank line; nothing if there are no values.
+fn section(lines: &mut Vec<String>, heading: &str, values: impl IntoIterator<Item = String>) {
+    let values: Vec<String> = values.into_iter().collect();
+    if values.is_empty() {
+        return;
+    }
+    lines.push(heading.to_string());
+    lines.extend(values);
+    lines.push(String::new());
+}
+
+fn reference(bill: &BillData) -> Option<String> {
+    match &bill.reference_type {
+        ReferenceType::QrRef(reference) => Some(reference.format_qr_reference()),
+        ReferenceType::Creditor(reference) => Some(reference.format_scor_reference()),
+        ReferenceType::NoRef => None,
+    }
+}
+
+/// The fields shared by both parts, in slip order; `payment_part` adds
+/// the additional information.
+fn information(bill: &BillData, language: Language, payment_part: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let mut account = vec![bill.iban.as_str().format_iban()];
+    account.extend(bill.creditor_address.to_lines().all_but_last().iter().cloned());
+    section(&mut lines, text(LabelKey::AccountPayableTo, language), account);
+    section(&mut lines, text(LabelKey::Reference, language), reference(bill));
+
+    if payment_part {
+        let additional = [&bill.unstructured_message, &bill.bill_information].into_iter().flatten().cloned();
+        section(&mut lines, text(LabelKey::AdditionalInformation, language), additional);
+    } else if bill.is_notification()
+        && let Some(notice) = &bill.unstructured_message
+    {
+        lines.push(notice.clone());
+        lines.push(String::new());
+    }
+
+    match &bill.debtor_address {
+        Some(debtor) => section(
+            &mut lines,
+            text(LabelKey::PayableBy, language),
+            debtor.to_lines().all_but_last().iter().cloned(),
+        ),
+        None => section(&mut lines, text(LabelKey::PayableByNameAddress, language), [BLANK.to_string()]),
+    }
+    lines
+}
+
+fn amount_lines(bill: &BillData, language: Language) -> Vec<String> {
+    let currency = text(LabelKey::Currency, language);
+    let amount = bill.amount.as_deref().map_or(BLANK.to_string(), |a| a.format_amount());
+    let width = currency.chars().count().max(3) + 2;
+    vec![
+        format!("{currency:width$}{}", text(LabelKey::Amount, language)),
+        format!("{:width$}{amount}", bill.currency.to_string()),
+    ]
+}
+
+fn receipt(bill: &BillData, language: Language) -> Vec<String> {
+    let mut lines = vec![text(LabelKey::Receipt, language).to_uppercase(), String::new()];
+    lines.extend(information(bill, language, false));
+    lines.extend(amount_lines(bill, language));
+    lines.push(String::new());
+    lines.push(text(LabelKey::AcceptancePoint, language).to_string());
+    lines
+}
+
+fn payment_part(bill: &BillData, language: Language) -> Vec<String> {
+    let mut lines = vec![text(LabelKey::PaymentPart, language).to_uppercase(), String::new()];
+    lines.extend(information(bill, language, true));
+    lines.extend(amount_lines(bill, language));
+    if bill.alternative_schemes.iter().any(Option::is_some) {
+        lines.push(String::new());
+        lines.extend(bill.alternative_schemes.iter().flatten().map(ToString::to_string));
+    }
+    lines
+}
+
+/// The QR code in half blocks. Light modules are drawn, so the code reads
+/// correctly on a dark terminal background; `invert` is for light ones.
+fn qr_lines(bill: &BillData, invert: bool) -> Vec<String> {
+    let Ok(qr) = qr_code(bill) else {
+        return vec!["(QR code could not be generated)".to_string()];
+    };
+    let light = |x: i32, y: i32| {
+        let inside = (0..qr.size()).contains(&x) && (0..qr.size()).contains(&y);
+        (!inside || !qr.get_module(x, y)) != invert
+    };
+
+    let range = -QUIET_ZONE..qr.size() + QUIET_ZONE;
+    range
+        .clone()
+        .step_by(2)
+        .map(|y| {
+            range
+                .clone()
+                .map(|x| match (light(x, y), light(x, y + 1) && y + 1 < range.end) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The complete preview, lines joined with `\n`.
+pub fn preview(bill: &BillData, language: Language, invert: bool) -> String {
+    let left = receipt(bill, language);
+    let right = payment_part(bill, language);
+    let width = left.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for i in 0..left.len().max(right.len()) {
+        let l = left.get(i).map_or("", String::as_str);
+        let r = right.get(i).map_or("", String::as_str);
+        let line = format!("{l:width$} │ {r}");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out.push('\n');
+    for line in qr_lines(bill, invert) {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_labels_and_formatted_values() {
+        let bill = swiss_qrust::build_bill().unwrap();
+        let text = preview(&bill, Language::De, false);
+
+        assert!(text.starts_with("EMPFANGSSCHEIN"));
+        assert!(text.contains("│ ZAHLTEIL"));
+        assert!(text.contains("CH64 3196 1000 0044 2155 7"));
+        assert!(text.contains("00 00082 07791 22585 74212 86694"));
+        assert!(text.contains("Annahmestelle"));
+    }
+
+    #[test]
+    fn qr_code_has_quiet_zone_and_two_modules_per_cell() {
+        let bill = swiss_qrust::build_bill().unwrap();
+        let size = qr_code(&bill).unwrap().size() as usize;
+        let lines = qr_lines(&bill, false);
+
+        assert_eq!(lines.len(), (size + 2 * QUIET_ZONE as usize).div_ceil(2));
+        assert!(lines.iter().all(|l| l.chars().count() == size + 2 * QUIET_ZONE as usize));
+        // The quiet zone (light) spans the first two rows.
+        assert!(lines[0].chars().all(|c| c == '█'));
+    }
+}
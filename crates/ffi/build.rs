@@ -0,0 +1,21 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+// Generates the C header from src/lib.rs into OUT_DIR. The committed
+// include/swiss_qrust.h is what consumers use (so they don't need
+// cbindgen); tests/header.rs fails when it is out of date.
+
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml")).expect("cbindgen.toml");
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("cannot generate C header")
+        .write_to_file(format!("{out_dir}/swiss_qrust.h"));
+}
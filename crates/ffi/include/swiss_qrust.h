@@ -0,0 +1,137 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+#ifndef SWISS_QRUST_H
+#define SWISS_QRUST_H
+
+/* Generated by cbindgen from crates/ffi/src/lib.rs - do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of every call. The numeric values are part of the ABI and don't
+// change.
+typedef enum SqrStatus {
+  SQR_STATUS_OK = 0,
+  // A required pointer was NULL or a string wasn't UTF-8.
+  SQR_STATUS_INVALID_ARGUMENT = 1,
+  // The bill isn't well-formed JSON.
+  SQR_STATUS_INVALID_JSON = 2,
+  // The bill is JSON but not a valid bill; see the error field.
+  SQR_STATUS_INVALID_BILL = 3,
+  // The raw reference can't be turned into a valid reference.
+  SQR_STATUS_INVALID_REFERENCE = 4,
+  SQR_STATUS_RENDER_FAILED = 5,
+  // A bug in the library; please report it with the message.
+  SQR_STATUS_INTERNAL_ERROR = 99,
+} SqrStatus;
+
+// Language of the slip's labels. Passed to the render functions as a
+// plain `uint32_t`, so an out-of-range value is an error rather than
+// undefined behavior.
+typedef enum SqrLanguage {
+  SQR_LANGUAGE_DE = 0,
+  SQR_LANGUAGE_FR = 1,
+  SQR_LANGUAGE_IT = 2,
+  SQR_LANGUAGE_EN = 3,
+} SqrLanguage;
+
+// A validated bill, created by [`sqr_bill_from_json`] and released with
+// [`sqr_bill_free`].
+typedef struct SqrBill SqrBill;
+
+// Bytes owned by the library; release with [`sqr_buffer_free`].
+typedef struct SqrBuffer {
+  uint8_t *data;
+  size_t len;
+} SqrBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parses and validates a bill.
+//
+// # Safety
+// `json` must be a NUL-terminated string and `out` must point to writable
+// memory for one pointer.
+enum SqrStatus sqr_bill_from_json(const char *json, struct SqrBill **out);
+
+// Validates a bill without keeping it. `SQR_STATUS_INVALID_BILL` comes
+// with the offending field, e.g. `creditor_address.city`.
+//
+// # Safety
+// `json` must be a NUL-terminated string.
+enum SqrStatus sqr_validate_json(const char *json);
+
+// Releases a bill. NULL is ignored.
+//
+// # Safety
+// `bill` must come from [`sqr_bill_from_json`] and not be used afterwards.
+void sqr_bill_free(struct SqrBill *bill);
+
+// Renders the bill as a single-page A4 PDF. `language` is one of the
+// [`SqrLanguage`] values; anything else is `SQR_STATUS_INVALID_ARGUMENT`.
+//
+// # Safety
+// `bill` must be a live bill from [`sqr_bill_from_json`], `out` must point
+// to writable memory for an [`SqrBuffer`].
+enum SqrStatus sqr_render_pdf(const struct SqrBill *bill, uint32_t language, struct SqrBuffer *out);
+
+// Renders the bill as SVG (UTF-8, not NUL-terminated). `language` as for
+// [`sqr_render_pdf`].
+//
+// # Safety
+// As for [`sqr_render_pdf`].
+enum SqrStatus sqr_render_svg(const struct SqrBill *bill, uint32_t language, struct SqrBuffer *out);
+
+// Releases a buffer. An empty buffer (`data` NULL) is ignored.
+//
+// # Safety
+// `buffer` must come from this library, unchanged, and not be used
+// afterwards.
+void sqr_buffer_free(struct SqrBuffer buffer);
+
+// Appends the check digit to 1 to 26 digits (spaces allowed), giving a
+// 27-digit QR reference.
+//
+// # Safety
+// `raw` must be a NUL-terminated string and `out` writable; the result is
+// released with [`sqr_string_free`].
+enum SqrStatus sqr_generate_qr_reference(const char *raw, char **out);
+
+// Turns alphanumeric characters into an ISO 11649 creditor reference
+// (`RF..`).
+//
+// # Safety
+// As for [`sqr_generate_qr_reference`].
+enum SqrStatus sqr_generate_scor_reference(const char *raw, char **out);
+
+// Releases a string returned by the library. NULL is ignored.
+//
+// # Safety
+// `text` must come from this library and not be used afterwards.
+void sqr_string_free(char *text);
+
+// Message of the last failed call on this thread, NULL if it succeeded.
+// Owned by the library, valid until the next call on this thread.
+const char *sqr_last_error_message(void);
+
+// Dotted path of the field that made the last bill invalid (e.g.
+// `debtor_address.plz`), NULL if unknown or there was no error.
+const char *sqr_last_error_field(void);
+
+// Version of the library, e.g. `0.1.0`. Static, never freed.
+const char *sqr_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SWISS_QRUST_H */
@@ -0,0 +1,372 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! C ABI for `swiss_qrust`, for callers in C, C++, Delphi and anything else
+//! that can load a C library. The header is `include/swiss_qrust.h`,
+//! generated by cbindgen; `tests/header.rs` keeps it in sync.
+//!
+//! Conventions:
+//!
+//! - Every function returns an [`SqrStatus`]; results come back through
+//!   out parameters, which are only written on `SQR_STATUS_OK`.
+//! - On failure, [`sqr_last_error_message`] (and, for invalid bills,
+//!   [`sqr_last_error_field`]) describe the error. Both are per thread and
+//!   valid until the next call into the library on that thread.
+//! - Whatever the library hands out is released with the matching
+//!   `sqr_*_free` function, never with `free()`.
+//! - Strings are NUL-terminated UTF-8. Bills are JSON in the input format
+//!   of the CLI (`InputBill`); a `profile` is resolved relative to the
+//!   working directory.
+//! - Panics don't cross the boundary; they are reported as
+//!   `SQR_STATUS_INTERNAL_ERROR`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use swiss_qrust::diagnostics::parse_and_validate;
+use swiss_qrust::pdf::render_bill_to_pdf;
+use swiss_qrust::svg::render_bill_to_svg;
+use swiss_qrust::{generate_iso11649_with_checksum, generate_qrr_with_checksum, BillData, Language};
+
+/// Result of every call. The numeric values are part of the ABI and don't
+/// change.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SqrStatus {
+    Ok = 0,
+    /// A required pointer was NULL or a string wasn't UTF-8.
+    InvalidArgument = 1,
+    /// The bill isn't well-formed JSON.
+    InvalidJson = 2,
+    /// The bill is JSON but not a valid bill; see the error field.
+    InvalidBill = 3,
+    /// The raw reference can't be turned into a valid reference.
+    InvalidReference = 4,
+    RenderFailed = 5,
+    /// A bug in the library; please report it with the message.
+    InternalError = 99,
+}
+
+/// Language of the slip's labels. Passed to the render functions as a
+/// plain `uint32_t`, so an out-of-range value is an error rather than
+/// undefined behavior.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SqrLanguage {
+    De = 0,
+    Fr = 1,
+    It = 2,
+    En = 3,
+}
+
+impl TryFrom<u32> for SqrLanguage {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, u32> {
+        match value {
+            0 => Ok(SqrLanguage::De),
+            1 => Ok(SqrLanguage::Fr),
+            2 => Ok(SqrLanguage::It),
+            3 => Ok(SqrLanguage::En),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<SqrLanguage> for Language {
+    fn from(language: SqrLanguage) -> Self {
+        match language {
+            SqrLanguage::De => Language::De,
+            SqrLanguage::Fr => Language::Fr,
+            SqrLanguage::It => Language::It,
+            SqrLanguage::En => Language::En,
+        }
+    }
+}
+
+/// A validated bill, created by [`sqr_bill_from_json`] and released with
+/// [`sqr_bill_free`].
+pub struct SqrBill(BillData);
+
+/// Bytes owned by the library; release with [`sqr_buffer_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct SqrBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl SqrBuffer {
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let bytes = Box::into_raw(bytes.into_boxed_slice());
+        Self { data: bytes.cast(), len: bytes.len() }
+    }
+}
+
+struct LastError {
+    message: CString,
+    field: Option<CString>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+/// A C string; interior NULs (which the library never produces on
+/// purpose) are dropped rather than truncating the text.
+fn c_string(text: impl Into<String>) -> CString {
+    let mut text = text.into();
+    text.retain(|c| c != '\0');
+    CString::new(text).expect("NULs removed")
+}
+
+fn fail(status: SqrStatus, message: impl Into<String>, field: Option<String>) -> SqrStatus {
+    let error = LastError { message: c_string(message), field: field.map(c_string) };
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(error));
+    status
+}
+
+/// Clears the last error, runs `body` and turns a panic into
+/// `SqrStatus::InternalError`.
+fn guard(body: impl FnOnce() -> SqrStatus) -> SqrStatus {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+        fail(SqrStatus::InternalError, message, None)
+    })
+}
+
+/// Borrows a C string argument.
+///
+/// # Safety
+/// `text` must be NULL or point to a NUL-terminated string that outlives
+/// the returned reference.
+unsafe fn str_arg<'a>(text: *const c_char, name: &str) -> Result<&'a str, SqrStatus> {
+    if text.is_null() {
+        return Err(fail(SqrStatus::InvalidArgument, format!("{name} is NULL"), None));
+    }
+    // SAFETY: non-NULL and NUL-terminated per the caller's contract.
+    unsafe { CStr::from_ptr(text) }
+        .to_str()
+        .map_err(|_| fail(SqrStatus::InvalidArgument, format!("{name} is not UTF-8"), None))
+}
+
+fn parse_bill(json: &str) -> Result<BillData, SqrStatus> {
+    if let Err(err) = serde_json::from_str::<serde_json::Value>(json) {
+        return Err(fail(SqrStatus::InvalidJson, err.to_string(), None));
+    }
+    parse_and_validate(json, "json", "bill.json")
+        .map_err(|diagnostic| fail(SqrStatus::InvalidBill, diagnostic.message, diagnostic.field))
+}
+
+/// Parses and validates a bill.
+///
+/// # Safety
+/// `json` must be a NUL-terminated string and `out` must point to writable
+/// memory for one pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_bill_from_json(json: *const c_char, out: *mut *mut SqrBill) -> SqrStatus {
+    guard(|| {
+        // SAFETY: per this function's contract.
+        let json = match unsafe { str_arg(json, "json") } {
+            Ok(json) => json,
+            Err(status) => return status,
+        };
+        if out.is_null() {
+            return fail(SqrStatus::InvalidArgument, "out is NULL", None);
+        }
+        match parse_bill(json) {
+            Ok(bill) => {
+                // SAFETY: checked for NULL above, writable per the contract.
+                unsafe { *out = Box::into_raw(Box::new(SqrBill(bill))) };
+                SqrStatus::Ok
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Validates a bill without keeping it. `SQR_STATUS_INVALID_BILL` comes
+/// with the offending field, e.g. `creditor_address.city`.
+///
+/// # Safety
+/// `json` must be a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_validate_json(json: *const c_char) -> SqrStatus {
+    guard(|| {
+        // SAFETY: per this function's contract.
+        match unsafe { str_arg(json, "json") }.and_then(parse_bill) {
+            Ok(_) => SqrStatus::Ok,
+            Err(status) => status,
+        }
+    })
+}
+
+/// Releases a bill. NULL is ignored.
+///
+/// # Safety
+/// `bill` must come from [`sqr_bill_from_json`] and not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_bill_free(bill: *mut SqrBill) {
+    if !bill.is_null() {
+        // SAFETY: allocated by `sqr_bill_from_json`, freed once per the contract.
+        drop(unsafe { Box::from_raw(bill) });
+    }
+}
+
+/// # Safety
+/// `bill` must be a live bill and `out` writable.
+unsafe fn render(
+    bill: *const SqrBill,
+    language: u32,
+    out: *mut SqrBuffer,
+    renderer: impl FnOnce(&BillData, Language) -> Result<Vec<u8>, swiss_qrust::pdf::RenderError>,
+) -> SqrStatus {
+    guard(|| {
+        if bill.is_null() || out.is_null() {
+            return fail(SqrStatus::InvalidArgument, "bill or out is NULL", None);
+        }
+        let language = match SqrLanguage::try_from(language) {
+            Ok(language) => Language::from(language),
+            Err(value) => return fail(SqrStatus::InvalidArgument, format!("unknown language {value}"), None),
+        };
+        // SAFETY: non-NULL and live per the contract.
+        let bill = unsafe { &(*bill).0 };
+        match renderer(bill, language) {
+            Ok(bytes) => {
+                // SAFETY: non-NULL and writable per the contract.
+                unsafe { *out = SqrBuffer::from_vec(bytes) };
+                SqrStatus::Ok
+            }
+            Err(err) => fail(SqrStatus::RenderFailed, err.to_string(), None),
+        }
+    })
+}
+
+/// Renders the bill as a single-page A4 PDF. `language` is one of the
+/// [`SqrLanguage`] values; anything else is `SQR_STATUS_INVALID_ARGUMENT`.
+///
+/// # Safety
+/// `bill` must be a live bill from [`sqr_bill_from_json`], `out` must point
+/// to writable memory for an [`SqrBuffer`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_render_pdf(bill: *const SqrBill, language: u32, out: *mut SqrBuffer) -> SqrStatus {
+    // SAFETY: same contract.
+    unsafe { render(bill, language, out, render_bill_to_pdf) }
+}
+
+/// Renders the bill as SVG (UTF-8, not NUL-terminated). `language` as for
+/// [`sqr_render_pdf`].
+///
+/// # Safety
+/// As for [`sqr_render_pdf`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_render_svg(bill: *const SqrBill, language: u32, out: *mut SqrBuffer) -> SqrStatus {
+    // SAFETY: same contract.
+    unsafe { render(bill, language, out, |bill, language| render_bill_to_svg(bill, language).map(String::into_bytes)) }
+}
+
+/// Releases a buffer. An empty buffer (`data` NULL) is ignored.
+///
+/// # Safety
+/// `buffer` must come from this library, unchanged, and not be used
+/// afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_buffer_free(buffer: SqrBuffer) {
+    if !buffer.data.is_null() {
+        // SAFETY: created by `SqrBuffer::from_vec` with this length.
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)) });
+    }
+}
+
+fn reference_out(out: *mut *mut c_char, generate: impl FnOnce() -> Option<String>, raw: &str) -> SqrStatus {
+    if out.is_null() {
+        return fail(SqrStatus::InvalidArgument, "out is NULL", None);
+    }
+    match generate() {
+        Some(reference) => {
+            // SAFETY: non-NULL and writable per the callers' contract.
+            unsafe { *out = c_string(reference).into_raw() };
+            SqrStatus::Ok
+        }
+        None => fail(SqrStatus::InvalidReference, format!("cannot generate a reference from '{raw}'"), None),
+    }
+}
+
+/// Appends the check digit to 1 to 26 digits (spaces allowed), giving a
+/// 27-digit QR reference.
+///
+/// # Safety
+/// `raw` must be a NUL-terminated string and `out` writable; the result is
+/// released with [`sqr_string_free`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_generate_qr_reference(raw: *const c_char, out: *mut *mut c_char) -> SqrStatus {
+    guard(|| {
+        // SAFETY: per this function's contract.
+        let raw = match unsafe { str_arg(raw, "raw") } {
+            Ok(raw) => raw,
+            Err(status) => return status,
+        };
+        reference_out(out, || generate_qrr_with_checksum(raw).ok(), raw)
+    })
+}
+
+/// Turns alphanumeric characters into an ISO 11649 creditor reference
+/// (`RF..`).
+///
+/// # Safety
+/// As for [`sqr_generate_qr_reference`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_generate_scor_reference(raw: *const c_char, out: *mut *mut c_char) -> SqrStatus {
+    guard(|| {
+        // SAFETY: per this function's contract.
+        let raw = match unsafe { str_arg(raw, "raw") } {
+            Ok(raw) => raw,
+            Err(status) => return status,
+        };
+        reference_out(out, || generate_iso11649_with_checksum(raw).ok(), raw)
+    })
+}
+
+/// Releases a string returned by the library. NULL is ignored.
+///
+/// # Safety
+/// `text` must come from this library and not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqr_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: created by `CString::into_raw` in this library.
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Message of the last failed call on this thread, NULL if it succeeded.
+/// Owned by the library, valid until the next call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn sqr_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |e| e.message.as_ptr()))
+}
+
+/// Dotted path of the field that made the last bill invalid (e.g.
+/// `debtor_address.plz`), NULL if unknown or there was no error.
+#[unsafe(no_mangle)]
+pub extern "C" fn sqr_last_error_field() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow().as_ref().and_then(|e| e.field.as_ref()).map_or(ptr::null(), |field| field.as_ptr())
+    })
+}
+
+/// Version of the library, e.g. `0.1.0`. Static, never freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn sqr_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
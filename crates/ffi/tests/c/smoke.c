@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+/* Exercises the C API the way a C caller would. Built and run by
+ * tests/c_program.rs; by hand:
+ *
+ *   cargo build -p swiss-qrust-ffi
+ *   cc crates/ffi/tests/c/smoke.c -Icrates/ffi/include -Ltarget/debug -lswiss_qrust_ffi \
+ *      -Wl,-rpath,target/debug -o smoke && ./smoke
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "swiss_qrust.h"
+
+#define CHECK(cond)                                                      \
+    do {                                                                 \
+        if (!(cond)) {                                                   \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,       \
+                    __LINE__, #cond);                                    \
+            return 1;                                                    \
+        }                                                                \
+    } while (0)
+
+static const char *BILL =
+    "{ \"iban\": \"CH4431999123000889012\", \"currency\": \"CHF\", \"amount\": \"1949.75\","
+    "  \"reference\": \"210000000003139471430009017\","
+    "  \"creditor_address\": { \"name\": \"Robert Schneider AG\", \"street\": \"Rue du Lac\","
+    "    \"house_num\": \"1268\", \"plz\": \"2501\", \"city\": \"Biel\", \"country\": \"CH\" } }";
+
+static const char *INVALID_BILL =
+    "{ \"iban\": \"CH4431999123000889012\", \"currency\": \"CHF\","
+    "  \"reference\": \"210000000003139471430009017\","
+    "  \"creditor_address\": { \"name\": \"Robert Schneider AG\","
+    "    \"plz\": \"2501\", \"city\": \"\", \"country\": \"CH\" } }";
+
+int main(void) {
+    printf("swiss_qrust %s\n", sqr_version());
+
+    SqrBill *bill = NULL;
+    CHECK(sqr_bill_from_json(BILL, &bill) == SQR_STATUS_OK);
+    CHECK(sqr_last_error_message() == NULL);
+
+    SqrBuffer pdf = {0};
+    CHECK(sqr_render_pdf(bill, SQR_LANGUAGE_FR, &pdf) == SQR_STATUS_OK);
+    CHECK(pdf.len > 1000 && memcmp(pdf.data, "%PDF", 4) == 0);
+    sqr_buffer_free(pdf);
+
+    SqrBuffer svg = {0};
+    CHECK(sqr_render_svg(bill, SQR_LANGUAGE_DE, &svg) == SQR_STATUS_OK);
+    CHECK(svg.len > 0 && memcmp(svg.data, "<svg", 4) == 0);
+    sqr_buffer_free(svg);
+
+    /* A language outside SqrLanguage is rejected, not undefined. */
+    SqrBuffer none = {0};
+    CHECK(sqr_render_pdf(bill, 4, &none) == SQR_STATUS_INVALID_ARGUMENT);
+    CHECK(sqr_render_svg(bill, 0xFFFFFFFF, &none) == SQR_STATUS_INVALID_ARGUMENT);
+    CHECK(none.data == NULL);
+    printf("bad language: %s\n", sqr_last_error_message());
+    sqr_bill_free(bill);
+
+    /* Errors: code, message and field. */
+    CHECK(sqr_validate_json(INVALID_BILL) == SQR_STATUS_INVALID_BILL);
+    CHECK(strcmp(sqr_last_error_field(), "creditor_address.city") == 0);
+    printf("invalid bill: %s (%s)\n", sqr_last_error_message(), sqr_last_error_field());
+
+    CHECK(sqr_validate_json("{") == SQR_STATUS_INVALID_JSON);
+    CHECK(sqr_last_error_field() == NULL);
+    CHECK(sqr_validate_json(NULL) == SQR_STATUS_INVALID_ARGUMENT);
+    CHECK(sqr_validate_json(BILL) == SQR_STATUS_OK);
+
+    bill = NULL;
+    CHECK(sqr_bill_from_json(INVALID_BILL, &bill) == SQR_STATUS_INVALID_BILL);
+    CHECK(bill == NULL);
+
+    /* References. */
+    char *reference = NULL;
+    CHECK(sqr_generate_qr_reference("4711", &reference) == SQR_STATUS_OK);
+    CHECK(strcmp(reference, "000000000000000000000047119") == 0);
+    sqr_string_free(reference);
+
+    CHECK(sqr_generate_scor_reference("5390 0754 7034", &reference) == SQR_STATUS_OK);
+    CHECK(strcmp(reference, "RF18539007547034") == 0);
+    sqr_string_free(reference);
+
+    CHECK(sqr_generate_qr_reference("12ab", &reference) == SQR_STATUS_INVALID_REFERENCE);
+
+    puts("ok");
+    return 0;
+}
@@ -0,0 +1,42 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Compiles tests/c/smoke.c against the generated header and the shared
+//! library, and runs it.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+// Calls `cc` with Unix linker flags.
+#[cfg(unix)]
+#[test]
+fn c_program_links_and_runs() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // target/<profile>/deps/c_program-... -> target/<profile>
+    let lib_dir = std::env::current_exe().unwrap().parent().unwrap().parent().unwrap().to_path_buf();
+    let exe = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("swiss_qrust_smoke");
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    let compiled = Command::new(&cc)
+        .arg(manifest_dir.join("tests/c/smoke.c"))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg(format!("-I{}", manifest_dir.join("include").display()))
+        .arg(format!("-L{}", lib_dir.display()))
+        .arg("-lswiss_qrust_ffi")
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-o")
+        .arg(&exe)
+        .status()
+        .unwrap_or_else(|e| panic!("cannot run {cc}: {e}"));
+    assert!(compiled.success(), "compiling smoke.c failed");
+
+    let output = Command::new(&exe).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.ends_with("ok\n"), "{stdout}");
+}
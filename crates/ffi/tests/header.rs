@@ -0,0 +1,25 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Checks that the committed include/swiss_qrust.h matches the header
+//! build.rs generates from src/lib.rs.
+
+use std::path::Path;
+
+#[test]
+fn committed_header_is_up_to_date() {
+    let generated = Path::new(env!("OUT_DIR")).join("swiss_qrust.h");
+    let committed = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/swiss_qrust.h");
+    let read =
+        |path: &Path| std::fs::read_to_string(path).unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+    assert!(
+        read(&generated) == read(&committed),
+        "{} is out of date; regenerate it with\n\n    cp {} {}\n",
+        committed.display(),
+        generated.display(),
+        committed.display()
+    );
+}
@@ -11,7 +11,8 @@ use iced::widget::{text, column, center, button, pick_list, row, container, svg}
 use iced::{Center, Element, Task, Color, Theme, Length, window, Background, Border, Shadow};
 use iced::theme::Palette;
 
-use swiss_qrust::{BillData, InputBill, Language};
+use swiss_qrust::Language;
+use swiss_qrust::diagnostics::parse_and_validate;
 use swiss_qrust::pdf::create_pdf;
 
 // Define the official Swiss Red (Pantone 485 C)
@@ -159,13 +160,17 @@ fn process_bill(in_path: &PathBuf, out_path: &PathBuf, language: Language) -> an
     let out_path_str = out_path.to_str().ok_or_else(|| anyhow::anyhow!("Invalid output path"))?;
 
     let content = std::fs::read_to_string(in_path)?;
-    let input_bill: InputBill = match in_path.extension().and_then(|s| s.to_str()) {
-        Some("toml") => toml::from_str(&content)?,
-        Some("json") => serde_json::from_str(&content)?,
+    let extension = match in_path.extension().and_then(|s| s.to_str()) {
+        Some(ext @ ("toml" | "json")) => ext,
         _ => anyhow::bail!("Use .toml or .json"),
     };
+    // The full path, so a creditor profile next to the bill is found.
+    let file_name = in_path.to_string_lossy();
 
-    let bill_data: BillData = input_bill.try_into()?;
+    // The diagnostic's Display is a multi-line, compiler-style report
+    // (file:line:column plus the offending source line).
+    let bill_data = parse_and_validate(&content, extension, &file_name)
+        .map_err(|diagnostic| anyhow::anyhow!("{diagnostic}"))?;
 
     create_pdf(out_path_str, language, &bill_data)?;
     Ok(())
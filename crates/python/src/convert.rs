@@ -0,0 +1,115 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Python objects to and from the JSON the library's input types
+//! deserialize from.
+//!
+//! Input usually comes from `DataFrame.to_dict("records")` or
+//! `df.iterrows()`, so this is lenient where pandas is sloppy: NaN and
+//! `pd.NA` count as missing, numpy scalars are unwrapped, and numbers in
+//! text fields (postal codes, house numbers, amounts read as floats) are
+//! turned into the text the bill expects.
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use serde_json::{Map, Number, Value};
+
+/// Converts a dict/list/scalar tree to JSON.
+pub fn to_json(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if obj.is_none() || obj.get_type().name()? == "NAType" {
+        return Ok(Value::Null);
+    }
+    if obj.is_instance_of::<PyBool>() {
+        return Ok(Value::Bool(obj.extract()?));
+    }
+    if obj.is_instance_of::<PyInt>() {
+        return Ok(match obj.extract::<i64>() {
+            Ok(i) => Value::from(i),
+            Err(_) => Value::String(obj.str()?.to_string()),
+        });
+    }
+    if obj.is_instance_of::<PyFloat>() {
+        return Ok(Number::from_f64(obj.extract()?).map_or(Value::Null, Value::Number));
+    }
+    if obj.is_instance_of::<PyString>() {
+        return Ok(Value::String(obj.extract()?));
+    }
+    if let Ok(dict) = obj.cast::<PyDict>() {
+        let mut map = Map::new();
+        for (key, value) in dict.iter() {
+            map.insert(key.str()?.to_string(), to_json(&value)?);
+        }
+        return Ok(Value::Object(map));
+    }
+    if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+        return obj.try_iter()?.map(|item| to_json(&item?)).collect::<PyResult<_>>().map(Value::Array);
+    }
+    // Other mappings, e.g. a pandas Series
+    if obj.hasattr("items")? {
+        let dict = PyDict::new(obj.py());
+        for item in obj.call_method0("items")?.try_iter()? {
+            let (key, value): (Bound<'_, PyAny>, Bound<'_, PyAny>) = item?.extract()?;
+            dict.set_item(key, value)?;
+        }
+        return to_json(dict.as_any());
+    }
+    // numpy scalars (np.int64, np.float64, ...); checked after mappings as
+    // a Series has `item` and `dtype` too
+    if obj.hasattr("item")? && obj.hasattr("dtype")? {
+        return to_json(&obj.call_method0("item")?);
+    }
+    Err(PyTypeError::new_err(format!("cannot use a {} in a bill", obj.get_type().name()?)))
+}
+
+/// Writes a number the way it would appear in a text field: integral
+/// floats without decimals (pandas turns integer columns with gaps into
+/// floats), `decimals` fixed if given.
+fn number_text(number: &Number, decimals: Option<usize>) -> String {
+    match (number.as_f64(), decimals) {
+        (Some(f), Some(decimals)) => format!("{f:.decimals$}"),
+        (Some(f), None) if f.fract() == 0.0 && f.abs() < 1e15 => format!("{f:.0}"),
+        _ => number.to_string(),
+    }
+}
+
+fn stringify(value: &mut Value, decimals: Option<usize>) {
+    if let Value::Number(number) = value {
+        *value = Value::String(number_text(number, decimals));
+    }
+}
+
+/// Turns numbers into text in the fields of an `InputBill` that are text.
+pub fn normalize_bill(bill: &mut Value) {
+    let Value::Object(bill) = bill else {
+        return;
+    };
+    for (key, value) in bill.iter_mut() {
+        match key.as_str() {
+            "amount" => stringify(value, Some(2)),
+            "iban" | "currency" | "reference" | "unstructured_message" | "bill_information" | "notification" => {
+                stringify(value, None)
+            }
+            "creditor_address" | "debtor_address" => {
+                if let Value::Object(address) = value {
+                    address.values_mut().for_each(|v| stringify(v, None));
+                }
+            }
+            "variables" => {
+                if let Value::Object(variables) = value {
+                    variables.values_mut().for_each(|v| stringify(v, None));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Converts JSON to plain Python objects.
+pub fn from_json<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    py.import("json")?.call_method1("loads", (value.to_string(),))
+}
+
@@ -0,0 +1,107 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Python exceptions mirroring `BillError`.
+//!
+//! Related `BillError` variants share an exception class, so callers can
+//! catch e.g. every reference problem with one `except`. Each exception
+//! carries `variant` (the name of the `BillError` variant, e.g.
+//! `"QrIbanRequiresQrReference"`) and `field` (the dotted input path, e.g.
+//! `"creditor_address.city"`, or `None`).
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+use swiss_qrust::BillError as RustBillError;
+
+create_exception!(swiss_qrust, BillError, PyValueError, "A bill, reference or Swico text is invalid.");
+create_exception!(swiss_qrust, BillParseError, BillError, "The input doesn't have the shape of a bill.");
+create_exception!(swiss_qrust, IbanError, BillError, "Invalid IBAN.");
+create_exception!(swiss_qrust, InvalidReferenceError, BillError, "Invalid reference, or one that doesn't fit the IBAN.");
+create_exception!(swiss_qrust, AddressError, BillError, "Invalid creditor or debtor address.");
+create_exception!(swiss_qrust, CharsetError, BillError, "Text outside the SPS character set.");
+create_exception!(swiss_qrust, CurrencyError, BillError, "Currency other than CHF or EUR.");
+create_exception!(swiss_qrust, AmountError, BillError, "Invalid amount.");
+create_exception!(
+    swiss_qrust,
+    AdditionalInformationError,
+    BillError,
+    "Invalid unstructured message or billing information."
+);
+create_exception!(swiss_qrust, AlternativeProcedureError, BillError, "Invalid alternative procedure.");
+create_exception!(swiss_qrust, SwicoError, BillError, "Invalid Swico billing information.");
+create_exception!(swiss_qrust, LanguageError, BillError, "Unknown language code.");
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("BillError", py.get_type::<BillError>())?;
+    m.add("BillParseError", py.get_type::<BillParseError>())?;
+    m.add("IbanError", py.get_type::<IbanError>())?;
+    m.add("InvalidReferenceError", py.get_type::<InvalidReferenceError>())?;
+    m.add("AddressError", py.get_type::<AddressError>())?;
+    m.add("CharsetError", py.get_type::<CharsetError>())?;
+    m.add("CurrencyError", py.get_type::<CurrencyError>())?;
+    m.add("AmountError", py.get_type::<AmountError>())?;
+    m.add("AdditionalInformationError", py.get_type::<AdditionalInformationError>())?;
+    m.add("AlternativeProcedureError", py.get_type::<AlternativeProcedureError>())?;
+    m.add("SwicoError", py.get_type::<SwicoError>())?;
+    m.add("LanguageError", py.get_type::<LanguageError>())?;
+    Ok(())
+}
+
+/// An exception of class `T` with `variant` and `field` set.
+pub fn raise(py: Python<'_>, class: Bound<'_, PyType>, message: String, variant: &str, field: Option<String>) -> PyErr {
+    let err = PyErr::from_type(class, message);
+    let value = err.value(py);
+    // Setting attributes on a fresh exception instance can't fail.
+    let _ = value.setattr("variant", variant);
+    let _ = value.setattr("field", field);
+    err
+}
+
+fn class_and_variant<'py>(py: Python<'py>, err: &RustBillError) -> (Bound<'py, PyType>, &'static str) {
+    match err {
+        RustBillError::ReferenceError(_) => (py.get_type::<InvalidReferenceError>(), "ReferenceError"),
+        RustBillError::QrIbanRequiresQrReference => {
+            (py.get_type::<InvalidReferenceError>(), "QrIbanRequiresQrReference")
+        }
+        RustBillError::QrReferenceRequiresQrIban => {
+            (py.get_type::<InvalidReferenceError>(), "QrReferenceRequiresQrIban")
+        }
+        RustBillError::IbanError(_) => (py.get_type::<IbanError>(), "IbanError"),
+        RustBillError::AddressError(_) => (py.get_type::<AddressError>(), "AddressError"),
+        RustBillError::SPSCharsetError(_) => (py.get_type::<CharsetError>(), "SPSCharsetError"),
+        RustBillError::InvalidCurrency => (py.get_type::<CurrencyError>(), "InvalidCurrency"),
+        RustBillError::InvalidAmount => (py.get_type::<AmountError>(), "InvalidAmount"),
+        RustBillError::NotificationWithAmount => (py.get_type::<AmountError>(), "NotificationWithAmount"),
+        RustBillError::AdditionalInformationTooLong => {
+            (py.get_type::<AdditionalInformationError>(), "AdditionalInformationTooLong")
+        }
+        RustBillError::AmbiguousBillingInformation => {
+            (py.get_type::<AdditionalInformationError>(), "AmbiguousBillingInformation")
+        }
+        RustBillError::NotificationWithMessage => {
+            (py.get_type::<AdditionalInformationError>(), "NotificationWithMessage")
+        }
+        RustBillError::Template(_) => (py.get_type::<AdditionalInformationError>(), "Template"),
+        RustBillError::AlternativeProcedureTooLong => {
+            (py.get_type::<AlternativeProcedureError>(), "AlternativeProcedureTooLong")
+        }
+        RustBillError::AlternativeProcedure(_) => (py.get_type::<AlternativeProcedureError>(), "AlternativeProcedure"),
+        RustBillError::InvalidSwicoDate(_) => (py.get_type::<SwicoError>(), "InvalidSwicoDate"),
+        RustBillError::Swico(_) => (py.get_type::<SwicoError>(), "Swico"),
+        RustBillError::Language(_) => (py.get_type::<LanguageError>(), "Language"),
+        RustBillError::Field { error, .. } => class_and_variant(py, error),
+    }
+}
+
+/// The Python exception for `err`, located at its field if known.
+pub fn bill_error(py: Python<'_>, err: RustBillError) -> PyErr {
+    let (class, variant) = class_and_variant(py, &err);
+    let field = err.field().map(str::to_string);
+    raise(py, class, err.to_string(), variant, field)
+}
@@ -0,0 +1,251 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! The `swiss_qrust` Python module. The public API and its documentation
+//! are in `swiss_qrust.pyi`; keep the two in sync.
+
+mod convert;
+mod errors;
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use serde_json::Value;
+use swiss_qrust::bill::qr_bill::QrBill;
+use swiss_qrust::pdf::{render_bill_to_pdf, RenderError};
+use swiss_qrust::png_renderers::render_bill_to_png;
+use swiss_qrust::svg::render_bill_to_svg;
+use swiss_qrust::{
+    decode_qr_text, generate_iso11649_with_checksum, generate_qrr_with_checksum, is_valid_iso11649_reference,
+    is_valid_qr_reference, BillData as RustBillData, InputBill, InputSwicoBillInformation, Language, ReferenceType,
+    SwicoBillInformation,
+};
+
+use crate::convert::{from_json, normalize_bill, to_json};
+use crate::errors::{bill_error, raise, BillParseError, InvalidReferenceError, LanguageError, SwicoError};
+
+fn parse_error(py: Python<'_>, message: String) -> PyErr {
+    raise(py, py.get_type::<BillParseError>(), message, "Parse", None)
+}
+
+fn language(py: Python<'_>, code: &str) -> PyResult<Language> {
+    code.parse().map_err(|e: swiss_qrust::LanguageError| {
+        raise(py, py.get_type::<LanguageError>(), format!("{e}, got '{code}'"), "Language", None)
+    })
+}
+
+fn render_error(err: RenderError) -> PyErr {
+    pyo3::exceptions::PyRuntimeError::new_err(err.to_string())
+}
+
+/// Validates `value` (an `InputBill` as JSON) into a bill.
+fn build(py: Python<'_>, mut value: Value) -> PyResult<(RustBillData, InputBill)> {
+    normalize_bill(&mut value);
+    if value.get("profile").is_some_and(|p| !p.is_null()) {
+        return Err(parse_error(py, "creditor profiles are not supported here; merge the dicts instead".into()));
+    }
+    let input: InputBill = serde_json::from_value(value.clone()).map_err(|e| parse_error(py, e.to_string()))?;
+    let bill = RustBillData::try_from(input.clone()).map_err(|err| bill_error(py, err))?;
+    Ok((bill, input))
+}
+
+/// A validated bill.
+#[pyclass(frozen, module = "swiss_qrust")]
+struct BillData {
+    bill: RustBillData,
+    input: InputBill,
+}
+
+#[pymethods]
+impl BillData {
+    #[new]
+    fn new(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let (bill, input) = build(py, to_json(data)?)?;
+        Ok(Self { bill, input })
+    }
+
+    #[staticmethod]
+    fn from_dict(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Self::new(py, data)
+    }
+
+    #[staticmethod]
+    fn from_json(py: Python<'_>, text: &str) -> PyResult<Self> {
+        let value = serde_json::from_str(text).map_err(|e| parse_error(py, e.to_string()))?;
+        let (bill, input) = build(py, value)?;
+        Ok(Self { bill, input })
+    }
+
+    #[staticmethod]
+    fn from_qr_payload(py: Python<'_>, payload: &str) -> PyResult<Self> {
+        let input = decode_qr_text(payload).map_err(|e| parse_error(py, e.to_string()))?;
+        let value = serde_json::to_value(&input).map_err(|e| parse_error(py, e.to_string()))?;
+        let (bill, input) = build(py, value)?;
+        Ok(Self { bill, input })
+    }
+
+    #[getter]
+    fn iban(&self) -> String {
+        self.bill.iban.as_str().to_string()
+    }
+
+    #[getter]
+    fn currency(&self) -> String {
+        self.bill.currency.to_string()
+    }
+
+    #[getter]
+    fn amount(&self) -> Option<String> {
+        self.bill.amount.clone()
+    }
+
+    #[getter]
+    fn reference(&self) -> Option<String> {
+        match &self.bill.reference_type {
+            ReferenceType::QrRef(reference) | ReferenceType::Creditor(reference) => Some(reference.clone()),
+            ReferenceType::NoRef => None,
+        }
+    }
+
+    #[getter]
+    fn reference_type(&self) -> &'static str {
+        match self.bill.reference_type {
+            ReferenceType::QrRef(_) => "QRR",
+            ReferenceType::Creditor(_) => "SCOR",
+            ReferenceType::NoRef => "NON",
+        }
+    }
+
+    #[getter]
+    fn unstructured_message(&self) -> Option<String> {
+        self.bill.unstructured_message.clone()
+    }
+
+    #[getter]
+    fn bill_information(&self) -> Option<String> {
+        self.bill.bill_information.clone()
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut value = serde_json::to_value(&self.input).map_err(|e| parse_error(py, e.to_string()))?;
+        if let Value::Object(map) = &mut value {
+            map.retain(|_, v| !v.is_null());
+        }
+        from_json(py, &value)
+    }
+
+    fn qr_payload(&self) -> PyResult<String> {
+        QrBill::new(&self.bill)
+            .and_then(|qr| qr.create_qr_text())
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    }
+
+    #[pyo3(signature = (language = "de"))]
+    fn to_pdf<'py>(&self, py: Python<'py>, language: &str) -> PyResult<Bound<'py, PyBytes>> {
+        let language = self::language(py, language)?;
+        let pdf = py.detach(|| render_bill_to_pdf(&self.bill, language)).map_err(render_error)?;
+        Ok(PyBytes::new(py, &pdf))
+    }
+
+    #[pyo3(signature = (language = "de"))]
+    fn to_svg(&self, py: Python<'_>, language: &str) -> PyResult<String> {
+        let language = self::language(py, language)?;
+        py.detach(|| render_bill_to_svg(&self.bill, language)).map_err(render_error)
+    }
+
+    #[pyo3(signature = (language = "de", dpi = 300.0))]
+    fn to_png<'py>(&self, py: Python<'py>, language: &str, dpi: f64) -> PyResult<Bound<'py, PyBytes>> {
+        let language = self::language(py, language)?;
+        if !(dpi > 0.0 && dpi <= 1200.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("dpi must be in (0, 1200], got {dpi}")));
+        }
+        let png = py.detach(|| render_bill_to_png(&self.bill, language, dpi)).map_err(render_error)?;
+        Ok(PyBytes::new(py, &png))
+    }
+
+    fn __repr__(&self) -> String {
+        let py_str = |s: Option<&str>| s.map_or("None".to_string(), |s| format!("{s:?}"));
+        format!(
+            "BillData(iban={:?}, creditor={:?}, amount={}, currency={:?}, reference={})",
+            self.iban(),
+            self.bill.creditor_address.name,
+            py_str(self.bill.amount.as_deref()),
+            self.currency(),
+            py_str(self.reference().as_deref()),
+        )
+    }
+}
+
+fn invalid_reference(py: Python<'_>, raw: &str) -> PyErr {
+    raise(
+        py,
+        py.get_type::<InvalidReferenceError>(),
+        format!("cannot generate a reference from '{raw}'"),
+        "ReferenceError",
+        None,
+    )
+}
+
+#[pyfunction]
+fn generate_qr_reference(py: Python<'_>, raw: &str) -> PyResult<String> {
+    generate_qrr_with_checksum(raw).map_err(|_| invalid_reference(py, raw))
+}
+
+#[pyfunction]
+fn generate_scor_reference(py: Python<'_>, raw: &str) -> PyResult<String> {
+    generate_iso11649_with_checksum(raw).map_err(|_| invalid_reference(py, raw))
+}
+
+#[pyfunction]
+fn is_valid_qr_reference_py(reference: &str) -> bool {
+    is_valid_qr_reference(reference).is_ok()
+}
+
+#[pyfunction]
+fn is_valid_scor_reference(reference: &str) -> bool {
+    is_valid_iso11649_reference(reference).is_ok()
+}
+
+#[pyfunction]
+fn encode_swico(py: Python<'_>, info: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+    let input: InputSwicoBillInformation =
+        serde_json::from_value(to_json(info)?).map_err(|e| parse_error(py, e.to_string()))?;
+    let info = SwicoBillInformation::try_from(input).map_err(|e| bill_error(py, e))?;
+    Ok(info.encode_as_text())
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, strict = false))]
+fn decode_swico<'py>(py: Python<'py>, text: &str, strict: bool) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let info = if strict {
+        let info = SwicoBillInformation::decode_strict(text)
+            .map_err(|e| raise(py, py.get_type::<SwicoError>(), e.to_string(), "Swico", None))?;
+        Some(info)
+    } else {
+        SwicoBillInformation::decode_text(text)
+    };
+    info.map(|info| {
+        let mut value = serde_json::to_value(InputSwicoBillInformation::from(&info))
+            .map_err(|e| parse_error(py, e.to_string()))?;
+        if let Value::Object(map) = &mut value {
+            map.retain(|_, v| !v.is_null());
+        }
+        from_json(py, &value)
+    })
+    .transpose()
+}
+
+#[pymodule(name = "swiss_qrust")]
+fn swiss_qrust_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    errors::register(m)?;
+    m.add_class::<BillData>()?;
+    m.add_function(wrap_pyfunction!(generate_qr_reference, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_scor_reference, m)?)?;
+    m.add("is_valid_qr_reference", wrap_pyfunction!(is_valid_qr_reference_py, m)?)?;
+    m.add_function(wrap_pyfunction!(is_valid_scor_reference, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_swico, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_swico, m)?)?;
+    Ok(())
+}
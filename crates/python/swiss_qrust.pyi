@@ -0,0 +1,99 @@
+"""Swiss QR-bill payment slips, references and Swico billing information.
+
+Backed by the Rust library ``swiss_qrust``. Bills are given as dicts in the
+input format of the command line tool (see ``crates/cli/data``), e.g. a row
+of ``DataFrame.to_dict("records")``: NaN and ``pd.NA`` count as missing and
+numbers in text fields (postal codes, amounts) are converted.
+"""
+
+from typing import Any, Literal, Mapping, Optional
+
+Language = Literal["de", "fr", "it", "en"]
+
+class BillError(ValueError):
+    """A bill, reference or Swico text is invalid.
+
+    ``variant`` is the name of the Rust ``BillError`` variant (e.g.
+    ``"QrIbanRequiresQrReference"``), ``field`` the dotted path of the
+    offending input field (e.g. ``"creditor_address.city"``) if known.
+    """
+
+    variant: str
+    field: Optional[str]
+
+class BillParseError(BillError):
+    """The input doesn't have the shape of a bill (missing or mistyped field)."""
+
+class IbanError(BillError): ...
+class InvalidReferenceError(BillError):
+    """Invalid reference, or a reference that doesn't fit the IBAN."""
+
+class AddressError(BillError): ...
+class CharsetError(BillError):
+    """Text outside the SPS character set; pass ``sanitize=True`` to transliterate."""
+
+class CurrencyError(BillError): ...
+class AmountError(BillError): ...
+class AdditionalInformationError(BillError): ...
+class AlternativeProcedureError(BillError): ...
+class SwicoError(BillError): ...
+class LanguageError(BillError): ...
+
+class BillData:
+    """A validated bill. Raises a ``BillError`` subclass if the data is invalid."""
+
+    def __init__(self, data: Mapping[str, Any]) -> None: ...
+    @staticmethod
+    def from_dict(data: Mapping[str, Any]) -> BillData: ...
+    @staticmethod
+    def from_json(text: str) -> BillData: ...
+    @staticmethod
+    def from_qr_payload(payload: str) -> BillData:
+        """Reads the text encoded in a QR-bill's QR code."""
+
+    @property
+    def iban(self) -> str: ...
+    @property
+    def currency(self) -> Literal["CHF", "EUR"]: ...
+    @property
+    def amount(self) -> Optional[str]: ...
+    @property
+    def reference(self) -> Optional[str]: ...
+    @property
+    def reference_type(self) -> Literal["QRR", "SCOR", "NON"]: ...
+    @property
+    def unstructured_message(self) -> Optional[str]: ...
+    @property
+    def bill_information(self) -> Optional[str]: ...
+    def to_dict(self) -> dict[str, Any]:
+        """The bill in the input format, without unset fields."""
+
+    def qr_payload(self) -> str:
+        """The text encoded in the QR code."""
+
+    def to_pdf(self, language: Language = "de") -> bytes:
+        """A single-page A4 PDF with receipt and payment part."""
+
+    def to_svg(self, language: Language = "de") -> str: ...
+    def to_png(self, language: Language = "de", dpi: float = 300.0) -> bytes: ...
+
+def generate_qr_reference(raw: str) -> str:
+    """Appends the check digit to 1 to 26 digits: ``"4711"`` -> ``"000000000000000000000047119"``."""
+
+def generate_scor_reference(raw: str) -> str:
+    """Creates an ISO 11649 creditor reference: ``"539007547034"`` -> ``"RF18539007547034"``."""
+
+def is_valid_qr_reference(reference: str) -> bool: ...
+def is_valid_scor_reference(reference: str) -> bool: ...
+def encode_swico(info: Mapping[str, Any]) -> Optional[str]:
+    """Swico S1 text (``//S1/10/...``) for the bill_information field; ``None`` if ``info`` is empty.
+
+    Keys as in the ``swico_bill_information`` of a bill, dates as ``"YYYY-MM-DD"``.
+    """
+
+def decode_swico(text: str, strict: bool = False) -> Optional[dict[str, Any]]:
+    """The reverse of ``encode_swico``.
+
+    Lenient by default: ``None`` if ``text`` isn't Swico S1, malformed parts
+    are skipped. With ``strict=True``, any violation raises ``SwicoError``.
+    """
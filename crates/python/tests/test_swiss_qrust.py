@@ -0,0 +1,98 @@
+# Copyright (c) 2026 Thomas Prosser
+# Licensed under MIT License
+# https://opensource.org/licenses/MIT
+#
+# Run with: cd crates/python && maturin develop && pytest
+
+import json
+import math
+from pathlib import Path
+
+import pytest
+
+import swiss_qrust
+from swiss_qrust import BillData
+
+BILL = json.loads((Path(__file__).parents[2] / "cli" / "data" / "robert_schneider.json").read_text())
+
+
+def test_renders_pdf_svg_and_png():
+    bill = BillData(BILL)
+
+    assert bill.to_pdf("fr").startswith(b"%PDF")
+    assert bill.to_svg().startswith("<svg")
+    assert bill.to_png(dpi=72).startswith(b"\x89PNG")
+
+
+def test_exposes_bill_fields():
+    bill = BillData.from_json(json.dumps(BILL))
+
+    assert bill.iban == "CH4331999000001265789"
+    assert (bill.currency, bill.amount) == ("CHF", "199.95")
+    assert (bill.reference_type, bill.reference) == ("QRR", "210000000003139471430009017")
+    assert bill.to_dict()["creditor_address"]["name"] == "Robert Schneider AG"
+    assert "Robert Schneider AG" in repr(bill)
+
+
+def test_round_trips_through_the_qr_payload():
+    bill = BillData(BILL)
+    decoded = BillData.from_qr_payload(bill.qr_payload())
+
+    assert decoded.to_dict()["debtor_address"]["city"] == "Zürich"
+    assert decoded.qr_payload() == bill.qr_payload()
+
+
+def test_accepts_pandas_style_rows():
+    row = {**BILL, "amount": 250, "unstructured_message": math.nan}
+    row["creditor_address"] = {**BILL["creditor_address"], "plz": 2501.0, "house_num": 1268}
+
+    bill = BillData.from_dict(row)
+    assert bill.amount == "250.00"
+    assert bill.unstructured_message is None
+
+
+def test_errors_mirror_bill_error():
+    bad_city = {**BILL, "creditor_address": {**BILL["creditor_address"], "city": ""}}
+    with pytest.raises(swiss_qrust.AddressError) as error:
+        BillData(bad_city)
+    assert error.value.variant == "AddressError"
+    assert error.value.field == "creditor_address.city"
+
+    with pytest.raises(swiss_qrust.InvalidReferenceError) as error:
+        BillData({**BILL, "reference": None})
+    assert error.value.variant == "QrIbanRequiresQrReference"
+
+    with pytest.raises(swiss_qrust.BillParseError):
+        BillData({"iban": "CH4331999000001265789"})
+
+    # All of them are BillErrors and ValueErrors.
+    with pytest.raises(ValueError):
+        BillData({**BILL, "currency": "USD"})
+
+    with pytest.raises(swiss_qrust.LanguageError):
+        BillData(BILL).to_pdf("rm")
+
+
+def test_generates_and_checks_references():
+    assert swiss_qrust.generate_qr_reference("4711") == "000000000000000000000047119"
+    assert swiss_qrust.generate_scor_reference("5390 0754 7034") == "RF18539007547034"
+    assert swiss_qrust.is_valid_qr_reference("210000000003139471430009017")
+    assert not swiss_qrust.is_valid_qr_reference("210000000003139471430009018")
+    assert swiss_qrust.is_valid_scor_reference("RF18539007547034")
+
+    with pytest.raises(swiss_qrust.InvalidReferenceError):
+        swiss_qrust.generate_qr_reference("12ab")
+
+
+def test_encodes_and_decodes_swico():
+    info = {"invoice_number": "10201409", "invoice_date": "2019-05-12", "vat_rate": 7.7}
+    text = swiss_qrust.encode_swico(info)
+
+    assert text == "//S1/10/10201409/11/190512/32/7.7"
+    assert swiss_qrust.decode_swico(text) == info
+    assert swiss_qrust.decode_swico("Order 17") is None
+
+    with pytest.raises(swiss_qrust.SwicoError):
+        swiss_qrust.decode_swico("//S1/11/1905", strict=True)
+    with pytest.raises(swiss_qrust.SwicoError):
+        swiss_qrust.encode_swico({"invoice_date": "12.05.2019"})
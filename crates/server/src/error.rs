@@ -0,0 +1,128 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! JSON error bodies. Every failure, including the ones raised by axum
+//! itself (oversized or malformed bodies, unknown routes), is answered with
+//!
+//! ```json
+//! { "error": "invalid_bill", "message": "Invalid city character length",
+//!   "field": "creditor_address.city", "line": 8, "column": 13 }
+//! ```
+//!
+//! where `error` is a stable code for clients to match on and `field`,
+//! `line` and `column` are only present when known.
+
+use axum::extract::rejection::{BytesRejection, JsonRejection, QueryRejection};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+use swiss_qrust::diagnostics::Diagnostic;
+use swiss_qrust::pdf::RenderError;
+use swiss_qrust::{BillError, QrDecodeError};
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub error: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: ErrorBody,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, error: &'static str, message: impl Into<String>) -> Self {
+        Self { status, body: ErrorBody { error, message: message.into(), field: None, line: None, column: None } }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    pub fn not_found() -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", "no such endpoint")
+    }
+
+    pub fn method_not_allowed() -> Self {
+        Self::new(StatusCode::METHOD_NOT_ALLOWED, "method_not_allowed", "use POST")
+    }
+
+    pub fn overloaded() -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, "overloaded", "too many concurrent requests, retry later")
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+/// A bill that doesn't parse or validate, located in the request body.
+impl From<Diagnostic> for ApiError {
+    fn from(diagnostic: Diagnostic) -> Self {
+        let mut error = Self::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_bill", diagnostic.message);
+        error.body.field = diagnostic.field;
+        error.body.line = diagnostic.location.as_ref().map(|l| l.line);
+        error.body.column = diagnostic.location.as_ref().map(|l| l.column);
+        error
+    }
+}
+
+impl From<BillError> for ApiError {
+    fn from(err: BillError) -> Self {
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_bill", err.to_string())
+    }
+}
+
+/// Rendering a validated bill should not fail; if it does, it's on us.
+impl From<RenderError> for ApiError {
+    fn from(err: RenderError) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "render_failed", err.to_string())
+    }
+}
+
+impl From<QrDecodeError> for ApiError {
+    fn from(err: QrDecodeError) -> Self {
+        Self::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_qr_payload", err.to_string())
+    }
+}
+
+impl From<BytesRejection> for ApiError {
+    fn from(rejection: BytesRejection) -> Self {
+        let error = match rejection.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+            _ => "bad_request",
+        };
+        Self::new(rejection.status(), error, rejection.body_text())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        let error = match rejection.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported_media_type",
+            _ => "bad_request",
+        };
+        Self::new(rejection.status(), error, rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
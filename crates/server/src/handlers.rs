@@ -0,0 +1,187 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+use axum::body::Bytes;
+use axum::extract::rejection::{BytesRejection, JsonRejection, QueryRejection};
+use axum::extract::Query;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use swiss_qrust::diagnostics::parse_and_validate;
+use swiss_qrust::pdf::{render_bill_to_pdf, RenderError};
+use swiss_qrust::png_renderers::render_bill_to_png;
+use swiss_qrust::svg::render_bill_to_svg;
+use swiss_qrust::traits::SwissQRFormatter;
+use swiss_qrust::{
+    decode_qr_text, generate_iso11649_with_checksum, generate_qrr_with_checksum, BillData, InputBill, Language,
+};
+
+use crate::error::ApiError;
+
+/// Upper bound for `?dpi=`; a 210 x 105 mm slip at 1200 dpi is already
+/// about 10000 x 5000 pixels.
+const MAX_DPI: f64 = 1200.0;
+
+#[derive(Debug, Deserialize)]
+pub struct RenderQuery {
+    /// `de`, `fr`, `it` or `en`; German if omitted.
+    lang: Option<String>,
+    /// PNG resolution, 300 if omitted.
+    dpi: Option<f64>,
+}
+
+impl RenderQuery {
+    fn language(&self) -> Result<Language, ApiError> {
+        match &self.lang {
+            Some(lang) => lang
+                .parse()
+                .map_err(|e: swiss_qrust::LanguageError| ApiError::bad_request(format!("{e}, got '{lang}'"))),
+            None => Ok(Language::De),
+        }
+    }
+
+    fn dpi(&self) -> Result<f64, ApiError> {
+        match self.dpi.unwrap_or(300.0) {
+            dpi if dpi > 0.0 && dpi <= MAX_DPI => Ok(dpi),
+            dpi => Err(ApiError::bad_request(format!("dpi must be between 0 and {MAX_DPI}, got {dpi}"))),
+        }
+    }
+}
+
+/// Parses and validates an `InputBill` JSON body, locating errors in it.
+///
+/// Creditor profiles are refused: they name files on the machine that
+/// parses the bill, which is the server here.
+fn parse_bill(body: Result<Bytes, BytesRejection>) -> Result<BillData, ApiError> {
+    let body = body?;
+    let text = std::str::from_utf8(&body).map_err(|_| ApiError::bad_request("request body is not UTF-8"))?;
+    if serde_json::from_str::<Value>(text).is_ok_and(|v| v.get("profile").is_some()) {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "profile_not_supported",
+            "creditor profiles are not supported by the server; send the complete bill",
+        ));
+    }
+    Ok(parse_and_validate(text, "json", "request")?)
+}
+
+/// Runs a renderer on the blocking pool, so slow renders don't stall the
+/// I/O threads.
+async fn render<F>(bill: BillData, content_type: &'static str, renderer: F) -> Result<Response, ApiError>
+where
+    F: FnOnce(&BillData) -> Result<Vec<u8>, RenderError> + Send + 'static,
+{
+    let bytes = tokio::task::spawn_blocking(move || renderer(&bill))
+        .await
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "render_failed", e.to_string()))??;
+    Ok(([(CONTENT_TYPE, content_type)], bytes).into_response())
+}
+
+pub async fn bill_pdf(
+    query: Result<Query<RenderQuery>, QueryRejection>,
+    body: Result<Bytes, BytesRejection>,
+) -> Result<Response, ApiError> {
+    let Query(query) = query?;
+    let (bill, language) = (parse_bill(body)?, query.language()?);
+    render(bill, "application/pdf", move |bill| render_bill_to_pdf(bill, language)).await
+}
+
+pub async fn bill_svg(
+    query: Result<Query<RenderQuery>, QueryRejection>,
+    body: Result<Bytes, BytesRejection>,
+) -> Result<Response, ApiError> {
+    let Query(query) = query?;
+    let (bill, language) = (parse_bill(body)?, query.language()?);
+    render(bill, "image/svg+xml", move |bill| render_bill_to_svg(bill, language).map(String::into_bytes)).await
+}
+
+pub async fn bill_png(
+    query: Result<Query<RenderQuery>, QueryRejection>,
+    body: Result<Bytes, BytesRejection>,
+) -> Result<Response, ApiError> {
+    let Query(query) = query?;
+    let (bill, language, dpi) = (parse_bill(body)?, query.language()?, query.dpi()?);
+    render(bill, "image/png", move |bill| render_bill_to_png(bill, language, dpi)).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct Validation {
+    valid: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<crate::error::ErrorBody>,
+}
+
+/// Always 200 for a bill that could be read; whether it is valid is in the
+/// body, with the errors in the same shape as the other endpoints' errors.
+pub async fn validate(body: Result<Bytes, BytesRejection>) -> Result<Json<Validation>, ApiError> {
+    match parse_bill(body) {
+        Ok(_) => Ok(Json(Validation { valid: true, errors: Vec::new() })),
+        Err(err) if err.status == StatusCode::UNPROCESSABLE_ENTITY => {
+            Ok(Json(Validation { valid: false, errors: vec![err.body] }))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Takes the QR code payload as plain text and returns the bill in the
+/// input format, ready to be edited and posted to `/bills/...`.
+pub async fn qr_decode(body: Result<Bytes, BytesRejection>) -> Result<Json<InputBill>, ApiError> {
+    let body = body?;
+    let text = std::str::from_utf8(&body).map_err(|_| ApiError::bad_request("request body is not UTF-8"))?;
+    Ok(Json(decode_qr_text(text)?))
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceKind {
+    Qrr,
+    Scor,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReferenceRequest {
+    kind: ReferenceKind,
+    /// Digits (QRR) or alphanumeric characters (SCOR) without check
+    /// digits; may be grouped.
+    raw: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReferenceResponse {
+    reference: String,
+    /// Grouped as on the slip.
+    formatted: String,
+}
+
+pub async fn references(
+    request: Result<Json<ReferenceRequest>, JsonRejection>,
+) -> Result<Json<ReferenceResponse>, ApiError> {
+    let Json(request) = request?;
+    let invalid = |_| {
+        ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_reference",
+            format!("cannot generate a reference from '{}'", request.raw),
+        )
+    };
+
+    let (reference, formatted) = match request.kind {
+        ReferenceKind::Qrr => {
+            let reference = generate_qrr_with_checksum(&request.raw).map_err(invalid)?;
+            let formatted = reference.format_qr_reference();
+            (reference, formatted)
+        }
+        ReferenceKind::Scor => {
+            let reference = generate_iso11649_with_checksum(&request.raw).map_err(invalid)?;
+            let formatted = reference.format_scor_reference();
+            (reference, formatted)
+        }
+    };
+    Ok(Json(ReferenceResponse { reference, formatted }))
+}
@@ -0,0 +1,81 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! An HTTP API around `swiss_qrust`, for running bill generation as an
+//! internal service.
+//!
+//! | Endpoint               | Body                         | Response                                  |
+//! |------------------------|------------------------------|-------------------------------------------|
+//! | `POST /bills/pdf`      | `InputBill` JSON             | `application/pdf`                         |
+//! | `POST /bills/svg`      | `InputBill` JSON             | `image/svg+xml`                           |
+//! | `POST /bills/png`      | `InputBill` JSON             | `image/png`                               |
+//! | `POST /bills/validate` | `InputBill` JSON             | `{"valid": bool, "errors": [...]}`        |
+//! | `POST /qr/decode`      | QR code payload (plain text) | `InputBill` JSON                          |
+//! | `POST /references`     | `{"kind": "qrr", "raw": ..}` | `{"reference": .., "formatted": ..}`      |
+//!
+//! The render endpoints take `?lang=de|fr|it|en`, `/bills/png` also
+//! `?dpi=`. Errors are JSON, see [`error`]. Request bodies are limited to
+//! [`ServerConfig::max_body_bytes`]; beyond
+//! [`ServerConfig::max_concurrent_requests`] requests in flight, new ones
+//! are answered with 503 instead of being queued.
+
+pub mod error;
+mod handlers;
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use axum::extract::{DefaultBodyLimit, Request, State};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::Router;
+use tokio::sync::Semaphore;
+
+use crate::error::ApiError;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Largest accepted request body, in bytes.
+    pub max_body_bytes: usize,
+    /// Requests handled at the same time.
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for ServerConfig {
+    /// 256 KiB bodies (a bill is well under 4 KiB) and two requests per
+    /// CPU, rendering being CPU-bound.
+    fn default() -> Self {
+        let cpus = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        Self { max_body_bytes: 256 * 1024, max_concurrent_requests: 2 * cpus }
+    }
+}
+
+/// Answers 503 when all slots are taken. The slot is held until the
+/// response is ready, including while the request body is still arriving.
+async fn limit_concurrency(State(slots): State<Arc<Semaphore>>, request: Request, next: Next) -> Response {
+    match slots.try_acquire() {
+        Ok(_permit) => next.run(request).await,
+        Err(_) => ApiError::overloaded().into_response(),
+    }
+}
+
+/// The API with its limits applied, ready for `axum::serve`.
+pub fn router(config: &ServerConfig) -> Router {
+    let slots = Arc::new(Semaphore::new(config.max_concurrent_requests.max(1)));
+
+    Router::new()
+        .route("/bills/pdf", post(handlers::bill_pdf))
+        .route("/bills/svg", post(handlers::bill_svg))
+        .route("/bills/png", post(handlers::bill_png))
+        .route("/bills/validate", post(handlers::validate))
+        .route("/qr/decode", post(handlers::qr_decode))
+        .route("/references", post(handlers::references))
+        .fallback(|| async { ApiError::not_found() })
+        .method_not_allowed_fallback(|| async { ApiError::method_not_allowed() })
+        .layer(DefaultBodyLimit::max(config.max_body_bytes))
+        .layer(middleware::from_fn_with_state(slots, limit_concurrency))
+}
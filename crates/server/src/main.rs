@@ -0,0 +1,45 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+use std::net::SocketAddr;
+
+use clap::Parser;
+use swiss_qrust_server::{router, ServerConfig};
+
+#[derive(Parser)]
+#[command(name = "swiss_qrust_server")]
+#[command(about = "Swiss QR Bill HTTP API", long_about = None)]
+struct Cli {
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    bind: SocketAddr,
+
+    /// Largest accepted request body, in bytes
+    #[arg(long)]
+    max_body_bytes: Option<usize>,
+
+    /// Requests handled at the same time; further ones get 503
+    #[arg(long)]
+    max_concurrent_requests: Option<usize>,
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+    let defaults = ServerConfig::default();
+    let config = ServerConfig {
+        max_body_bytes: cli.max_body_bytes.unwrap_or(defaults.max_body_bytes),
+        max_concurrent_requests: cli.max_concurrent_requests.unwrap_or(defaults.max_concurrent_requests),
+    };
+
+    let listener = tokio::net::TcpListener::bind(cli.bind).await?;
+    println!("Listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(&config))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+}
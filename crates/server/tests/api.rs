@@ -0,0 +1,184 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Requests over a real socket against a server on an ephemeral port.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use swiss_qrust_server::{router, ServerConfig};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const BILL: &str = include_str!("../../cli/data/robert_schneider.json");
+
+async fn start(config: ServerConfig) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router(&config)).await.unwrap() });
+    addr
+}
+
+struct Response {
+    status: u16,
+    content_type: String,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+fn request_head(path: &str, content_type: &str, length: usize) -> String {
+    format!(
+        "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: {content_type}\r\nContent-Length: {length}\r\nConnection: close\r\n\r\n"
+    )
+}
+
+async fn read_response(mut stream: TcpStream) -> Response {
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).await.unwrap();
+    let split = raw.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+    let head = String::from_utf8_lossy(&raw[..split]).to_string();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    let content_type = head
+        .lines()
+        .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-type: ").map(str::to_string))
+        .unwrap_or_default();
+    Response { status, content_type, body: raw[split + 4..].to_vec() }
+}
+
+async fn post(addr: SocketAddr, path: &str, content_type: &str, body: &str) -> Response {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(request_head(path, content_type, body.len()).as_bytes()).await.unwrap();
+    stream.write_all(body.as_bytes()).await.unwrap();
+    read_response(stream).await
+}
+
+async fn post_json(addr: SocketAddr, path: &str, body: &str) -> Response {
+    post(addr, path, "application/json", body).await
+}
+
+#[tokio::test]
+async fn renders_bills_in_all_formats() {
+    let addr = start(ServerConfig::default()).await;
+
+    let pdf = post_json(addr, "/bills/pdf?lang=fr", BILL).await;
+    assert_eq!((pdf.status, pdf.content_type.as_str()), (200, "application/pdf"));
+    assert!(pdf.body.starts_with(b"%PDF"));
+
+    let svg = post_json(addr, "/bills/svg", BILL).await;
+    assert_eq!((svg.status, svg.content_type.as_str()), (200, "image/svg+xml"));
+    assert!(String::from_utf8(svg.body).unwrap().contains("<svg"));
+
+    let png = post_json(addr, "/bills/png?dpi=72", BILL).await;
+    assert_eq!((png.status, png.content_type.as_str()), (200, "image/png"));
+    assert!(png.body.starts_with(b"\x89PNG"));
+}
+
+#[tokio::test]
+async fn invalid_bills_get_located_json_errors() {
+    let addr = start(ServerConfig::default()).await;
+    let bill = BILL.replace("\"city\": \"Biel\"", "\"city\": \"\"");
+
+    let response = post_json(addr, "/bills/pdf", &bill).await;
+    assert_eq!(response.status, 422);
+    let error = response.json();
+    assert_eq!(error["error"], "invalid_bill");
+    assert_eq!(error["field"], "creditor_address.city");
+    assert_eq!(error["line"], 12);
+
+    let validation = post_json(addr, "/bills/validate", &bill).await;
+    assert_eq!(validation.status, 200);
+    assert_eq!(validation.json()["valid"], false);
+    assert_eq!(validation.json()["errors"][0]["field"], "creditor_address.city");
+
+    let valid = post_json(addr, "/bills/validate", BILL).await;
+    assert_eq!(valid.json(), json!({ "valid": true }));
+}
+
+#[tokio::test]
+async fn rejects_bad_requests_with_json() {
+    let addr = start(ServerConfig { max_body_bytes: 1024, ..ServerConfig::default() }).await;
+
+    let syntax = post_json(addr, "/bills/validate", "{").await;
+    assert_eq!((syntax.status, syntax.json()["valid"].clone()), (200, json!(false)));
+
+    let language = post_json(addr, "/bills/svg?lang=rm", BILL).await;
+    assert_eq!((language.status, language.json()["error"].clone()), (400, json!("bad_request")));
+
+    let profile = post_json(addr, "/bills/pdf", r#"{"profile": "/etc/creditor.toml"}"#).await;
+    assert_eq!((profile.status, profile.json()["error"].clone()), (422, json!("profile_not_supported")));
+
+    let large = post_json(addr, "/bills/pdf", &" ".repeat(2048)).await;
+    assert_eq!((large.status, large.json()["error"].clone()), (413, json!("payload_too_large")));
+
+    let unknown = post_json(addr, "/bills/docx", BILL).await;
+    assert_eq!((unknown.status, unknown.json()["error"].clone()), (404, json!("not_found")));
+}
+
+#[tokio::test]
+async fn decodes_qr_payloads() {
+    let addr = start(ServerConfig::default()).await;
+    let payload = "SPC\n0200\n1\nCH4431999123000889012\nS\nRobert Schneider AG\nRue du Lac\n1268\n2501\nBiel\nCH\n\n\n\n\n\n\n\n1949.75\nCHF\n\n\n\n\n\n\n\nQRR\n210000000003139471430009017\nOrder of 15 June 2020\nEPD\n";
+
+    let response = post(addr, "/qr/decode", "text/plain", payload).await;
+    assert_eq!(response.status, 200);
+    let bill = response.json();
+    assert_eq!(bill["creditor_address"]["name"], "Robert Schneider AG");
+    assert_eq!(bill["amount"], "1949.75");
+
+    let invalid = post(addr, "/qr/decode", "text/plain", "hello").await;
+    assert_eq!((invalid.status, invalid.json()["error"].clone()), (422, json!("invalid_qr_payload")));
+}
+
+#[tokio::test]
+async fn generates_references() {
+    let addr = start(ServerConfig::default()).await;
+
+    let qrr = post_json(addr, "/references", r#"{"kind": "qrr", "raw": "4711"}"#).await;
+    assert_eq!(
+        qrr.json(),
+        json!({ "reference": "000000000000000000000047119", "formatted": "00 00000 00000 00000 00000 47119" })
+    );
+
+    let scor = post_json(addr, "/references", r#"{"kind": "scor", "raw": "5390 0754 7034"}"#).await;
+    assert_eq!(scor.json()["reference"], "RF18539007547034");
+
+    let invalid = post_json(addr, "/references", r#"{"kind": "qrr", "raw": "12ab"}"#).await;
+    assert_eq!((invalid.status, invalid.json()["error"].clone()), (422, json!("invalid_reference")));
+
+    let malformed = post_json(addr, "/references", r#"{"kind": "iban"}"#).await;
+    assert_eq!(malformed.status, 422);
+}
+
+#[tokio::test]
+async fn sheds_requests_beyond_the_concurrency_limit() {
+    let addr = start(ServerConfig { max_concurrent_requests: 1, ..ServerConfig::default() }).await;
+
+    // Holds the only slot: the handler waits for the rest of the body.
+    let mut slow = TcpStream::connect(addr).await.unwrap();
+    slow.write_all(request_head("/bills/validate", "application/json", BILL.len()).as_bytes()).await.unwrap();
+    slow.write_all(&BILL.as_bytes()[..10]).await.unwrap();
+
+    let mut shed = None;
+    for _ in 0..50 {
+        let response = post_json(addr, "/bills/validate", BILL).await;
+        if response.status == 503 {
+            shed = Some(response);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(shed.expect("second request was not shed").json()["error"], "overloaded");
+
+    slow.write_all(&BILL.as_bytes()[10..]).await.unwrap();
+    assert_eq!(read_response(slow).await.json()["valid"], true);
+    assert_eq!(post_json(addr, "/bills/validate", BILL).await.status, 200);
+}
@@ -0,0 +1,83 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Errors thrown to JavaScript.
+//!
+//! Every failure is thrown as an `Error` named `"BillError"` with a `code`
+//! and, for bills, the dotted path of the offending `field` - the same
+//! codes and fields as the HTTP API's error bodies:
+//!
+//! ```text
+//! BillError: Invalid city character length
+//!   code: "invalid_bill", field: "creditor_address.city"
+//! ```
+
+use swiss_qrust::diagnostics::Diagnostic;
+use swiss_qrust::pdf::RenderError;
+use swiss_qrust::QrDecodeError;
+use wasm_bindgen::JsValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// One of the `BillErrorCode`s declared in `lib.rs`.
+    pub code: &'static str,
+    pub message: String,
+    pub field: Option<String>,
+}
+
+impl Error {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), field: None }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new("bad_request", message)
+    }
+
+    /// `{code, message, field?}` as a plain object, the shape of
+    /// `Validation.errors`.
+    pub fn to_object(&self) -> js_sys::Object {
+        let object = js_sys::Object::new();
+        self.assign(&object);
+        object
+    }
+
+    fn assign(&self, target: &js_sys::Object) {
+        // Setting a property on a fresh, unfrozen object can't fail.
+        let _ = js_sys::Reflect::set(target, &"code".into(), &self.code.into());
+        let _ = js_sys::Reflect::set(target, &"message".into(), &self.message.as_str().into());
+        if let Some(field) = &self.field {
+            let _ = js_sys::Reflect::set(target, &"field".into(), &field.as_str().into());
+        }
+    }
+}
+
+impl From<Error> for JsValue {
+    fn from(err: Error) -> Self {
+        let error = js_sys::Error::new(&err.message);
+        error.set_name("BillError");
+        err.assign(&error);
+        error.into()
+    }
+}
+
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self { code: "invalid_bill", message: diagnostic.message, field: diagnostic.field }
+    }
+}
+
+impl From<RenderError> for Error {
+    fn from(err: RenderError) -> Self {
+        Self::new("render_failed", err.to_string())
+    }
+}
+
+impl From<QrDecodeError> for Error {
+    fn from(err: QrDecodeError) -> Self {
+        Self::new("invalid_qr_payload", err.to_string())
+    }
+}
@@ -0,0 +1,402 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! The `swiss-qrust` npm package: `swiss_qrust` compiled to WebAssembly,
+//! for Node and browsers.
+//!
+//! Unlike the demo app's `crates/web/src/api.rs`, which passes JSON strings
+//! around, this takes and returns plain JS objects typed by the
+//! declarations below (bills in the input format of the CLI, snake_case
+//! like the files in `crates/cli/data`), renders to `Uint8Array`s and
+//! throws `BillError`s (see [`error`]). Objects cross the boundary as JSON
+//! (`JSON.stringify` / `JSON.parse`), so bills go through exactly the
+//! validation the CLI, server and C API use.
+
+mod error;
+
+use serde_json::Value;
+use swiss_qrust::bill::qr_bill::QrBill;
+use swiss_qrust::diagnostics::parse_and_validate;
+use swiss_qrust::pdf::render_bill_to_pdf;
+use swiss_qrust::png_renderers::render_bill_to_png;
+use swiss_qrust::svg::render_bill_to_svg;
+use swiss_qrust::{
+    decode_qr_text, generate_iso11649_with_checksum, generate_qrr_with_checksum, is_valid_iso11649_reference,
+    is_valid_qr_reference, BillData, InputSwicoBillInformation, Language as RustLanguage, SwicoBillInformation,
+};
+use wasm_bindgen::prelude::*;
+
+use crate::error::Error;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &'static str = r#"
+export interface Address {
+    name: string;
+    street?: string;
+    house_num?: string;
+    plz: string;
+    city: string;
+    /** ISO 3166-1 alpha-2 code, e.g. `"CH"` (see `Bill.resolve_countries`). */
+    country: string;
+}
+
+export interface RateDetail {
+    rate: number;
+    amount: number;
+}
+
+export interface PaymentCondition {
+    discount: number;
+    days: number;
+}
+
+/** Swico S1 billing information; dates as `"YYYY-MM-DD"`. */
+export interface SwicoBillInformation {
+    invoice_number?: string;
+    invoice_date?: string;
+    customer_reference?: string;
+    vat_number?: string;
+    vat_date?: string;
+    vat_start_date?: string;
+    vat_end_date?: string;
+    vat_rate?: number;
+    vat_rate_details?: RateDetail[];
+    vat_import_taxes?: RateDetail[];
+    payment_conditions?: PaymentCondition[];
+}
+
+/** A bill in the input format of the command line tool. */
+export interface Bill {
+    iban: string;
+    creditor_address: Address;
+    debtor_address?: Address;
+    currency: "CHF" | "EUR";
+    /** Numbers are written with two decimals. */
+    amount?: string | number;
+    /** QR reference (with a QR-IBAN) or creditor reference (`RF...`). */
+    reference?: string;
+    unstructured_message?: string;
+    bill_information?: string;
+    /** Encoded into `bill_information`; giving both is an error. */
+    swico_bill_information?: SwicoBillInformation;
+    alternative_schemes?: [string | null, string | null];
+    /** Transliterate text outside the SPS character set instead of rejecting it. */
+    sanitize?: boolean;
+    /** Accept country names and alpha-3/numeric codes in addresses. */
+    resolve_countries?: boolean;
+    /** Language of a "do not use for payment" notification. */
+    notification?: "de" | "fr" | "it" | "en";
+    /** Values for `{name}` placeholders in the message and billing information. */
+    variables?: Record<string, string>;
+}
+
+export type BillErrorCode =
+    | "bad_request"
+    | "invalid_bill"
+    | "profile_not_supported"
+    | "invalid_reference"
+    | "invalid_qr_payload"
+    | "invalid_swico"
+    | "render_failed";
+
+export interface BillErrorDetails {
+    code: BillErrorCode;
+    message: string;
+    /** Dotted path of the offending input field, e.g. `"creditor_address.city"`. */
+    field?: string;
+}
+
+/** Thrown by every function of this package. */
+export interface BillError extends Error, BillErrorDetails {
+    name: "BillError";
+}
+
+export interface Validation {
+    valid: boolean;
+    errors: BillErrorDetails[];
+}
+"#;
+
+/// Language of the slip's labels.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    De,
+    Fr,
+    It,
+    En,
+}
+
+impl From<Language> for RustLanguage {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::De => RustLanguage::De,
+            Language::Fr => RustLanguage::Fr,
+            Language::It => RustLanguage::It,
+            Language::En => RustLanguage::En,
+        }
+    }
+}
+
+fn language(language: Option<Language>) -> RustLanguage {
+    language.map_or(RustLanguage::De, RustLanguage::from)
+}
+
+/// Upper bound for `dpi`, as in the HTTP API.
+const MAX_DPI: f64 = 1200.0;
+
+/// A JS value as JSON text.
+fn to_json(value: &JsValue) -> Result<String, Error> {
+    js_sys::JSON::stringify(value)
+        .ok()
+        .and_then(|json| json.as_string())
+        .ok_or_else(|| Error::bad_request("expected a JSON-serializable object"))
+}
+
+/// JSON as a plain JS value, without the `null`s of unset fields.
+fn to_js(mut value: Value) -> JsValue {
+    strip_nulls(&mut value);
+    // `serde_json` output is always valid JSON.
+    js_sys::JSON::parse(&value.to_string()).unwrap_or(JsValue::UNDEFINED)
+}
+
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+/// Parses and validates a bill given as JSON text.
+fn parse_bill(json: &str) -> Result<BillData, Error> {
+    let mut value: Value = serde_json::from_str(json).map_err(|e| Error::bad_request(e.to_string()))?;
+    if value.get("profile").is_some_and(|p| !p.is_null()) {
+        return Err(Error::new(
+            "profile_not_supported",
+            "creditor profiles are not supported here; merge the profile into the bill",
+        ));
+    }
+    if let Some(amount) = value.get_mut("amount")
+        && let Some(number) = amount.as_f64()
+    {
+        *amount = Value::String(format!("{number:.2}"));
+    }
+    Ok(parse_and_validate(&value.to_string(), "json", "bill")?)
+}
+
+fn bill(value: &JsValue) -> Result<BillData, Error> {
+    parse_bill(&to_json(value)?)
+}
+
+/// Renders the bill (receipt and payment part on an A4 page) to a PDF.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = renderPdf)]
+pub fn render_pdf(
+    #[wasm_bindgen(unchecked_param_type = "Bill")] bill: &JsValue,
+    language: Option<Language>,
+) -> Result<Vec<u8>, Error> {
+    Ok(render_bill_to_pdf(&self::bill(bill)?, self::language(language))?)
+}
+
+/// Renders the bill (receipt and payment part, 210 x 105 mm) to SVG.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = renderSvg)]
+pub fn render_svg(
+    #[wasm_bindgen(unchecked_param_type = "Bill")] bill: &JsValue,
+    language: Option<Language>,
+) -> Result<String, Error> {
+    Ok(render_bill_to_svg(&self::bill(bill)?, self::language(language))?)
+}
+
+/// Renders the bill to a PNG at `dpi` (300 if omitted, at most 1200).
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = renderPng)]
+pub fn render_png(
+    #[wasm_bindgen(unchecked_param_type = "Bill")] bill: &JsValue,
+    language: Option<Language>,
+    dpi: Option<f64>,
+) -> Result<Vec<u8>, Error> {
+    let dpi = match dpi.unwrap_or(300.0) {
+        dpi if dpi > 0.0 && dpi <= MAX_DPI => dpi,
+        dpi => return Err(Error::bad_request(format!("dpi must be between 0 and {MAX_DPI}, got {dpi}"))),
+    };
+    Ok(render_bill_to_png(&self::bill(bill)?, self::language(language), dpi)?)
+}
+
+/// Validates the bill without rendering it. Throws only if `bill` can't be
+/// read at all.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = validateBill, unchecked_return_type = "Validation")]
+pub fn validate_bill(#[wasm_bindgen(unchecked_param_type = "Bill")] bill: &JsValue) -> Result<JsValue, Error> {
+    let errors = js_sys::Array::new();
+    if let Err(err) = parse_bill(&to_json(bill)?) {
+        errors.push(&err.to_object());
+    }
+    let validation = js_sys::Object::new();
+    js_sys::Reflect::set(&validation, &"valid".into(), &(errors.length() == 0).into()).ok();
+    js_sys::Reflect::set(&validation, &"errors".into(), &errors).ok();
+    Ok(validation.into())
+}
+
+/// The text encoded in the bill's QR code.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = qrPayload)]
+pub fn qr_payload(#[wasm_bindgen(unchecked_param_type = "Bill")] bill: &JsValue) -> Result<String, Error> {
+    let bill = self::bill(bill)?;
+    QrBill::new(&bill)
+        .and_then(|qr| qr.create_qr_text())
+        .map_err(|e| Error::new("invalid_bill", e.to_string()))
+}
+
+/// Reads the text encoded in a QR-bill's QR code back into a bill.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = decodeQrPayload, unchecked_return_type = "Bill")]
+pub fn decode_qr_payload(payload: &str) -> Result<JsValue, Error> {
+    let input = decode_qr_text(payload)?;
+    let value = serde_json::to_value(&input).map_err(|e| Error::new("invalid_qr_payload", e.to_string()))?;
+    Ok(to_js(value))
+}
+
+fn invalid_reference(raw: &str) -> Error {
+    Error::new("invalid_reference", format!("cannot generate a reference from '{raw}'"))
+}
+
+fn qr_reference(raw: &str) -> Result<String, Error> {
+    generate_qrr_with_checksum(raw).map_err(|_| invalid_reference(raw))
+}
+
+fn scor_reference(raw: &str) -> Result<String, Error> {
+    generate_iso11649_with_checksum(raw).map_err(|_| invalid_reference(raw))
+}
+
+/// Appends the check digit to 1 to 26 digits:
+/// `"4711"` -> `"000000000000000000000047119"`.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = generateQrReference)]
+pub fn generate_qr_reference(raw: &str) -> Result<String, Error> {
+    qr_reference(raw)
+}
+
+/// Creates an ISO 11649 creditor reference:
+/// `"539007547034"` -> `"RF18539007547034"`.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = generateScorReference)]
+pub fn generate_scor_reference(raw: &str) -> Result<String, Error> {
+    scor_reference(raw)
+}
+
+#[wasm_bindgen(js_name = isValidQrReference)]
+pub fn is_valid_qr_reference_js(reference: &str) -> bool {
+    is_valid_qr_reference(reference).is_ok()
+}
+
+#[wasm_bindgen(js_name = isValidScorReference)]
+pub fn is_valid_scor_reference_js(reference: &str) -> bool {
+    is_valid_iso11649_reference(reference).is_ok()
+}
+
+fn swico_error(message: String) -> Error {
+    Error::new("invalid_swico", message)
+}
+
+fn encode_swico_json(json: &str) -> Result<Option<String>, Error> {
+    let input: InputSwicoBillInformation = serde_json::from_str(json).map_err(|e| Error::bad_request(e.to_string()))?;
+    let info = SwicoBillInformation::try_from(input).map_err(|e| swico_error(e.to_string()))?;
+    Ok(info.encode_as_text())
+}
+
+fn decode_swico_value(text: &str, strict: bool) -> Result<Option<Value>, Error> {
+    let info = if strict {
+        Some(SwicoBillInformation::decode_strict(text).map_err(|e| swico_error(e.to_string()))?)
+    } else {
+        SwicoBillInformation::decode_text(text)
+    };
+    info.map(|info| {
+        serde_json::to_value(InputSwicoBillInformation::from(&info)).map_err(|e| swico_error(e.to_string()))
+    })
+    .transpose()
+}
+
+/// Swico S1 text (`//S1/10/...`) for `bill_information`; `undefined` if
+/// `info` is empty.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = encodeSwico)]
+pub fn encode_swico(
+    #[wasm_bindgen(unchecked_param_type = "SwicoBillInformation")] info: &JsValue,
+) -> Result<Option<String>, Error> {
+    encode_swico_json(&to_json(info)?)
+}
+
+/// The reverse of `encodeSwico`. Lenient by default: `undefined` if `text`
+/// isn't Swico S1, malformed parts are skipped. With `strict`, any
+/// violation throws.
+///
+/// @throws {BillError}
+#[wasm_bindgen(js_name = decodeSwico, unchecked_return_type = "SwicoBillInformation | undefined")]
+pub fn decode_swico(text: &str, strict: Option<bool>) -> Result<JsValue, Error> {
+    Ok(decode_swico_value(text, strict.unwrap_or(false))?.map_or(JsValue::UNDEFINED, to_js))
+}
+
+/// Whether `value` was thrown by this package, for `catch (e: unknown)`.
+#[wasm_bindgen(js_name = isBillError, unchecked_return_type = "value is BillError")]
+pub fn is_bill_error(#[wasm_bindgen(unchecked_param_type = "unknown")] value: &JsValue) -> bool {
+    value.dyn_ref::<js_sys::Error>().is_some_and(|error| error.name() == "BillError")
+        && js_sys::Reflect::has(value, &"code".into()).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BILL: &str = include_str!("../../cli/data/robert_schneider.json");
+
+    #[test]
+    fn writes_numeric_amounts_with_two_decimals() {
+        let mut bill: Value = serde_json::from_str(BILL).unwrap();
+        bill["amount"] = Value::from(250);
+        assert_eq!(parse_bill(&bill.to_string()).unwrap().amount.as_deref(), Some("250.00"));
+    }
+
+    #[test]
+    fn locates_invalid_fields() {
+        let mut bill: Value = serde_json::from_str(BILL).unwrap();
+        bill["creditor_address"]["city"] = Value::from("");
+        let err = parse_bill(&bill.to_string()).unwrap_err();
+        assert_eq!((err.code, err.field.as_deref()), ("invalid_bill", Some("creditor_address.city")));
+
+        bill["profile"] = Value::from("acme.toml");
+        assert_eq!(parse_bill(&bill.to_string()).unwrap_err().code, "profile_not_supported");
+    }
+
+    #[test]
+    fn generates_references() {
+        assert_eq!(qr_reference("4711").unwrap(), "000000000000000000000047119");
+        assert_eq!(scor_reference("539007547034").unwrap(), "RF18539007547034");
+        assert_eq!(qr_reference("12ab").unwrap_err().code, "invalid_reference");
+    }
+
+    #[test]
+    fn round_trips_swico() {
+        let text = encode_swico_json(r#"{"invoice_number": "10201409", "vat_rate": 7.7}"#).unwrap();
+        assert_eq!(text.as_deref(), Some("//S1/10/10201409/32/7.7"));
+        let info = decode_swico_value(&text.unwrap(), true).unwrap().unwrap();
+        assert_eq!(info["invoice_number"], "10201409");
+        assert_eq!(decode_swico_value("//S1/11/1905", true).unwrap_err().code, "invalid_swico");
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2026 Thomas Prosser
+// Licensed under MIT License
+// https://opensource.org/licenses/MIT
+//
+// Run with: cd crates/wasm && npm run build && npm test
+
+import assert from "node:assert/strict";
+import { readFileSync } from "node:fs";
+import { test } from "node:test";
+
+import {
+    Language,
+    decodeQrPayload,
+    decodeSwico,
+    encodeSwico,
+    generateQrReference,
+    generateScorReference,
+    isBillError,
+    isValidQrReference,
+    isValidScorReference,
+    qrPayload,
+    renderPdf,
+    renderPng,
+    renderSvg,
+    validateBill,
+} from "swiss-qrust";
+
+const BILL = JSON.parse(readFileSync(new URL("../../cli/data/robert_schneider.json", import.meta.url)));
+
+/** Calls `f` and returns what it threw. */
+function thrown(f) {
+    try {
+        f();
+    } catch (e) {
+        return e;
+    }
+    assert.fail("did not throw");
+}
+
+test("renders PDF, SVG and PNG", () => {
+    const pdf = renderPdf(BILL, Language.Fr);
+    assert.ok(pdf instanceof Uint8Array);
+    assert.equal(new TextDecoder().decode(pdf.subarray(0, 4)), "%PDF");
+    assert.ok(renderSvg(BILL).startsWith("<svg"));
+    assert.deepEqual([...renderPng(BILL, Language.En, 72).subarray(1, 4)], [0x50, 0x4e, 0x47]);
+});
+
+test("accepts numeric amounts and missing optional fields", () => {
+    const bill = { ...BILL, amount: 250, debtor_address: undefined };
+    assert.match(qrPayload(bill), /\r\n250\.00\r\nCHF\r\n/);
+});
+
+test("throws structured errors", () => {
+    const error = thrown(() => renderPdf({ ...BILL, creditor_address: { ...BILL.creditor_address, city: "" } }));
+    assert.ok(error instanceof Error);
+    assert.ok(isBillError(error));
+    assert.equal(error.name, "BillError");
+    assert.equal(error.code, "invalid_bill");
+    assert.equal(error.field, "creditor_address.city");
+
+    assert.equal(thrown(() => renderPng(BILL, Language.De, 5000)).code, "bad_request");
+    assert.equal(thrown(() => renderSvg({ ...BILL, profile: "acme.toml" })).code, "profile_not_supported");
+    assert.ok(!isBillError(new Error("other")));
+});
+
+test("validates without throwing", () => {
+    assert.deepEqual(validateBill(BILL), { valid: true, errors: [] });
+
+    const validation = validateBill({ ...BILL, reference: undefined });
+    assert.equal(validation.valid, false);
+    assert.equal(validation.errors[0].code, "invalid_bill");
+    // The QR-IBAN is what requires the reference.
+    assert.equal(validation.errors[0].field, "iban");
+});
+
+test("round-trips through the QR payload", () => {
+    const decoded = decodeQrPayload(qrPayload(BILL));
+    assert.equal(decoded.creditor_address.name, "Robert Schneider AG");
+    assert.equal(decoded.debtor_address.city, BILL.debtor_address.city);
+    assert.equal(qrPayload(decoded), qrPayload(BILL));
+    assert.equal(thrown(() => decodeQrPayload("hello")).code, "invalid_qr_payload");
+});
+
+test("generates and checks references", () => {
+    assert.equal(generateQrReference("4711"), "000000000000000000000047119");
+    assert.equal(generateScorReference("5390 0754 7034"), "RF18539007547034");
+    assert.ok(isValidQrReference("210000000003139471430009017"));
+    assert.ok(!isValidQrReference("210000000003139471430009018"));
+    assert.ok(isValidScorReference("RF18539007547034"));
+    assert.equal(thrown(() => generateQrReference("12ab")).code, "invalid_reference");
+});
+
+test("encodes and decodes Swico billing information", () => {
+    const info = { invoice_number: "10201409", invoice_date: "2019-05-12", vat_rate: 7.7 };
+    const text = encodeSwico(info);
+
+    assert.equal(text, "//S1/10/10201409/11/190512/32/7.7");
+    assert.deepEqual(decodeSwico(text), info);
+    assert.equal(decodeSwico("Order 17"), undefined);
+    assert.equal(thrown(() => decodeSwico("//S1/11/1905", true)).code, "invalid_swico");
+    assert.equal(thrown(() => encodeSwico({ invoice_date: "12.05.2019" })).code, "invalid_swico");
+});
@@ -20,7 +20,7 @@ use swiss_qrust::pdf::render_bill_to_pdf;
 use swiss_qrust::svg::render_bill_to_svg;
 use swiss_qrust::{
     generate_iso11649_with_checksum, generate_qrr_with_checksum, is_qr_iban, is_valid_iban, label,
-    Country, InputBill, Language, LabelKey, ReferenceType,
+    Country, InputBill, Language, LabelKey, ReferenceType, SwicoBillInformation,
 };
 
 fn parse_lang(lang: &str) -> Language {
@@ -88,6 +88,13 @@ pub fn generate_scor_reference(raw: &str) -> Result<String, JsValue> {
     generate_iso11649_with_checksum(raw).map_err(to_js_err)
 }
 
+/// Spells out Swico S1 billing information (`//S1/10/...`) in the given
+/// language for the preview; `undefined` if the text isn't S1.
+#[wasm_bindgen]
+pub fn describe_swico_bill_information(text: &str, lang: &str) -> Option<String> {
+    SwicoBillInformation::decode_text(text.trim()).map(|info| info.localized_text(parse_lang(lang)))
+}
+
 /// Validates a reference number against the given IBAN's QR-ness: returns
 /// `{"valid": bool, "error": string | null}` as JSON. A QR-IBAN requires a
 /// QR reference; a normal IBAN must not have one (SCOR or none are fine).
@@ -0,0 +1,34 @@
+#!/usr/bin/env bash
+#
+# Replace the bundled postal directory with the full Swiss Post export
+# ("Postleitzahlen der Schweiz", open data, semicolon separated).
+#
+# build_functions/build_postal_codes.rs reads the export as-is (columns are
+# looked up by name) and, since it carries no "# partial snapshot" marker,
+# treats it as complete - `--check-postal-codes` then also reports unknown
+# Swiss and Liechtenstein postal codes.
+#
+# Usage:
+#   scripts/update_postal_directory.sh          # download the current export
+#   scripts/update_postal_directory.sh <url>    # download from another URL
+set -euo pipefail
+cd "$(dirname "$0")/.."
+
+url="${1:-https://swisspost.opendatasoft.com/api/explore/v2.1/catalog/datasets/plz_verzeichnis_v2/exports/csv?delimiter=%3B}"
+target=assets/data/postal_codes.csv
+
+tmp="$(mktemp)"
+trap 'rm -f "$tmp"' EXIT
+curl --fail --silent --show-error --location "$url" --output "$tmp"
+
+header="$(head -n 1 "$tmp" | tr '[:upper:]' '[:lower:]')"
+for column in postleitzahl ortbez27 kanton; do
+    if [[ "$header" != *"$column"* ]]; then
+        echo "error: the download has no '$column' column, is it the Swiss Post export?" >&2
+        exit 1
+    fi
+done
+
+mv "$tmp" "$target"
+trap - EXIT
+echo "==> $(($(wc -l < "$target") - 1)) rows written to $target"
@@ -21,6 +21,14 @@ pub enum AddressIdentity {
 
 const ADDRESS_TYPE: &str = "S";
 
+/// Field length limits from spec 4.2.2 (Cdtr / UltmtDbtr), shared by
+/// [`Address::new`] and the input JSON Schema.
+pub const NAME_MAX_LEN: usize = 70;
+pub const STREET_MAX_LEN: usize = 70;
+pub const HOUSE_NUM_MAX_LEN: usize = 16;
+pub const PLZ_MAX_LEN: usize = 16;
+pub const CITY_MAX_LEN: usize = 35;
+
 #[derive(Error, Debug)]
 pub enum AddressError {
     #[error("Invalid name: {0}")]
@@ -35,8 +43,24 @@ pub enum AddressError {
     AddressCityError,
     #[error("invalid country code: {0}")]
     AddressCountryError(String),
-    #[error(transparent)]
-    AddressCharsetError(#[from] SPSCharsetError),
+    /// `field` is the address field with the character, e.g. `"street"`.
+    #[error("{error}")]
+    AddressCharsetError { field: &'static str, error: SPSCharsetError },
+}
+
+impl AddressError {
+    /// The [`InputAddress`] field the error is about, e.g. `"city"`.
+    pub fn field(&self) -> &'static str {
+        match self {
+            AddressError::AddressNameError(_) => "name",
+            AddressError::AddressStreetError(_) => "street",
+            AddressError::AddressHouseError => "house_num",
+            AddressError::AddressPostalCodeError => "plz",
+            AddressError::AddressCityError => "city",
+            AddressError::AddressCountryError(_) => "country",
+            AddressError::AddressCharsetError { field, .. } => field,
+        }
+    }
 }
 
 /// No need for unstructured address
@@ -75,9 +99,9 @@ impl Address {
         city: &str,
         country: &str,
     ) -> Result<Self, AddressError> {
-        // Name
+        // Name. Lengths count characters, as in the SPS and the JSON schema.
         let name = name.trim().to_string();
-        if !(1..=70).contains(&name.len()) {
+        if !(1..=NAME_MAX_LEN).contains(&name.chars().count()) {
             return Err(AddressError::AddressNameError(
                 "Name must be 1–70 chars".into(),
             ));
@@ -89,7 +113,7 @@ impl Address {
             .filter(|s| !s.is_empty())
             .map(|s| s.to_string());
         if let Some(ref s) = street
-            && s.len() > 70 {
+            && s.chars().count() > STREET_MAX_LEN {
                 return Err(AddressError::AddressStreetError(
                     "Street must not be longer than 70 chars".into(),
                 ));
@@ -101,19 +125,19 @@ impl Address {
             .filter(|s| !s.is_empty())
             .map(|s| s.to_string());
         if let Some(ref h) = house_num
-            && h.len() > 16 {
+            && h.chars().count() > HOUSE_NUM_MAX_LEN {
                 return Err(AddressError::AddressHouseError);
             }
 
         // Postal code
         let plz = plz.trim().to_string();
-        if plz.is_empty() || plz.len() > 16 {
+        if plz.is_empty() || plz.chars().count() > PLZ_MAX_LEN {
             return Err(AddressError::AddressPostalCodeError);
         }
 
         // City
         let city = city.trim().to_string();
-        if city.is_empty() || city.len() > 35 {
+        if city.is_empty() || city.chars().count() > CITY_MAX_LEN {
             return Err(AddressError::AddressCityError);
         }
 
@@ -130,15 +154,18 @@ impl Address {
             .map_err(|e| AddressError::AddressCountryError(e.to_string()))?;
 
         // Character set (spec 4.1.1): applies to every general text field.
-        is_valid_sps_charset(&name)?;
+        let charset = |field, text: &str| {
+            is_valid_sps_charset(text).map_err(|error| AddressError::AddressCharsetError { field, error })
+        };
+        charset("name", &name)?;
         if let Some(ref s) = street {
-            is_valid_sps_charset(s)?;
+            charset("street", s)?;
         }
         if let Some(ref h) = house_num {
-            is_valid_sps_charset(h)?;
+            charset("house_num", h)?;
         }
-        is_valid_sps_charset(&plz)?;
-        is_valid_sps_charset(&city)?;
+        charset("plz", &plz)?;
+        charset("city", &city)?;
 
         Ok(Self {
             address_type: ADDRESS_TYPE.into(),
@@ -172,7 +199,7 @@ impl Address {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InputAddress {
     pub name: String,
     pub street: Option<String>,
@@ -182,6 +209,17 @@ pub struct InputAddress {
     pub country: String,
 }
 
+impl InputAddress {
+    /// Replaces `country` with its alpha-2 code if [`Country::resolve`]
+    /// recognizes it ("Schweiz", "CHE", "756" -> "CH"). Unrecognized values
+    /// are left for [`Address::new`] to reject.
+    pub fn resolve_country(&mut self) {
+        if let Some(country) = Country::resolve(&self.country) {
+            self.country = country.meta().alpha2.to_string();
+        }
+    }
+}
+
 impl TryFrom<InputAddress> for Address {
     type Error = AddressError;
 
@@ -204,7 +242,38 @@ mod tests {
     #[test]
     fn rejects_name_outside_permitted_charset() {
         let err = Address::new("Hello 🤣", None, None, "3000", "Bern", "CH").unwrap_err();
-        assert!(matches!(err, AddressError::AddressCharsetError(_)));
+        assert!(matches!(err, AddressError::AddressCharsetError { field: "name", .. }));
+    }
+
+    #[test]
+    fn resolve_country_accepts_names() {
+        let mut input = InputAddress {
+            name: "Pia Rutschmann".into(),
+            street: None,
+            house_num: None,
+            plz: "9490".into(),
+            city: "Vaduz".into(),
+            country: "Fürstentum Liechtenstein".into(),
+        };
+        assert!(Address::try_from(input.clone()).is_err());
+
+        input.resolve_country();
+        assert_eq!(Address::try_from(input).unwrap().country, Country::LI);
+    }
+
+    #[test]
+    fn lengths_count_characters_not_bytes() {
+        let name = "Müller".repeat(11) + "Jörg";
+        assert_eq!(name.chars().count(), NAME_MAX_LEN);
+        assert!(name.len() > NAME_MAX_LEN);
+        let street = "ä".repeat(STREET_MAX_LEN);
+        let city = "ü".repeat(CITY_MAX_LEN);
+        assert!(Address::new(&name, Some(&street), None, "8000", &city, "CH").is_ok());
+
+        let err = Address::new(&(name + "s"), None, None, "8000", "Zürich", "CH").unwrap_err();
+        assert!(matches!(err, AddressError::AddressNameError(_)));
+        let err = Address::new("Jörg", Some(&(street + "ä")), None, "8000", "Zürich", "CH").unwrap_err();
+        assert!(matches!(err, AddressError::AddressStreetError(_)));
     }
 
     #[test]
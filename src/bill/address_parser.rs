@@ -0,0 +1,376 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Splits free-form address lines ("Rue du Lac 1268", "2501 Biel") into a
+//! structured [`Address`].
+//!
+//! The combined address type "K" was removed from the QR-bill on 21 Nov
+//! 2025, so addresses kept as two or three text lines in legacy systems
+//! have to be split into street, house number, postal code and town. This
+//! is heuristic: [`parse_address_lines`] reports how sure it is
+//! ([`Confidence`]) and what it had to guess ([`Ambiguity`]), so callers can
+//! route doubtful cases to a human.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use thiserror::Error;
+
+use crate::{Address, AddressError, Country};
+
+#[derive(Debug, Error)]
+pub enum AddressParseError {
+    #[error("No line with postal code and town found")]
+    NoPostalLine,
+    #[error(transparent)]
+    Address(#[from] AddressError),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// Something [`parse_address_lines`] had to guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ambiguity {
+    /// No country prefix ("CH-") or country line; the default was used.
+    CountryAssumed(Country),
+    /// The postal code doesn't have the usual format for the country
+    /// (e.g. five digits for Switzerland).
+    PostalCodeFormat { plz: String, country: Country },
+    /// The street line has a number at both ends ("3 Route 66"); the one
+    /// given in `chosen` was taken as the house number.
+    HouseNumberPosition { chosen: String, other: String },
+    /// A line that couldn't be placed in a structured field (c/o, building
+    /// name, second street line, ...) and was left out.
+    DiscardedLine(String),
+}
+
+impl Ambiguity {
+    /// Minor ambiguities are expected with normal data (most legacy
+    /// addresses don't carry a country); major ones mean the result may
+    /// well be wrong.
+    fn is_major(&self) -> bool {
+        matches!(self, Ambiguity::PostalCodeFormat { .. } | Ambiguity::HouseNumberPosition { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAddress {
+    pub address: Address,
+    pub confidence: Confidence,
+    pub ambiguities: Vec<Ambiguity>,
+}
+
+/// Country prefixes used in front of postal codes ("CH-2501", "D-79539",
+/// "FL-9490"): the old international licence plate codes plus ISO alpha-2.
+const POSTAL_PREFIXES: &[(&str, Country)] = &[
+    ("CH", Country::CH),
+    ("FL", Country::LI),
+    ("LI", Country::LI),
+    ("D", Country::DE),
+    ("DE", Country::DE),
+    ("F", Country::FR),
+    ("FR", Country::FR),
+    ("I", Country::IT),
+    ("IT", Country::IT),
+    ("A", Country::AT),
+    ("AT", Country::AT),
+];
+
+/// `[prefix-]plz town`
+static POSTAL_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(?P<prefix>[A-Za-z]{1,2})(?:\s?-\s?|\s))?(?P<plz>\d{4,5})\s+(?P<city>\S.*)$").unwrap()
+});
+
+/// A house number: "12", "12a", "12 a", "12-14", "12/3", "12bis", "7 ter".
+const HOUSE_NUMBER: &str = r"\d+(?:\s?(?:bis|ter|quater)\b|\s?[A-Za-z]\b)?(?:\s?[-/]\s?\d+[A-Za-z]?)?";
+
+static TRAILING_NUMBER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"^(?P<street>.*?\D)[\s,]+(?P<num>{HOUSE_NUMBER})$")).unwrap()
+});
+
+static LEADING_NUMBER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"^(?P<num>{HOUSE_NUMBER})(?:\s*,\s*|\s+)(?P<street>\D.*)$")).unwrap()
+});
+
+/// "Postfach 123", "Case postale 12", "Casella postale", "P.O. Box 7", "CP 4"
+static POST_BOX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?P<label>postfach|case postale|casella postale|p\.?\s?o\.?\s?box|cp)\.?(?:\s+(?P<num>\d+))?$").unwrap()
+});
+
+/// Parses address lines (without the name) into an [`Address`].
+///
+/// The last line with a postal code and town is the postal line; a line
+/// after it may hold the country (anything [`Country::resolve`] accepts). Of the lines before it,
+/// the last street-like line is used, falling back to a post box line.
+/// A single line is split at commas first ("Rue du Lac 1268, 2501 Biel").
+///
+/// ```
+/// use swiss_qrust::{parse_address_lines, Confidence, Country};
+///
+/// let parsed = parse_address_lines("Robert Schneider AG", &["Rue du Lac 1268", "CH-2501 Biel"], Country::CH)?;
+/// assert_eq!(parsed.address.street.as_deref(), Some("Rue du Lac"));
+/// assert_eq!(parsed.address.house_num.as_deref(), Some("1268"));
+/// assert_eq!(parsed.address.plz, "2501");
+/// assert_eq!(parsed.confidence, Confidence::High);
+/// # Ok::<(), swiss_qrust::AddressParseError>(())
+/// ```
+pub fn parse_address_lines(
+    name: &str,
+    lines: &[&str],
+    default_country: Country,
+) -> Result<ParsedAddress, AddressParseError> {
+    let mut lines: Vec<&str> = lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+    if lines.len() == 1 {
+        lines = lines[0].split(',').map(str::trim).filter(|l| !l.is_empty()).collect();
+    }
+
+    let mut ambiguities = Vec::new();
+
+    // Postal line, optionally followed by a country line.
+    let postal_idx = lines
+        .iter()
+        .rposition(|l| POSTAL_LINE.is_match(l))
+        .ok_or(AddressParseError::NoPostalLine)?;
+    let caps = POSTAL_LINE.captures(lines[postal_idx]).ok_or(AddressParseError::NoPostalLine)?;
+    let plz = caps["plz"].to_string();
+    let city = caps["city"].trim().to_string();
+
+    let prefix_country = caps.name("prefix").and_then(|p| {
+        POSTAL_PREFIXES
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(p.as_str()))
+            .map(|(_, country)| *country)
+    });
+    let mut line_country = None;
+    for extra in &lines[postal_idx + 1..] {
+        match Country::resolve(extra) {
+            Some(country) if line_country.is_none() => line_country = Some(country),
+            _ => ambiguities.push(Ambiguity::DiscardedLine(extra.to_string())),
+        }
+    }
+
+    let country = match line_country.or(prefix_country) {
+        Some(country) => country,
+        None => {
+            let guessed = guess_country(&plz, default_country);
+            ambiguities.push(Ambiguity::CountryAssumed(guessed));
+            guessed
+        }
+    };
+    if !postal_code_matches(&plz, country) {
+        ambiguities.push(Ambiguity::PostalCodeFormat { plz: plz.clone(), country });
+    }
+
+    // Street: last street-like line before the postal line; post box lines
+    // only if there is nothing better.
+    let before = &lines[..postal_idx];
+    let street_idx = before
+        .iter()
+        .rposition(|l| !POST_BOX.is_match(l))
+        .or_else(|| before.len().checked_sub(1));
+    for (i, line) in before.iter().enumerate() {
+        if Some(i) != street_idx {
+            ambiguities.push(Ambiguity::DiscardedLine(line.to_string()));
+        }
+    }
+
+    let (street, house_num) = match street_idx.map(|i| before[i]) {
+        Some(line) => split_street_line(line, &mut ambiguities),
+        None => (None, None),
+    };
+
+    let address = Address::new(
+        name,
+        street.as_deref(),
+        house_num.as_deref(),
+        &plz,
+        &city,
+        country.meta().alpha2,
+    )?;
+
+    let confidence = if ambiguities.iter().any(Ambiguity::is_major) {
+        Confidence::Low
+    } else if ambiguities.iter().any(|a| !matches!(a, Ambiguity::CountryAssumed(c) if *c == default_country)) {
+        Confidence::Medium
+    } else {
+        Confidence::High
+    };
+
+    Ok(ParsedAddress { address, confidence, ambiguities })
+}
+
+/// Splits "Bahnhofstrasse 12a" / "12bis, rue de la Gare" / "Postfach 123"
+/// into street and house number.
+fn split_street_line(line: &str, ambiguities: &mut Vec<Ambiguity>) -> (Option<String>, Option<String>) {
+    if let Some(caps) = POST_BOX.captures(line) {
+        return (Some(caps["label"].to_string()), caps.name("num").map(|n| n.as_str().to_string()));
+    }
+
+    let trailing = TRAILING_NUMBER.captures(line);
+    let leading = LEADING_NUMBER.captures(line);
+
+    match (trailing, leading) {
+        (Some(t), Some(l)) => {
+            // "3 Route 66": the Swiss convention (number after the street)
+            // wins, but the result is flagged.
+            ambiguities.push(Ambiguity::HouseNumberPosition {
+                chosen: t["num"].to_string(),
+                other: l["num"].to_string(),
+            });
+            (Some(t["street"].trim().to_string()), Some(t["num"].to_string()))
+        }
+        (Some(t), None) => (Some(t["street"].trim().to_string()), Some(t["num"].to_string())),
+        (None, Some(l)) => (Some(l["street"].trim().to_string()), Some(l["num"].to_string())),
+        (None, None) => (Some(line.to_string()), None),
+    }
+}
+
+/// Without any country indication, four digits are Swiss (or, for
+/// 9485-9498, Liechtenstein) when the default is CH or LI.
+fn guess_country(plz: &str, default_country: Country) -> Country {
+    if matches!(default_country, Country::CH | Country::LI) && plz.len() == 4 {
+        return match plz.parse::<u32>() {
+            Ok(9485..=9498) => Country::LI,
+            _ => Country::CH,
+        };
+    }
+    default_country
+}
+
+fn postal_code_matches(plz: &str, country: Country) -> bool {
+    match country {
+        Country::CH | Country::LI | Country::AT => plz.len() == 4,
+        Country::DE | Country::FR | Country::IT => plz.len() == 5,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Case {
+        lines: &'static [&'static str],
+        street: Option<&'static str>,
+        house_num: Option<&'static str>,
+        plz: &'static str,
+        city: &'static str,
+        country: Country,
+        confidence: Confidence,
+    }
+
+    const fn case(
+        lines: &'static [&'static str],
+        street: Option<&'static str>,
+        house_num: Option<&'static str>,
+        plz: &'static str,
+        city: &'static str,
+        country: Country,
+        confidence: Confidence,
+    ) -> Case {
+        Case { lines, street, house_num, plz, city, country, confidence }
+    }
+
+    use Confidence::*;
+
+    const CASES: &[Case] = &[
+        // Swiss German: number after street
+        case(&["Bahnhofstrasse 12", "8001 Zürich"], Some("Bahnhofstrasse"), Some("12"), "8001", "Zürich", Country::CH, High),
+        case(&["Mustergasse 12a", "3600 Thun"], Some("Mustergasse"), Some("12a"), "3600", "Thun", Country::CH, High),
+        case(&["Mustergasse 12 a", "3600 Thun"], Some("Mustergasse"), Some("12 a"), "3600", "Thun", Country::CH, High),
+        case(&["Am Wasser 1", "CH-3000 Bern"], Some("Am Wasser"), Some("1"), "3000", "Bern", Country::CH, High),
+        case(&["Industriestr. 14-16", "CH 6300 Zug"], Some("Industriestr."), Some("14-16"), "6300", "Zug", Country::CH, High),
+        case(&["Weg 3/1", "4051 Basel"], Some("Weg"), Some("3/1"), "4051", "Basel", Country::CH, High),
+        case(&["Hauptstrasse", "9000 St. Gallen"], Some("Hauptstrasse"), None, "9000", "St. Gallen", Country::CH, High),
+        // Swiss French: usually after the street, too
+        case(&["Rue du Lac 1268", "2501 Biel"], Some("Rue du Lac"), Some("1268"), "2501", "Biel", Country::CH, High),
+        case(&["Rue du Lac 1268", "2501 Biel/Bienne"], Some("Rue du Lac"), Some("1268"), "2501", "Biel/Bienne", Country::CH, High),
+        case(&["Avenue du 24-Septembre 3", "2800 Delémont"], Some("Avenue du 24-Septembre"), Some("3"), "2800", "Delémont", Country::CH, High),
+        // French: number before street, bis/ter
+        case(&["12 rue de la Paix", "F-75002 Paris"], Some("rue de la Paix"), Some("12"), "75002", "Paris", Country::FR, High),
+        case(&["12bis, avenue Foch", "FR-69006 Lyon"], Some("avenue Foch"), Some("12bis"), "69006", "Lyon", Country::FR, High),
+        case(&["7 ter rue Neuve", "F-68300 Saint-Louis"], Some("rue Neuve"), Some("7 ter"), "68300", "Saint-Louis", Country::FR, High),
+        // Italian
+        case(&["Via Nassa 5", "6900 Lugano"], Some("Via Nassa"), Some("5"), "6900", "Lugano", Country::CH, High),
+        case(&["Via Roma 10", "I-22100 Como"], Some("Via Roma"), Some("10"), "22100", "Como", Country::IT, High),
+        // Germany / Austria / Liechtenstein
+        case(&["Hauptstr. 1", "D-79539 Lörrach"], Some("Hauptstr."), Some("1"), "79539", "Lörrach", Country::DE, High),
+        case(&["Ringstrasse 5", "A-6800 Feldkirch"], Some("Ringstrasse"), Some("5"), "6800", "Feldkirch", Country::AT, High),
+        case(&["Städtle 17", "FL-9490 Vaduz"], Some("Städtle"), Some("17"), "9490", "Vaduz", Country::LI, High),
+        case(&["Städtle 17", "9490 Vaduz"], Some("Städtle"), Some("17"), "9490", "Vaduz", Country::LI, Medium),
+        // Country on its own line
+        case(&["Musterweg 2", "79539 Lörrach", "DE"], Some("Musterweg"), Some("2"), "79539", "Lörrach", Country::DE, High),
+        case(&["Musterweg 2", "79539 Lörrach", "Deutschland"], Some("Musterweg"), Some("2"), "79539", "Lörrach", Country::DE, High),
+        case(&["Via Nassa 5", "6900 Lugano", "Svizzera"], Some("Via Nassa"), Some("5"), "6900", "Lugano", Country::CH, High),
+        // Post boxes
+        case(&["Postfach", "3001 Bern"], Some("Postfach"), None, "3001", "Bern", Country::CH, High),
+        case(&["Postfach 123", "3001 Bern"], Some("Postfach"), Some("123"), "3001", "Bern", Country::CH, High),
+        case(&["Case postale 12", "1211 Genève 3"], Some("Case postale"), Some("12"), "1211", "Genève 3", Country::CH, High),
+        case(&["Bundesplatz 3", "Postfach", "3003 Bern"], Some("Bundesplatz"), Some("3"), "3003", "Bern", Country::CH, Medium),
+        // Extra lines and single-line input
+        case(&["c/o Muster AG", "Seestrasse 4", "8800 Thalwil"], Some("Seestrasse"), Some("4"), "8800", "Thalwil", Country::CH, Medium),
+        case(&["Rue du Lac 1268, 2501 Biel"], Some("Rue du Lac"), Some("1268"), "2501", "Biel", Country::CH, High),
+        case(&["2501 Biel"], None, None, "2501", "Biel", Country::CH, High),
+        // Doubtful
+        case(&["3 Route 66", "1000 Lausanne"], Some("3 Route"), Some("66"), "1000", "Lausanne", Country::CH, Low),
+        case(&["Hauptstrasse 1", "80331 München"], Some("Hauptstrasse"), Some("1"), "80331", "München", Country::CH, Low),
+    ];
+
+    #[test]
+    fn parses_test_table() {
+        for case in CASES {
+            let parsed = parse_address_lines("Muster AG", case.lines, Country::CH)
+                .unwrap_or_else(|e| panic!("{:?}: {e}", case.lines));
+            let a = &parsed.address;
+            assert_eq!(
+                (a.street.as_deref(), a.house_num.as_deref(), a.plz.as_str(), a.city.as_str(), a.country),
+                (case.street, case.house_num, case.plz, case.city, case.country),
+                "{:?}",
+                case.lines
+            );
+            assert_eq!(parsed.confidence, case.confidence, "{:?}: {:?}", case.lines, parsed.ambiguities);
+        }
+    }
+
+    #[test]
+    fn reports_discarded_lines() {
+        let parsed = parse_address_lines("Muster AG", &["c/o Muster AG", "Seestrasse 4", "8800 Thalwil"], Country::CH).unwrap();
+        assert!(parsed.ambiguities.contains(&Ambiguity::DiscardedLine("c/o Muster AG".to_string())));
+    }
+
+    #[test]
+    fn reports_competing_house_numbers() {
+        let parsed = parse_address_lines("Muster AG", &["3 Route 66", "1000 Lausanne"], Country::CH).unwrap();
+        assert!(parsed.ambiguities.contains(&Ambiguity::HouseNumberPosition {
+            chosen: "66".to_string(),
+            other: "3".to_string(),
+        }));
+    }
+
+    #[test]
+    fn foreign_default_country_is_used_without_prefix() {
+        let parsed = parse_address_lines("Muster GmbH", &["Hauptstr. 1", "79539 Lörrach"], Country::DE).unwrap();
+        assert_eq!(parsed.address.country, Country::DE);
+        assert_eq!(parsed.confidence, Confidence::High);
+    }
+
+    #[test]
+    fn missing_postal_line_is_an_error() {
+        let err = parse_address_lines("Muster AG", &["Bahnhofstrasse 12", "Zürich"], Country::CH).unwrap_err();
+        assert!(matches!(err, AddressParseError::NoPostalLine));
+    }
+
+    #[test]
+    fn invalid_fields_surface_address_errors() {
+        let long_city = format!("8001 {}", "Z".repeat(40));
+        let err = parse_address_lines("Muster AG", &["Bahnhofstrasse 12", &long_city], Country::CH).unwrap_err();
+        assert!(matches!(err, AddressParseError::Address(AddressError::AddressCityError)));
+    }
+}
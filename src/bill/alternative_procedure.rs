@@ -0,0 +1,374 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Alternative procedures (spec 4.2.2, AltPmt).
+//!
+//! The QR-bill spec treats the two alternative procedure lines as opaque
+//! text of up to 100 characters, prefixed by the procedure name. eBill is
+//! the one procedure with a published syntax, so it gets a typed variant
+//! that validates the recipient; everything else round-trips unchanged as
+//! [`AlternativeProcedure::Other`].
+//!
+//! eBill parameters follow `eBill/<type>/<recipient>[/<business case>]`:
+//!
+//! - type `B` for a bill, `R` for a reminder of an earlier bill,
+//! - recipient as e-mail address, 17-digit eBill participant id (PID) or
+//!   enterprise identification number (UID, `CHE-123.456.789`),
+//! - optionally the business case id of the bill, which a reminder must
+//!   carry so the recipient's bank can link it to the original.
+
+use std::fmt;
+use std::str::FromStr;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use thiserror::Error;
+
+use crate::support::validators::{is_valid_sps_charset, SPSCharsetError};
+use crate::ALTERNATIVE_PROCEDURE_MAX_LEN;
+
+/// Procedure name of eBill as it appears in the QR code.
+pub const EBILL_PROCEDURE_NAME: &str = "eBill";
+
+/// Length of an eBill participant id (PID).
+pub const EBILL_PARTICIPANT_ID_LEN: usize = 17;
+
+static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9.!#$%&'*+=?^_`{|}~-]+@[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]*[A-Za-z0-9])?)+$").unwrap()
+});
+
+#[derive(Debug, PartialEq, Error)]
+pub enum AlternativeProcedureError {
+    #[error("alternative procedure is empty")]
+    Empty,
+    #[error("alternative procedure must not exceed 100 characters")]
+    TooLong,
+    #[error(transparent)]
+    Charset(#[from] SPSCharsetError),
+    #[error("eBill parameters must look like eBill/<B|R>/<recipient>[/<business case>]")]
+    InvalidEBillSyntax,
+    #[error("unknown eBill type '{0}' (expected B or R)")]
+    InvalidEBillType(String),
+    #[error("'{0}' is not an e-mail address, eBill participant id or UID")]
+    InvalidEBillRecipient(String),
+    #[error("invalid business case id '{0}'")]
+    InvalidBusinessCase(String),
+    #[error("an eBill reminder needs the business case id of the original bill")]
+    MissingBusinessCase,
+}
+
+/// eBill document type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EBillType {
+    /// `B`
+    Bill,
+    /// `R`
+    Reminder,
+}
+
+impl EBillType {
+    pub fn code(&self) -> &'static str {
+        match self {
+            EBillType::Bill => "B",
+            EBillType::Reminder => "R",
+        }
+    }
+}
+
+/// Who receives the eBill.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EBillRecipient {
+    Email(String),
+    /// 17-digit eBill participant id, e.g. `41100000000872800`.
+    ParticipantId(String),
+    /// Enterprise identification number, stored as `CHE` plus 9 digits.
+    EnterpriseId(String),
+}
+
+impl EBillRecipient {
+    /// Recognizes the recipient kind. UIDs may be written with or without
+    /// separators (`CHE-116.281.710`, `CHE116281710`).
+    pub fn parse(input: &str) -> Result<Self, AlternativeProcedureError> {
+        let input = input.trim();
+        let invalid = || AlternativeProcedureError::InvalidEBillRecipient(input.to_string());
+
+        if input.contains('@') {
+            return EMAIL_REGEX
+                .is_match(input)
+                .then(|| EBillRecipient::Email(input.to_string()))
+                .ok_or_else(invalid);
+        }
+        if input.len() == EBILL_PARTICIPANT_ID_LEN && input.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(EBillRecipient::ParticipantId(input.to_string()));
+        }
+        if input.get(..3).is_some_and(|prefix| prefix.eq_ignore_ascii_case("CHE")) {
+            let digits: String = input[3..].chars().filter(|c| !matches!(c, '-' | '.')).collect();
+            return (digits.len() == 9 && digits.bytes().all(|b| b.is_ascii_digit()) && uid_checksum_ok(&digits))
+                .then(|| EBillRecipient::EnterpriseId(format!("CHE{digits}")))
+                .ok_or_else(invalid);
+        }
+        Err(invalid())
+    }
+}
+
+/// UID check digit (eCH-0097): mod 11 over the first eight digits with
+/// weights 5,4,3,2,7,6,5,4; a remainder leading to 10 never occurs in
+/// issued numbers.
+fn uid_checksum_ok(digits: &str) -> bool {
+    const WEIGHTS: [u32; 8] = [5, 4, 3, 2, 7, 6, 5, 4];
+    let values: Vec<u32> = digits.bytes().map(|b| u32::from(b - b'0')).collect();
+    let sum: u32 = values.iter().zip(WEIGHTS).map(|(v, w)| v * w).sum();
+    let check = match 11 - sum % 11 {
+        11 => 0,
+        10 => return false,
+        check => check,
+    };
+    values[8] == check
+}
+
+/// UIDs are written `CHE-123.456.789`, as on the commercial register.
+impl fmt::Display for EBillRecipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EBillRecipient::Email(email) => f.write_str(email),
+            EBillRecipient::ParticipantId(pid) => f.write_str(pid),
+            EBillRecipient::EnterpriseId(uid) => {
+                write!(f, "CHE-{}.{}.{}", &uid[3..6], &uid[6..9], &uid[9..12])
+            }
+        }
+    }
+}
+
+/// eBill parameters of an alternative procedure line.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EBill {
+    pub kind: EBillType,
+    pub recipient: EBillRecipient,
+    pub business_case: Option<String>,
+}
+
+impl EBill {
+    /// An eBill for a new bill.
+    pub fn bill(recipient: EBillRecipient) -> Self {
+        Self { kind: EBillType::Bill, recipient, business_case: None }
+    }
+
+    /// A reminder for the bill with business case id `business_case`.
+    pub fn reminder(recipient: EBillRecipient, business_case: impl Into<String>) -> Self {
+        Self { kind: EBillType::Reminder, recipient, business_case: Some(business_case.into()) }
+    }
+
+    /// Parses the parameters after `eBill/`.
+    fn parse_parameters(parameters: &str) -> Result<Self, AlternativeProcedureError> {
+        let mut parts = parameters.splitn(3, '/');
+        let (Some(kind), Some(recipient)) = (parts.next(), parts.next()) else {
+            return Err(AlternativeProcedureError::InvalidEBillSyntax);
+        };
+        let kind = match kind {
+            "B" => EBillType::Bill,
+            "R" => EBillType::Reminder,
+            other => return Err(AlternativeProcedureError::InvalidEBillType(other.to_string())),
+        };
+        let ebill = Self {
+            kind,
+            recipient: EBillRecipient::parse(recipient)?,
+            business_case: parts.next().map(str::to_string),
+        };
+        ebill.validate()?;
+        Ok(ebill)
+    }
+
+    fn validate(&self) -> Result<(), AlternativeProcedureError> {
+        match &self.business_case {
+            Some(case) if case.is_empty() || case.contains('/') || case.trim() != case => {
+                Err(AlternativeProcedureError::InvalidBusinessCase(case.clone()))
+            }
+            None if self.kind == EBillType::Reminder => Err(AlternativeProcedureError::MissingBusinessCase),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for EBill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{EBILL_PROCEDURE_NAME}/{}/{}", self.kind.code(), self.recipient)?;
+        if let Some(case) = &self.business_case {
+            write!(f, "/{case}")?;
+        }
+        Ok(())
+    }
+}
+
+/// One alternative procedure line.
+///
+/// ```
+/// use swiss_qrust::{AlternativeProcedure, EBillRecipient};
+///
+/// let procedure: AlternativeProcedure = "eBill/B/peter@sample.ch".parse()?;
+/// let AlternativeProcedure::EBill(ebill) = &procedure else { unreachable!() };
+/// assert_eq!(ebill.recipient, EBillRecipient::Email("peter@sample.ch".into()));
+/// assert_eq!(procedure.name(), "eBill");
+/// assert_eq!(procedure.to_string(), "eBill/B/peter@sample.ch");
+///
+/// let other: AlternativeProcedure = "UltraPay/005;12345".parse()?;
+/// assert_eq!(other.name(), "UltraPay");
+/// assert!(matches!(other, AlternativeProcedure::Other(_)));
+/// # Ok::<(), swiss_qrust::AlternativeProcedureError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlternativeProcedure {
+    EBill(EBill),
+    /// Any other procedure, kept verbatim.
+    Other(String),
+}
+
+impl AlternativeProcedure {
+    /// Parses an AltPmt line. Lines naming eBill must follow the eBill
+    /// syntax; anything else is accepted as [`AlternativeProcedure::Other`]
+    /// as long as it fits the spec's length and character set.
+    pub fn parse(input: &str) -> Result<Self, AlternativeProcedureError> {
+        if input.trim().is_empty() {
+            return Err(AlternativeProcedureError::Empty);
+        }
+        if input.chars().count() > ALTERNATIVE_PROCEDURE_MAX_LEN {
+            return Err(AlternativeProcedureError::TooLong);
+        }
+        is_valid_sps_charset(input)?;
+
+        let procedure = match input.split_once('/') {
+            Some((name, parameters)) if name.eq_ignore_ascii_case(EBILL_PROCEDURE_NAME) => {
+                AlternativeProcedure::EBill(EBill::parse_parameters(parameters)?)
+            }
+            None if input.eq_ignore_ascii_case(EBILL_PROCEDURE_NAME) => {
+                return Err(AlternativeProcedureError::InvalidEBillSyntax);
+            }
+            _ => AlternativeProcedure::Other(input.to_string()),
+        };
+
+        // Normalizing a UID can make the line longer than the input.
+        if procedure.to_string().chars().count() > ALTERNATIVE_PROCEDURE_MAX_LEN {
+            return Err(AlternativeProcedureError::TooLong);
+        }
+        Ok(procedure)
+    }
+
+    /// Procedure name: everything up to the first `/`, or the whole line.
+    /// Rendered in bold in the further information section.
+    pub fn name(&self) -> &str {
+        match self {
+            AlternativeProcedure::EBill(_) => EBILL_PROCEDURE_NAME,
+            AlternativeProcedure::Other(line) => line.split('/').next().unwrap_or(line),
+        }
+    }
+}
+
+/// Encodes the line as it goes into the QR code.
+impl fmt::Display for AlternativeProcedure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlternativeProcedure::EBill(ebill) => ebill.fmt(f),
+            AlternativeProcedure::Other(line) => f.write_str(line),
+        }
+    }
+}
+
+impl FromStr for AlternativeProcedure {
+    type Err = AlternativeProcedureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<EBill> for AlternativeProcedure {
+    fn from(ebill: EBill) -> Self {
+        AlternativeProcedure::EBill(ebill)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ebill(line: &str) -> EBill {
+        match AlternativeProcedure::parse(line).unwrap() {
+            AlternativeProcedure::EBill(ebill) => ebill,
+            other => panic!("expected eBill, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_ebill_recipients() {
+        assert_eq!(
+            ebill("eBill/B/sarah.beispiel@einfach-zahlen.ch").recipient,
+            EBillRecipient::Email("sarah.beispiel@einfach-zahlen.ch".into())
+        );
+        assert_eq!(
+            ebill("eBill/B/41100000000872800").recipient,
+            EBillRecipient::ParticipantId("41100000000872800".into())
+        );
+        assert_eq!(
+            ebill("eBill/B/CHE-116.281.710").recipient,
+            EBillRecipient::EnterpriseId("CHE116281710".into())
+        );
+    }
+
+    #[test]
+    fn parses_reminder_with_business_case() {
+        let reminder = ebill("eBill/R/peter@sample.ch/2026-INV-0042");
+        assert_eq!(reminder.kind, EBillType::Reminder);
+        assert_eq!(reminder.business_case.as_deref(), Some("2026-INV-0042"));
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        for line in [
+            "eBill/B/peter@sample.ch",
+            "eBill/B/41100000000872800/123",
+            "eBill/R/CHE-116.281.710/2026-INV-0042",
+            "Name AV1: UV;UltraPay005;12345",
+            "NoSlashHere",
+        ] {
+            assert_eq!(AlternativeProcedure::parse(line).unwrap().to_string(), line);
+        }
+        assert_eq!(
+            AlternativeProcedure::parse("ebill/B/CHE116281710").unwrap().to_string(),
+            "eBill/B/CHE-116.281.710"
+        );
+    }
+
+    #[test]
+    fn builds_ebill_lines() {
+        let recipient = EBillRecipient::parse("peter@sample.ch").unwrap();
+        assert_eq!(AlternativeProcedure::from(EBill::bill(recipient.clone())).to_string(), "eBill/B/peter@sample.ch");
+        assert_eq!(EBill::reminder(recipient, "42").to_string(), "eBill/R/peter@sample.ch/42");
+    }
+
+    #[test]
+    fn rejects_invalid_ebill_parameters() {
+        use AlternativeProcedureError::*;
+        let err = |line: &str| AlternativeProcedure::parse(line).unwrap_err();
+
+        assert_eq!(err("eBill"), InvalidEBillSyntax);
+        assert_eq!(err("eBill/B"), InvalidEBillSyntax);
+        assert_eq!(err("eBill/X/peter@sample.ch"), InvalidEBillType("X".into()));
+        assert_eq!(err("eBill/B/peter@"), InvalidEBillRecipient("peter@".into()));
+        assert_eq!(err("eBill/B/peter@sample"), InvalidEBillRecipient("peter@sample".into()));
+        assert_eq!(err("eBill/B/4110000000087280"), InvalidEBillRecipient("4110000000087280".into()));
+        assert_eq!(err("eBill/B/CHE-116.281.711"), InvalidEBillRecipient("CHE-116.281.711".into()));
+        assert_eq!(err("eBill/R/peter@sample.ch"), MissingBusinessCase);
+        assert_eq!(err("eBill/R/peter@sample.ch/"), InvalidBusinessCase(String::new()));
+        assert_eq!(err("eBill/R/peter@sample.ch/1/2"), InvalidBusinessCase("1/2".into()));
+    }
+
+    #[test]
+    fn other_procedures_only_get_spec_checks() {
+        assert_eq!(AlternativeProcedure::parse(""), Err(AlternativeProcedureError::Empty));
+        assert_eq!(AlternativeProcedure::parse(&"x".repeat(101)), Err(AlternativeProcedureError::TooLong));
+        assert!(matches!(AlternativeProcedure::parse("Pay/🤣"), Err(AlternativeProcedureError::Charset(_))));
+        assert_eq!(AlternativeProcedure::parse("UltraPay/abc").unwrap().name(), "UltraPay");
+    }
+}
@@ -10,14 +10,29 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use thiserror::Error;
 use crate::Address;
-use crate::address::AddressError;
+use crate::address::{AddressError, InputAddress};
+use crate::bill::alternative_procedure::{AlternativeProcedure, AlternativeProcedureError};
 use crate::bill::reference_type::ReferenceType;
-use crate::input::InputBill;
-use crate::support::utils::is_qr_iban;
+use crate::input::{InputBill, TemplateError};
+use crate::language::{label, LabelKey, Language, LanguageError};
+use crate::Iban;
 use crate::support::validators::*;
 
+/// Amount format per spec 4.2.2 (Amt): up to 9 integer digits, always two
+/// decimals.
+pub const AMOUNT_PATTERN: &str = r"^\d{1,9}\.\d{2}$";
+
+/// Spec 4.2.2 (AddInf): shared budget of Ustrd + StrdBkgInf.
+pub const ADDITIONAL_INFORMATION_MAX_LEN: usize = 140;
+
+/// Spec 3.5.5 / 4.2.2 (AltPmt): per occurrence.
+pub const ALTERNATIVE_PROCEDURE_MAX_LEN: usize = 100;
+
+/// Amount of a notification bill, see [`BillData::into_notification`].
+pub const NOTIFICATION_AMOUNT: &str = "0.00";
+
 pub static AMOUNT_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^\d{1,9}\.\d{2}$").unwrap());
+    Lazy::new(|| Regex::new(AMOUNT_PATTERN).unwrap());
 
 #[derive(Debug, Error)]
 pub enum BillError{
@@ -41,13 +56,56 @@ pub enum BillError{
     AdditionalInformationTooLong,
     #[error("Alternative procedure parameters must not exceed 100 characters")]
     AlternativeProcedureTooLong,
+    #[error(transparent)]
+    AlternativeProcedure(#[from] AlternativeProcedureError),
     #[error("Provide either bill_information or swico_bill_information, not both")]
     AmbiguousBillingInformation,
     #[error("Invalid date '{0}' in Swico billing information (expected YYYY-MM-DD)")]
     InvalidSwicoDate(String),
+    #[error("Invalid Swico billing information: {0}")]
+    Swico(#[from] crate::bill::swico::SwicoError),
+    #[error("A notification bill must not have an amount (it is always 0.00)")]
+    NotificationWithAmount,
+    #[error("A notification bill carries the notice as its unstructured message; remove the message")]
+    NotificationWithMessage,
+    #[error(transparent)]
+    Language(#[from] LanguageError),
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+    /// `error`, caused by the input field at `path`, a dotted path such as
+    /// `"debtor_address.city"` or `"alternative_schemes.1"`.
+    #[error("{error}")]
+    Field { path: String, error: Box<BillError> },
 }
 
-#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+impl BillError {
+    /// Dotted path of the input field that caused the error, if known.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            BillError::Field { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// The error itself, without the field it is attached to.
+    pub fn kind(&self) -> &BillError {
+        match self {
+            BillError::Field { error, .. } => error,
+            other => other,
+        }
+    }
+
+    /// Attaches the error to `field`, in front of a path that is already
+    /// attached: `city` at `debtor_address` becomes `debtor_address.city`.
+    pub(crate) fn at(self, field: &str) -> BillError {
+        match self {
+            BillError::Field { path, error } => BillError::Field { path: format!("{field}.{path}"), error },
+            error => BillError::Field { path: field.to_string(), error: Box::new(error) },
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, strum::EnumIter)]
 pub enum Currency {
     #[default]
     CHF,
@@ -82,11 +140,12 @@ impl Display for QRCountry {
 /// Build one via `BillData::try_from(input_bill)` or [`BillData::new`] -
 /// both enforce the spec's structural rules (IBAN checksum, QR-IBAN/QR-
 /// reference pairing, amount format, message length limits, ...), returning
-/// a [`BillError`] on the first violation. There is no way to construct an
+/// a [`BillError`] on the first violation, attached to the field that
+/// caused it (see [`BillError::field`]). There is no way to construct an
 /// invalid `BillData` outside this module.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct BillData {
-    pub iban: String,
+    pub iban: Iban,
     pub creditor_address: Address,
     pub debtor_address: Option<Address>,
     pub currency: Currency,
@@ -94,7 +153,10 @@ pub struct BillData {
     pub reference_type: ReferenceType,
     pub unstructured_message: Option<String>,
     pub bill_information: Option<String>,
-    pub alternative_schemes: [Option<String>; 2],
+    pub alternative_schemes: [Option<AlternativeProcedure>; 2],
+    /// Set for "do not use for payment" notifications, to the language of
+    /// the notice. See [`BillData::into_notification`].
+    pub notification: Option<Language>,
 } impl BillData {
     pub fn new (
         iban: String,
@@ -116,60 +178,63 @@ pub struct BillData {
 
         if let Some(ref amt) = amount {
             if !AMOUNT_REGEX.is_match(amt) {
-                return Err(BillError::InvalidAmount);
+                return Err(BillError::InvalidAmount.at("amount"));
             }
             // Spec: amount must be between 0.01 and 999999999.99 - an
             // all-zero value such as "0.00" is not a payable amount.
             if amt.chars().all(|c| c == '0' || c == '.') {
-                return Err(BillError::InvalidAmount);
+                return Err(BillError::InvalidAmount.at("amount"));
             }
         }
 
-        is_valid_iban(&iban)?;
-
-        // TODO: This should have happened before here.
-        let iban: String =
-            iban
-                .chars()
-                .filter(|s| !s.is_whitespace())
-                .collect();
+        let iban = Iban::parse(&iban).map_err(|e| BillError::from(e).at("iban"))?;
 
         // Spec 4.2.2 (RmtInf.Tp) / 4.3.2: a QR-IBAN must carry a QR
         // reference, and a QR reference must not be paired with a plain IBAN.
         let has_qr_reference = matches!(reference_type, ReferenceType::QrRef(_));
-        match (is_qr_iban(&iban), has_qr_reference) {
-            (true, false) => return Err(BillError::QrIbanRequiresQrReference),
-            (false, true) => return Err(BillError::QrReferenceRequiresQrIban),
+        match (iban.is_qr_iban(), has_qr_reference) {
+            // Without any reference there is no reference to point at.
+            (true, false) => {
+                let field = if reference_type == ReferenceType::NoRef { "iban" } else { "reference" };
+                return Err(BillError::QrIbanRequiresQrReference.at(field));
+            }
+            (false, true) => return Err(BillError::QrReferenceRequiresQrIban.at("iban")),
             _ => {}
         }
 
         // Spec 4.1.1: character set restriction applies to all free-text fields.
+        let charset = |field: &str, text: &str| is_valid_sps_charset(text).map_err(|e| BillError::from(e).at(field));
         if let Some(ref msg) = unstructured_message {
-            is_valid_sps_charset(msg)?;
+            charset("unstructured_message", msg)?;
         }
         if let Some(ref info) = bill_information {
-            is_valid_sps_charset(info)?;
+            charset("bill_information", info)?;
         }
-        for scheme in alternative_schemes.iter().flatten() {
-            is_valid_sps_charset(scheme)?;
+        for (i, scheme) in alternative_schemes.iter().enumerate() {
+            if let Some(scheme) = scheme {
+                charset(&format!("alternative_schemes.{i}"), scheme)?;
+            }
         }
 
         // Spec 4.2.2 (AddInf comment): Ustrd + StrdBkgInf share a common
         // 140-character budget.
         let combined_len = unstructured_message.as_deref().unwrap_or("").chars().count()
             + bill_information.as_deref().unwrap_or("").chars().count();
-        if combined_len > 140 {
-            return Err(BillError::AdditionalInformationTooLong);
+        if combined_len > ADDITIONAL_INFORMATION_MAX_LEN {
+            let field = if unstructured_message.is_some() { "unstructured_message" } else { "bill_information" };
+            return Err(BillError::AdditionalInformationTooLong.at(field));
         }
 
         // Spec 3.5.5 / 4.2.2 (AltPmt): max 100 characters per occurrence.
-        if alternative_schemes
-            .iter()
-            .flatten()
-            .any(|scheme| scheme.chars().count() > 100)
-        {
-            return Err(BillError::AlternativeProcedureTooLong);
-        }
+        let scheme = |i: usize, scheme: &Option<String>| {
+            let Some(scheme) = scheme.as_deref() else { return Ok(None) };
+            let field = format!("alternative_schemes.{i}");
+            if scheme.chars().count() > ALTERNATIVE_PROCEDURE_MAX_LEN {
+                return Err(BillError::AlternativeProcedureTooLong.at(&field));
+            }
+            AlternativeProcedure::parse(scheme).map(Some).map_err(|e| BillError::from(e).at(&field))
+        };
+        let alternative_schemes = [scheme(0, &alternative_schemes[0])?, scheme(1, &alternative_schemes[1])?];
 
         let bill =  BillData{
             iban,
@@ -181,51 +246,139 @@ pub struct BillData {
             unstructured_message,
             bill_information,
             alternative_schemes,
+            notification: None,
         };
         Ok(bill)
     }
+
+    /// Turns the bill into a "do not use for payment" notification (spec
+    /// 3.5.4 / IG Annex), e.g. for eBill notifications or invoices settled
+    /// by direct debit: the amount becomes 0.00 - otherwise never valid -
+    /// and the unstructured message the notice in `language`.
+    ///
+    /// The bill must not have an amount or unstructured message already.
+    ///
+    /// ```
+    /// # use swiss_qrust::*;
+    /// let bill = build_bill()?;
+    /// # let bill = BillData { amount: None, unstructured_message: None, ..bill };
+    /// let notification = bill.into_notification(Language::Fr)?;
+    /// assert_eq!(notification.amount.as_deref(), Some("0.00"));
+    /// assert_eq!(notification.unstructured_message.as_deref(), Some("NE PAS UTILISER POUR LE PAIEMENT"));
+    /// # Ok::<(), BillError>(())
+    /// ```
+    pub fn into_notification(self, language: Language) -> Result<Self, BillError> {
+        if self.amount.is_some() {
+            return Err(BillError::NotificationWithAmount.at("amount"));
+        }
+        if self.unstructured_message.is_some() {
+            return Err(BillError::NotificationWithMessage.at("unstructured_message"));
+        }
+
+        let notice = label(LabelKey::DoNotUseForPayment, language)?;
+        let combined_len = notice.chars().count()
+            + self.bill_information.as_deref().unwrap_or("").chars().count();
+        if combined_len > ADDITIONAL_INFORMATION_MAX_LEN {
+            return Err(BillError::AdditionalInformationTooLong.at("bill_information"));
+        }
+
+        Ok(Self {
+            amount: Some(NOTIFICATION_AMOUNT.to_string()),
+            unstructured_message: Some(notice.to_string()),
+            notification: Some(language),
+            ..self
+        })
+    }
+
+    /// Whether this is a "do not use for payment" notification.
+    pub fn is_notification(&self) -> bool {
+        self.notification.is_some()
+    }
 }
 
 impl TryFrom<InputBill> for BillData {
     type Error = BillError;
 
-    fn try_from(input: InputBill) -> Result<Self, Self::Error> {
-
-        let currency = input.currency.parse()?;
+    fn try_from(mut input: InputBill) -> Result<Self, Self::Error> {
+        input.prepare()?;
+        BillData::from_prepared(input)
+    }
+}
 
-        let creditor_address = Address::try_from(input.creditor_address)?;
+impl BillData {
+    /// Validates an [`InputBill`] that has been through
+    /// [`InputBill::prepare`]. Errors are attached to the input field that
+    /// caused them, see [`BillError::field`].
+    pub(crate) fn from_prepared(input: InputBill) -> Result<Self, BillError> {
+        let currency = input.currency.parse().map_err(|e: BillError| e.at("currency"))?;
+
+        let address = |address: InputAddress, name: &str| {
+            Address::try_from(address).map_err(|e| {
+                let field = e.field();
+                BillError::from(e).at(field).at(name)
+            })
+        };
+        let creditor_address = address(input.creditor_address, "creditor_address")?;
         let debtor_address =
             match input.debtor_address {
-                Some(addr) => Some(Address::try_from(addr)?),
+                Some(addr) => Some(address(addr, "debtor_address")?),
                 None => None,
             };
         let alternative_schemes = input
             .alternative_schemes
             .unwrap_or([None, None]);
 
-        let reference_type =
-            ReferenceType::infer(input.reference.unwrap_or("".to_string()).as_str())?;
+        let reference_type = ReferenceType::infer(input.reference.unwrap_or("".to_string()).as_str())
+            .map_err(|e| BillError::from(e).at("reference"))?;
 
-        let bill_information = match (input.bill_information, input.swico_bill_information) {
-            (Some(_), Some(_)) => return Err(BillError::AmbiguousBillingInformation),
-            (Some(raw), None) => Some(raw),
+        // Billing information checked by `new` was written as Swico fields
+        // if `info_field` says so.
+        let (bill_information, info_field) = match (input.bill_information, input.swico_bill_information) {
+            (Some(_), Some(_)) => return Err(BillError::AmbiguousBillingInformation.at("swico_bill_information")),
+            (Some(raw), None) => (Some(raw), "bill_information"),
             (None, Some(swico_input)) => {
-                crate::bill::swico::SwicoBillInformation::try_from(swico_input)?.encode_as_text()
+                let swico = crate::bill::swico::SwicoBillInformation::try_from(swico_input)
+                    .map_err(|e| e.at("swico_bill_information"))?;
+                (swico.encode_as_text(), "swico_bill_information")
+            }
+            (None, None) => (None, "bill_information"),
+        };
+        let as_written = |e: BillError| match e {
+            BillError::Field { path, error } if path == "bill_information" => {
+                BillError::Field { path: info_field.to_string(), error }
             }
-            (None, None) => None,
+            e => e,
         };
 
-        BillData::new(
+        let notification = input
+            .notification
+            .as_deref()
+            .map(str::parse::<Language>)
+            .transpose()
+            .map_err(|e| BillError::from(e).at("notification"))?;
+        // "0.00" is how a notification's amount is naturally written down;
+        // it only becomes valid through `into_notification`.
+        let amount = match (notification, input.amount) {
+            (Some(_), Some(amount)) if amount.trim() == NOTIFICATION_AMOUNT => None,
+            (_, amount) => amount,
+        };
+
+        let bill = BillData::new(
             input.iban,
             creditor_address,
             debtor_address,
             currency,
-            input.amount,
+            amount,
             reference_type,
             input.unstructured_message,
             bill_information,
             alternative_schemes,
         )
+        .map_err(as_written)?;
+        match notification {
+            Some(language) => bill.into_notification(language).map_err(as_written),
+            None => Ok(bill),
+        }
     }
 }
 
@@ -283,6 +436,8 @@ pub fn build_bill()  -> Result<BillData, BillError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
+    use crate::Country;
 
     const QR_IBAN: &str = "CH64 3196 1000 0044 2155 7";
     const PLAIN_IBAN: &str = "CH93 0076 2011 6238 5295 7";
@@ -307,7 +462,7 @@ mod tests {
         )
         .unwrap_err();
 
-        assert!(matches!(err, BillError::QrIbanRequiresQrReference));
+        assert!(matches!(err.kind(), BillError::QrIbanRequiresQrReference));
     }
 
     #[test]
@@ -325,7 +480,8 @@ mod tests {
         )
         .unwrap_err();
 
-        assert!(matches!(err, BillError::QrReferenceRequiresQrIban));
+        assert!(matches!(err.kind(), BillError::QrReferenceRequiresQrIban));
+        assert_eq!(err.field(), Some("iban"));
     }
 
     #[test]
@@ -359,7 +515,8 @@ mod tests {
         )
         .unwrap_err();
 
-        assert!(matches!(err, BillError::InvalidAmount));
+        assert!(matches!(err.kind(), BillError::InvalidAmount));
+        assert_eq!(err.field(), Some("amount"));
     }
 
     #[test]
@@ -393,7 +550,8 @@ mod tests {
         )
         .unwrap_err();
 
-        assert!(matches!(err, BillError::SPSCharsetError(_)));
+        assert!(matches!(err.kind(), BillError::SPSCharsetError(_)));
+        assert_eq!(err.field(), Some("unstructured_message"));
     }
 
     #[test]
@@ -411,7 +569,7 @@ mod tests {
         )
         .unwrap_err();
 
-        assert!(matches!(err, BillError::AdditionalInformationTooLong));
+        assert!(matches!(err.kind(), BillError::AdditionalInformationTooLong));
     }
 
     #[test]
@@ -430,6 +588,91 @@ mod tests {
         .is_ok());
     }
 
+    #[test]
+    fn sanitize_option_cleans_text_fields_before_validation() {
+        let toml = r#"
+iban = "CH93 0076 2011 6238 5295 7"
+currency = "CHF"
+unstructured_message = "Rechnung “März” – 🤣"
+sanitize = true
+
+[creditor_address]
+name = "Иван Петров"
+plz = "2501"
+city = "Biel"
+country = "CH"
+"#;
+        let input: InputBill = toml::from_str(toml).unwrap();
+        let bill = BillData::try_from(input.clone()).unwrap();
+
+        assert_eq!(bill.creditor_address.name, "Ivan Petrov");
+        assert_eq!(bill.unstructured_message.as_deref(), Some("Rechnung \"März\" - "));
+
+        let strict = InputBill { sanitize: None, ..input };
+        let err = BillData::try_from(strict).unwrap_err();
+        assert!(matches!(err.kind(), BillError::AddressError(_)));
+        assert_eq!(err.field(), Some("creditor_address.name"));
+    }
+
+    #[test]
+    fn resolve_countries_option_accepts_country_names() {
+        let toml = r#"
+iban = "CH93 0076 2011 6238 5295 7"
+currency = "CHF"
+resolve_countries = true
+
+[creditor_address]
+name = "Robert Schneider AG"
+plz = "2501"
+city = "Biel"
+country = "Schweiz"
+
+[debtor_address]
+name = "Pia-Maria Rutschmann-Schnyder"
+plz = "79539"
+city = "Lörrach"
+country = "DEU"
+"#;
+        let input: InputBill = toml::from_str(toml).unwrap();
+        let bill = BillData::try_from(input.clone()).unwrap();
+
+        assert_eq!(bill.creditor_address.country, Country::CH);
+        assert_eq!(bill.debtor_address.unwrap().country, Country::DE);
+
+        let strict = InputBill { resolve_countries: None, ..input };
+        let err = BillData::try_from(strict).unwrap_err();
+        assert!(matches!(err.kind(), BillError::AddressError(_)));
+        assert_eq!(err.field(), Some("creditor_address.country"));
+    }
+
+    #[test]
+    fn braces_are_text_unless_the_bill_has_variables() {
+        let toml = r#"
+iban = "CH93 0076 2011 6238 5295 7"
+currency = "CHF"
+unstructured_message = "Order {17}"
+bill_information = "a {{b}} c"
+
+[creditor_address]
+name = "Robert Schneider AG"
+plz = "2501"
+city = "Biel"
+country = "CH"
+"#;
+        let input: InputBill = toml::from_str(toml).unwrap();
+        let bill = BillData::try_from(input.clone()).unwrap();
+        assert_eq!(bill.unstructured_message.as_deref(), Some("Order {17}"));
+        assert_eq!(bill.bill_information.as_deref(), Some("a {{b}} c"));
+
+        let templated = InputBill { variables: Some(BTreeMap::new()), ..input };
+        let err = BillData::try_from(templated).unwrap_err();
+        assert_eq!(err.field(), Some("unstructured_message"));
+        assert!(matches!(
+            err.kind(),
+            BillError::Template(TemplateError::UnknownVariable(name)) if name == "17"
+        ));
+    }
+
     #[test]
     fn alternative_procedure_over_100_chars_is_rejected() {
         let err = BillData::new(
@@ -445,7 +688,48 @@ mod tests {
         )
         .unwrap_err();
 
-        assert!(matches!(err, BillError::AlternativeProcedureTooLong));
+        assert!(matches!(err.kind(), BillError::AlternativeProcedureTooLong));
+    }
+
+    #[test]
+    fn alternative_procedures_are_typed() {
+        let bill = BillData::new(
+            QR_IBAN.to_string(),
+            address(),
+            None,
+            Currency::CHF,
+            None,
+            ReferenceType::QrRef(QRR.to_string()),
+            None,
+            None,
+            [Some("eBill/B/peter@sample.ch".to_string()), Some("UltraPay/005".to_string())],
+        )
+        .unwrap();
+
+        assert!(matches!(bill.alternative_schemes[0], Some(AlternativeProcedure::EBill(_))));
+        assert_eq!(bill.alternative_schemes[1], Some(AlternativeProcedure::Other("UltraPay/005".into())));
+    }
+
+    #[test]
+    fn invalid_ebill_recipient_is_rejected() {
+        let err = BillData::new(
+            QR_IBAN.to_string(),
+            address(),
+            None,
+            Currency::CHF,
+            None,
+            ReferenceType::QrRef(QRR.to_string()),
+            None,
+            None,
+            [None, Some("eBill/B/not-an-address".to_string())],
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err.kind(),
+            BillError::AlternativeProcedure(AlternativeProcedureError::InvalidEBillRecipient(_))
+        ));
+        assert_eq!(err.field(), Some("alternative_schemes.1"));
     }
 
     #[test]
@@ -0,0 +1,202 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::support::traits::SwissQRFormatter;
+use crate::support::validators::IbanError;
+use crate::{is_qr_iban, Country};
+
+/// Length of every Swiss and Liechtenstein IBAN.
+pub const IBAN_LEN: usize = 21;
+
+/// A validated Swiss or Liechtenstein IBAN, stored in electronic format
+/// (upper case, no spaces).
+///
+/// CH/LI IBANs are 21 characters: country code, 2 check digits, 5-digit
+/// institution id (IID) and a 12-character account number.
+///
+/// ```
+/// use swiss_qrust::Iban;
+///
+/// let iban: Iban = "IBAN CH93 0076 2011 6238 5295 7".parse()?;
+/// assert_eq!(iban.as_str(), "CH9300762011623852957");
+/// assert_eq!(iban.iid(), "00762");
+/// assert_eq!(iban.account(), "011623852957");
+/// assert_eq!(iban.to_string(), "CH93 0076 2011 6238 5295 7");
+/// # Ok::<(), swiss_qrust::IbanError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Iban(String);
+
+impl Iban {
+    /// Parses an IBAN in electronic ("CH9300762011623852957") or paper
+    /// format ("IBAN CH93 0076 2011 6238 5295 7"). Letters may be lower
+    /// case.
+    pub fn parse(input: &str) -> Result<Self, IbanError> {
+        let trimmed = input.trim_start();
+        let trimmed = match trimmed.get(..4) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("IBAN") => &trimmed[4..],
+            _ => trimmed,
+        };
+        let iban: String = trimmed
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        if !(iban.starts_with("CH") || iban.starts_with("LI")) {
+            return Err(IbanError::IncorrectCountryCode);
+        }
+
+        let len = iban.chars().count();
+        if len != IBAN_LEN {
+            return Err(IbanError::IncorrectLength { expected: IBAN_LEN, actual: len });
+        }
+
+        // From here on the string is 21 characters; all byte slicing below
+        // is safe once they are known to be ASCII.
+        if !iban.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(IbanError::InvalidCharacter);
+        }
+        if !iban[2..4].bytes().all(|b| b.is_ascii_digit()) {
+            return Err(IbanError::InvalidCharacter);
+        }
+        if !iban[4..9].bytes().all(|b| b.is_ascii_digit()) {
+            return Err(IbanError::InvalidIid);
+        }
+
+        if mod97(&iban) != 1 {
+            return Err(IbanError::InvalidIban);
+        }
+
+        Ok(Self(iban))
+    }
+
+    /// Electronic format, as encoded in the QR code.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn country(&self) -> Country {
+        if self.0.starts_with("LI") { Country::LI } else { Country::CH }
+    }
+
+    pub fn check_digits(&self) -> &str {
+        &self.0[2..4]
+    }
+
+    /// The 5-digit institution id (bank clearing number).
+    pub fn iid(&self) -> &str {
+        &self.0[4..9]
+    }
+
+    /// The 12-character account number.
+    pub fn account(&self) -> &str {
+        &self.0[9..]
+    }
+
+    /// Whether the IID is a QR-IID (30000-31999), see [`is_qr_iban`].
+    pub fn is_qr_iban(&self) -> bool {
+        is_qr_iban(&self.0)
+    }
+}
+
+/// ISO 7064 mod 97-10 over the rearranged IBAN (country code and check
+/// digits moved to the end, letters as 10-35). Expects upper-case ASCII
+/// alphanumerics.
+fn mod97(iban: &str) -> u32 {
+    iban[4..].bytes().chain(iban[..4].bytes()).fold(0, |remainder, b| match b {
+        b'0'..=b'9' => (remainder * 10 + u32::from(b - b'0')) % 97,
+        _ => (remainder * 100 + u32::from(b - b'A') + 10) % 97,
+    })
+}
+
+/// Paper format in groups of four, per spec via [`SwissQRFormatter::format_iban`].
+impl fmt::Display for Iban {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.format_iban())
+    }
+}
+
+impl FromStr for Iban {
+    type Err = IbanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for Iban {
+    type Error = IbanError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl AsRef<str> for Iban {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_electronic_and_paper_format() {
+        let electronic = Iban::parse("CH4431999123000889012").unwrap();
+        let paper = Iban::parse("CH44 3199 9123 0008 8901 2").unwrap();
+        let prefixed = Iban::parse("iban ch44 3199 9123 0008 8901 2").unwrap();
+
+        assert_eq!(electronic, paper);
+        assert_eq!(paper, prefixed);
+        assert!(paper.is_qr_iban());
+        assert_eq!(paper.iid(), "31999");
+    }
+
+    #[test]
+    fn parses_liechtenstein_iban() {
+        let iban = Iban::parse("LI21 0881 0000 2324 013A A").unwrap();
+        assert_eq!(iban.country(), Country::LI);
+        assert_eq!(iban.account(), "0002324013AA");
+    }
+
+    #[test]
+    fn rejects_wrong_length_with_actual_length() {
+        assert_eq!(
+            Iban::parse("CH44 0871 0000 0033 1272 0007"),
+            Err(IbanError::IncorrectLength { expected: 21, actual: 24 })
+        );
+        assert_eq!(
+            Iban::parse("CH93 0076 2011 6238 5295"),
+            Err(IbanError::IncorrectLength { expected: 21, actual: 20 })
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_iban() {
+        assert_eq!(Iban::parse("DE89 3704 0044 0532 0130 00"), Err(IbanError::IncorrectCountryCode));
+    }
+
+    #[test]
+    fn rejects_malformed_structure() {
+        assert_eq!(Iban::parse("CH93 0076 2011 6238 5295 Ä"), Err(IbanError::InvalidCharacter));
+        assert_eq!(Iban::parse("CHX3 0076 2011 6238 5295 7"), Err(IbanError::InvalidCharacter));
+        assert_eq!(Iban::parse("CH93 A076 2011 6238 5295 7"), Err(IbanError::InvalidIid));
+        assert_eq!(Iban::parse("CH94 0076 2011 6238 5295 7"), Err(IbanError::InvalidIban));
+    }
+
+    #[test]
+    fn displays_in_paper_format() {
+        let iban: Iban = "CH9300762011623852957".parse().unwrap();
+        assert_eq!(iban.to_string(), "CH93 0076 2011 6238 5295 7");
+        assert_eq!(iban.as_str(), "CH9300762011623852957");
+    }
+}
@@ -0,0 +1,395 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Invoice line items as the source of Swico billing information.
+//!
+//! An [`Invoice`] holds the lines of a bill and derives what goes on the
+//! QR-bill from them: the total for [`BillData::amount`](crate::BillData)
+//! and [`SwicoBillInformation`] with the net amount per VAT rate (tag 32)
+//! and the payment conditions (tag 40). Amounts are summed in cents, so
+//! the figures match what an accounting system prints on the invoice.
+//!
+//! [`Invoice::fit_bill_information`] additionally keeps the S1 text within
+//! the 140 characters it shares with the unstructured message, dropping
+//! optional tags if needed.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::{
+    BillError, PaymentCondition, RateDetail, SwicoBillInformation, ADDITIONAL_INFORMATION_MAX_LEN,
+};
+
+/// One invoice position. Prices are net, i.e. without VAT.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvoiceLine {
+    pub description: String,
+    pub quantity: f64,
+    pub unit_price: f64,
+    /// VAT rate in percent, e.g. `8.1`.
+    pub vat_rate: f64,
+}
+
+impl InvoiceLine {
+    pub fn new(description: impl Into<String>, quantity: f64, unit_price: f64, vat_rate: f64) -> Self {
+        Self { description: description.into(), quantity, unit_price, vat_rate }
+    }
+
+    /// Net amount in cents, rounded half away from zero.
+    fn net_cents(&self) -> i64 {
+        (self.quantity * self.unit_price * 100.0).round() as i64
+    }
+
+    /// Net amount of the line, rounded to cents.
+    pub fn net_amount(&self) -> f64 {
+        cents_to_amount(self.net_cents())
+    }
+}
+
+/// Payment terms: early payment discounts and the net payment period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentTerms {
+    /// Discounts for early payment, e.g. 2% within 10 days.
+    pub discounts: Vec<PaymentCondition>,
+    /// Days until the full amount is due.
+    pub net_days: u32,
+}
+
+impl PaymentTerms {
+    /// Payable within `net_days`, no discount.
+    pub fn net(net_days: u32) -> Self {
+        Self { discounts: Vec::new(), net_days }
+    }
+
+    /// Swico tag 40: the discounts by deadline, then `0:<net_days>`.
+    pub fn payment_conditions(&self) -> Vec<PaymentCondition> {
+        let mut conditions = self.discounts.clone();
+        conditions.sort_by_key(|c| c.days);
+        conditions.push(PaymentCondition { discount: 0.0, days: self.net_days });
+        conditions
+    }
+}
+
+/// An invoice: header data plus line items.
+///
+/// ```
+/// use swiss_qrust::{Invoice, InvoiceLine, PaymentTerms, RateDetail};
+///
+/// let invoice = Invoice {
+///     invoice_number: Some("2026-0042".into()),
+///     lines: vec![
+///         InvoiceLine::new("Consulting", 8.0, 150.0, 8.1),
+///         InvoiceLine::new("Book", 2.0, 24.5, 2.6),
+///     ],
+///     terms: Some(PaymentTerms::net(30)),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(invoice.amount()?, "1347.47");
+/// let info = invoice.swico_bill_information();
+/// assert_eq!(info.vat_rate_details, vec![
+///     RateDetail { rate: 2.6, amount: 49.0 },
+///     RateDetail { rate: 8.1, amount: 1200.0 },
+/// ]);
+/// assert_eq!(info.encode_as_text().unwrap(), "//S1/10/2026-0042/32/2.6:49;8.1:1200/40/0:30");
+/// # Ok::<(), swiss_qrust::BillError>(())
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Invoice {
+    pub invoice_number: Option<String>,
+    pub invoice_date: Option<NaiveDate>,
+    pub customer_reference: Option<String>,
+    pub vat_number: Option<String>,
+    pub vat_date: Option<NaiveDate>,
+    pub vat_start_date: Option<NaiveDate>,
+    pub vat_end_date: Option<NaiveDate>,
+    pub lines: Vec<InvoiceLine>,
+    pub terms: Option<PaymentTerms>,
+}
+
+/// Swico billing information trimmed to the shared 140-character budget,
+/// see [`Invoice::fit_bill_information`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FittedBillInformation {
+    pub bill_information: SwicoBillInformation,
+    /// The S1 text, `None` if nothing is left to encode.
+    pub text: Option<String>,
+    /// Tags dropped to make it fit, in the order they were dropped.
+    pub omitted_tags: Vec<u32>,
+}
+
+impl Invoice {
+    /// Net amount in cents per VAT rate (keyed in thousandths of a percent,
+    /// the precision of S1 numbers), ascending by rate.
+    fn net_cents_by_rate(&self) -> BTreeMap<i64, i64> {
+        let mut by_rate = BTreeMap::new();
+        for line in &self.lines {
+            *by_rate.entry((line.vat_rate * 1000.0).round() as i64).or_insert(0) += line.net_cents();
+        }
+        by_rate
+    }
+
+    /// Net amount per VAT rate, ascending by rate (Swico tag 32).
+    pub fn net_amounts(&self) -> Vec<RateDetail> {
+        self.net_cents_by_rate()
+            .into_iter()
+            .map(|(rate, cents)| RateDetail { rate: rate as f64 / 1000.0, amount: cents_to_amount(cents) })
+            .collect()
+    }
+
+    /// VAT per rate, computed on the rate's net total and rounded to cents.
+    pub fn vat_amounts(&self) -> Vec<RateDetail> {
+        self.net_cents_by_rate()
+            .into_iter()
+            .map(|(rate, cents)| RateDetail { rate: rate as f64 / 1000.0, amount: cents_to_amount(vat_cents(rate, cents)) })
+            .collect()
+    }
+
+    fn total_cents(&self) -> i64 {
+        self.net_cents_by_rate()
+            .into_iter()
+            .map(|(rate, cents)| cents + vat_cents(rate, cents))
+            .sum()
+    }
+
+    /// Total including VAT.
+    pub fn total(&self) -> f64 {
+        cents_to_amount(self.total_cents())
+    }
+
+    /// The total formatted for [`BillData::amount`](crate::BillData), or
+    /// [`BillError::InvalidAmount`] if it is not payable (zero, negative
+    /// or above 999'999'999.99).
+    pub fn amount(&self) -> Result<String, BillError> {
+        let cents = self.total_cents();
+        if !(1..=99_999_999_999).contains(&cents) {
+            return Err(BillError::InvalidAmount);
+        }
+        Ok(format!("{}.{:02}", cents / 100, cents % 100))
+    }
+
+    /// All Swico billing information the invoice provides.
+    pub fn swico_bill_information(&self) -> SwicoBillInformation {
+        SwicoBillInformation {
+            invoice_number: self.invoice_number.clone(),
+            invoice_date: self.invoice_date,
+            customer_reference: self.customer_reference.clone(),
+            vat_number: self.vat_number.clone(),
+            vat_date: self.vat_date,
+            vat_start_date: self.vat_start_date,
+            vat_end_date: self.vat_end_date,
+            vat_rate: None,
+            vat_rate_details: self.net_amounts(),
+            vat_import_taxes: Vec::new(),
+            payment_conditions: self.terms.as_ref().map(PaymentTerms::payment_conditions).unwrap_or_default(),
+        }
+    }
+
+    /// [`Self::swico_bill_information`], validated and shortened until its
+    /// S1 text and `unstructured_message` fit the 140 characters they
+    /// share (spec 4.2.2, AddInf).
+    ///
+    /// Shortening goes from least to most important: a single VAT rate is
+    /// written without its net amount, then the customer reference (20),
+    /// the VAT dates (31), the payment conditions (40) and the VAT rates
+    /// (32) are dropped. Invoice number, date and VAT number are kept; if
+    /// they alone don't fit, the result is
+    /// [`BillError::AdditionalInformationTooLong`].
+    pub fn fit_bill_information(&self, unstructured_message: Option<&str>) -> Result<FittedBillInformation, BillError> {
+        let mut info = self.swico_bill_information();
+        info.validate()?;
+
+        let budget = ADDITIONAL_INFORMATION_MAX_LEN
+            .checked_sub(unstructured_message.map_or(0, |m| m.chars().count()))
+            .ok_or(BillError::AdditionalInformationTooLong)?;
+        let fits = |info: &SwicoBillInformation| {
+            info.encode_as_text().is_none_or(|text| text.chars().count() <= budget)
+        };
+
+        let mut omitted_tags = Vec::new();
+        let steps: [(Option<u32>, Shorten); 5] = [
+            (None, |info| {
+                if let [single] = info.vat_rate_details[..] {
+                    info.vat_rate = Some(single.rate);
+                    info.vat_rate_details.clear();
+                }
+            }),
+            (Some(20), |info| info.customer_reference = None),
+            (Some(31), |info| {
+                info.vat_date = None;
+                info.vat_start_date = None;
+                info.vat_end_date = None;
+            }),
+            (Some(40), |info| info.payment_conditions.clear()),
+            (Some(32), |info| {
+                info.vat_rate = None;
+                info.vat_rate_details.clear();
+            }),
+        ];
+        for (tag, shorten) in steps {
+            if fits(&info) {
+                break;
+            }
+            let before = info.clone();
+            shorten(&mut info);
+            if let Some(tag) = tag
+                && info != before
+            {
+                omitted_tags.push(tag);
+            }
+        }
+
+        if !fits(&info) {
+            return Err(BillError::AdditionalInformationTooLong);
+        }
+        Ok(FittedBillInformation { text: info.encode_as_text(), bill_information: info, omitted_tags })
+    }
+}
+
+/// One shortening step of [`Invoice::fit_bill_information`].
+type Shorten = fn(&mut SwicoBillInformation);
+
+/// VAT in cents on `net_cents` at `rate` thousandths of a percent.
+fn vat_cents(rate: i64, net_cents: i64) -> i64 {
+    (net_cents as f64 * rate as f64 / 100_000.0).round() as i64
+}
+
+fn cents_to_amount(cents: i64) -> f64 {
+    cents as f64 / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SwicoError;
+
+    fn invoice() -> Invoice {
+        Invoice {
+            invoice_number: Some("10201409".into()),
+            invoice_date: NaiveDate::from_ymd_opt(2026, 5, 12),
+            customer_reference: Some("1400.000-53".into()),
+            vat_number: Some("106017086".into()),
+            vat_date: NaiveDate::from_ymd_opt(2026, 5, 8),
+            lines: vec![
+                InvoiceLine::new("Hosting", 12.0, 49.9, 8.1),
+                InvoiceLine::new("Domain", 1.0, 18.0, 8.1),
+                InvoiceLine::new("Manual", 3.0, 12.35, 2.6),
+                InvoiceLine::new("Postage", 1.0, 7.0, 0.0),
+            ],
+            terms: Some(PaymentTerms { discounts: vec![PaymentCondition { discount: 2.0, days: 10 }], net_days: 30 }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn aggregates_net_amounts_per_rate() {
+        assert_eq!(
+            invoice().net_amounts(),
+            vec![
+                RateDetail { rate: 0.0, amount: 7.0 },
+                RateDetail { rate: 2.6, amount: 37.05 },
+                RateDetail { rate: 8.1, amount: 616.8 },
+            ]
+        );
+        assert_eq!(
+            invoice().vat_amounts(),
+            vec![
+                RateDetail { rate: 0.0, amount: 0.0 },
+                RateDetail { rate: 2.6, amount: 0.96 },
+                RateDetail { rate: 8.1, amount: 49.96 },
+            ]
+        );
+    }
+
+    #[test]
+    fn total_includes_vat() {
+        // 660.85 net + 0.96 + 49.96 VAT
+        assert_eq!(invoice().amount().unwrap(), "711.77");
+        assert_eq!(invoice().total(), 711.77);
+    }
+
+    #[test]
+    fn non_payable_totals_are_rejected() {
+        let empty = Invoice::default();
+        assert!(matches!(empty.amount(), Err(BillError::InvalidAmount)));
+
+        let credit = Invoice { lines: vec![InvoiceLine::new("Refund", 1.0, -10.0, 8.1)], ..Default::default() };
+        assert!(matches!(credit.amount(), Err(BillError::InvalidAmount)));
+    }
+
+    #[test]
+    fn payment_conditions_end_with_the_net_period() {
+        let terms = PaymentTerms {
+            discounts: vec![
+                PaymentCondition { discount: 1.0, days: 20 },
+                PaymentCondition { discount: 3.0, days: 5 },
+            ],
+            net_days: 60,
+        };
+        assert_eq!(
+            terms.payment_conditions(),
+            vec![
+                PaymentCondition { discount: 3.0, days: 5 },
+                PaymentCondition { discount: 1.0, days: 20 },
+                PaymentCondition { discount: 0.0, days: 60 },
+            ]
+        );
+    }
+
+    #[test]
+    fn fits_without_trimming_when_there_is_room() {
+        let fitted = invoice().fit_bill_information(None).unwrap();
+        assert_eq!(
+            fitted.text.as_deref(),
+            Some("//S1/10/10201409/11/260512/20/1400.000-53/30/106017086/31/260508/32/0:7;2.6:37.05;8.1:616.8/40/2:10;0:30")
+        );
+        assert!(fitted.omitted_tags.is_empty());
+    }
+
+    #[test]
+    fn trims_optional_tags_to_share_the_budget_with_the_message() {
+        let message = "Thank you for your order. Please pay within 30 days.";
+        let fitted = invoice().fit_bill_information(Some(message)).unwrap();
+
+        assert_eq!(fitted.omitted_tags, vec![20, 31]);
+        let text = fitted.text.unwrap();
+        assert_eq!(text, "//S1/10/10201409/11/260512/30/106017086/32/0:7;2.6:37.05;8.1:616.8/40/2:10;0:30");
+        assert!(text.chars().count() + message.chars().count() <= ADDITIONAL_INFORMATION_MAX_LEN);
+    }
+
+    #[test]
+    fn single_rate_is_shortened_before_anything_is_dropped() {
+        let invoice = Invoice {
+            invoice_number: Some("1".into()),
+            lines: vec![InvoiceLine::new("Hosting", 12.0, 49.9, 8.1)],
+            ..Default::default()
+        };
+        let message = "x".repeat(120);
+        let fitted = invoice.fit_bill_information(Some(&message)).unwrap();
+
+        assert_eq!(fitted.text.as_deref(), Some("//S1/10/1/32/8.1"));
+        assert!(fitted.omitted_tags.is_empty());
+    }
+
+    #[test]
+    fn reports_when_even_the_mandatory_part_does_not_fit() {
+        let message = "x".repeat(130);
+        assert!(matches!(
+            invoice().fit_bill_information(Some(&message)),
+            Err(BillError::AdditionalInformationTooLong)
+        ));
+    }
+
+    #[test]
+    fn invalid_vat_rates_are_reported() {
+        let invoice = Invoice { lines: vec![InvoiceLine::new("Typo", 1.0, 10.0, 810.0)], ..Default::default() };
+        assert!(matches!(
+            invoice.fit_bill_information(None),
+            Err(BillError::Swico(SwicoError::PercentageOutOfRange { tag: 32, .. }))
+        ));
+    }
+}
@@ -1,10 +1,25 @@
 pub mod bill_data;
 pub mod address;
+pub mod address_parser;
+pub mod alternative_procedure;
+pub mod iban;
+pub mod invoice;
 pub mod reference_type;
+pub mod reference_template;
+pub mod reference_allocator;
 pub mod qr_bill;
+pub mod qr_decode;
 pub mod swico;
+pub mod swico_format;
 
 pub use bill_data::*;
 pub use address::*;
+pub use address_parser::*;
+pub use alternative_procedure::*;
+pub use iban::*;
+pub use invoice::*;
 pub use reference_type::ReferenceType;
+pub use reference_template::*;
+pub use reference_allocator::*;
+pub use qr_decode::*;
 pub use swico::*;
\ No newline at end of file
@@ -158,10 +158,8 @@ impl<'a> QrBill
         // Alternative Schemes - a repeating group; skipping absent
         // occurrences is fine since order between occurrences carries no
         // positional meaning.
-        for scheme in &self.bill_data.alternative_schemes {
-            if let Some(scheme) = scheme {
-                qr_text.append_data_field(Some(scheme));
-            }
+        for scheme in self.bill_data.alternative_schemes.iter().flatten() {
+            qr_text.append_data_field(Some(&scheme.to_string()));
         }
 
         Ok(qr_text.build())
@@ -251,7 +249,7 @@ mod tests {
     #[test]
     fn strd_bkg_inf_placeholder_preserves_alt_pmt_position_when_billing_info_absent() {
         let mut bill = qr_bill_bill_data();
-        bill.alternative_schemes = [Some("eBill/B/sarah.beispiel@einfach-zahlen.ch".to_string()), None];
+        bill.alternative_schemes = [Some("eBill/B/sarah.beispiel@einfach-zahlen.ch".parse().unwrap()), None];
 
         let text = QrBill::new(&bill).unwrap().create_qr_text().unwrap();
         let fields: Vec<&str> = text.split("\r\n").collect();
@@ -0,0 +1,219 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Reading QR-bill payloads: the reverse of
+//! [`QrBill::create_qr_text`](crate::qr_bill::QrBill::create_qr_text).
+//!
+//! [`decode_qr_text`] checks the payload's structure (spec 4.2.2: header,
+//! field count, trailer) and maps it onto an [`InputBill`], the same shape
+//! as the TOML/JSON input files, so a scanned bill can be saved, edited and
+//! rendered again. The content itself is validated by the usual
+//! `BillData::try_from`, or in one go by [`BillData::from_qr_text`].
+
+use thiserror::Error;
+
+use crate::address::InputAddress;
+use crate::language::{label, LabelKey, Language};
+use crate::{BillData, BillError, InputBill, NOTIFICATION_AMOUNT};
+
+/// Fields up to and including the trailer (`EPD`).
+const MANDATORY_FIELDS: usize = 31;
+/// Trailer, StrdBkgInf and up to two AltPmt.
+const MAX_FIELDS: usize = MANDATORY_FIELDS + 3;
+
+#[derive(Debug, Error)]
+pub enum QrDecodeError {
+    #[error("not a Swiss QR-bill payload (expected header SPC)")]
+    NotQrBill,
+    #[error("unsupported QR-bill version '{0}' (expected 02xx)")]
+    UnsupportedVersion(String),
+    #[error("unsupported coding type '{0}' (expected 1)")]
+    UnsupportedCoding(String),
+    #[error("payload has {0} fields, expected 31 to 34")]
+    FieldCount(usize),
+    #[error("missing trailer EPD")]
+    MissingTrailer,
+    #[error("unsupported address type '{0}' in field {1} (expected S)")]
+    UnsupportedAddressType(String, usize),
+    #[error("unknown reference type '{0}'")]
+    UnknownReferenceType(String),
+    #[error(transparent)]
+    Bill(#[from] BillError),
+}
+
+/// Decodes a QR-bill payload into the input file format.
+///
+/// Lines may be separated by CRLF or LF (spec 4.1.4). A "do not use for
+/// payment" notice with amount 0.00 is recognized and turned back into
+/// `notification`.
+///
+/// ```
+/// use swiss_qrust::decode_qr_text;
+///
+/// let payload = "SPC\n0200\n1\nCH4431999123000889012\nS\nRobert Schneider AG\nRue du Lac\n1268\n2501\nBiel\nCH\n\n\n\n\n\n\n\n1949.75\nCHF\n\n\n\n\n\n\n\nQRR\n210000000003139471430009017\nOrder of 15.10.2020\nEPD";
+/// let input = decode_qr_text(payload)?;
+/// assert_eq!(input.creditor_address.city, "Biel");
+/// assert_eq!(input.amount.as_deref(), Some("1949.75"));
+/// assert_eq!(input.debtor_address.is_none(), true);
+/// # Ok::<(), swiss_qrust::QrDecodeError>(())
+/// ```
+pub fn decode_qr_text(text: &str) -> Result<InputBill, QrDecodeError> {
+    let text = text.trim_end_matches(['\r', '\n']);
+    let fields: Vec<&str> = text.split('\n').map(|f| f.strip_suffix('\r').unwrap_or(f)).collect();
+
+    if fields.first().map(|f| f.trim_start_matches('\u{feff}')) != Some("SPC") {
+        return Err(QrDecodeError::NotQrBill);
+    }
+    if fields.len() < 3 {
+        return Err(QrDecodeError::FieldCount(fields.len()));
+    }
+    if !fields[1].starts_with("02") || fields[1].len() != 4 {
+        return Err(QrDecodeError::UnsupportedVersion(fields[1].to_string()));
+    }
+    if fields[2] != "1" {
+        return Err(QrDecodeError::UnsupportedCoding(fields[2].to_string()));
+    }
+    if !(MANDATORY_FIELDS..=MAX_FIELDS).contains(&fields.len()) {
+        return Err(QrDecodeError::FieldCount(fields.len()));
+    }
+    if fields[30] != "EPD" {
+        return Err(QrDecodeError::MissingTrailer);
+    }
+
+    let creditor_address = address(&fields, 4)?.ok_or(QrDecodeError::FieldCount(fields.len()))?;
+    let debtor_address = address(&fields, 20)?;
+
+    let reference = match fields[27] {
+        "NON" => None,
+        "QRR" | "SCOR" => optional(fields[28]),
+        other => return Err(QrDecodeError::UnknownReferenceType(other.to_string())),
+    };
+
+    let mut amount = optional(fields[18]);
+    let mut unstructured_message = optional(fields[29]);
+    let mut notification = None;
+    if amount.as_deref() == Some(NOTIFICATION_AMOUNT)
+        && let Some(language) = notice_language(unstructured_message.as_deref())
+    {
+        notification = Some(language.code().to_string());
+        amount = None;
+        unstructured_message = None;
+    }
+
+    let alternative_schemes = [fields.get(32), fields.get(33)].map(|f| f.and_then(|f| optional(f)));
+
+    Ok(InputBill {
+        iban: fields[3].to_string(),
+        creditor_address,
+        debtor_address,
+        currency: fields[19].to_string(),
+        amount,
+        reference,
+        unstructured_message,
+        bill_information: fields.get(31).and_then(|f| optional(f)),
+        swico_bill_information: None,
+        alternative_schemes: alternative_schemes.iter().any(Option::is_some).then_some(alternative_schemes),
+        sanitize: None,
+        resolve_countries: None,
+        notification,
+        profile: None,
+        variables: None,
+    })
+}
+
+impl BillData {
+    /// Decodes and validates a QR-bill payload, see [`decode_qr_text`].
+    pub fn from_qr_text(text: &str) -> Result<Self, QrDecodeError> {
+        Ok(BillData::try_from(decode_qr_text(text)?)?)
+    }
+}
+
+fn optional(field: &str) -> Option<String> {
+    (!field.is_empty()).then(|| field.to_string())
+}
+
+/// The 7 address fields starting at `start`; `None` if all are empty.
+fn address(fields: &[&str], start: usize) -> Result<Option<InputAddress>, QrDecodeError> {
+    let f = &fields[start..start + 7];
+    if f.iter().all(|field| field.is_empty()) {
+        return Ok(None);
+    }
+    if f[0] != "S" {
+        return Err(QrDecodeError::UnsupportedAddressType(f[0].to_string(), start));
+    }
+    Ok(Some(InputAddress {
+        name: f[1].to_string(),
+        street: optional(f[2]),
+        house_num: optional(f[3]),
+        plz: f[4].to_string(),
+        city: f[5].to_string(),
+        country: f[6].to_string(),
+    }))
+}
+
+fn notice_language(message: Option<&str>) -> Option<Language> {
+    let message = message?;
+    [Language::De, Language::Fr, Language::It, Language::En]
+        .into_iter()
+        .find(|&language| label(LabelKey::DoNotUseForPayment, language).is_ok_and(|notice| notice == message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qr_bill::QrBill;
+    use crate::{build_bill, AlternativeProcedure};
+
+    #[test]
+    fn round_trips_generated_payloads() {
+        let mut bill = build_bill().unwrap();
+        bill.alternative_schemes = [Some(AlternativeProcedure::parse("eBill/B/peter@sample.ch").unwrap()), None];
+        bill.unstructured_message = Some("Order {17}".into());
+        let payload = QrBill::new(&bill).unwrap().create_qr_text().unwrap();
+
+        assert_eq!(BillData::from_qr_text(&payload).unwrap(), bill);
+        assert_eq!(BillData::from_qr_text(&payload.replace("\r\n", "\n")).unwrap(), bill);
+    }
+
+    #[test]
+    fn recognizes_notifications() {
+        let bill = BillData { amount: None, unstructured_message: None, ..build_bill().unwrap() }
+            .into_notification(Language::It)
+            .unwrap();
+        let payload = QrBill::new(&bill).unwrap().create_qr_text().unwrap();
+
+        let input = decode_qr_text(&payload).unwrap();
+        assert_eq!(input.notification.as_deref(), Some("it"));
+        assert_eq!(input.amount, None);
+        assert_eq!(BillData::try_from(input).unwrap(), bill);
+    }
+
+    #[test]
+    fn rejects_malformed_payloads() {
+        let payload = QrBill::new(&build_bill().unwrap()).unwrap().create_qr_text().unwrap();
+        let err = |text: &str| decode_qr_text(text).unwrap_err();
+
+        assert!(matches!(err("hello"), QrDecodeError::NotQrBill));
+        assert!(matches!(
+            err(&payload.replacen("0200", "0100", 1)),
+            QrDecodeError::UnsupportedVersion(v) if v == "0100"
+        ));
+        assert!(matches!(err(&payload.replace("\r\nEPD", "")), QrDecodeError::FieldCount(30)));
+        assert!(matches!(err(&payload.replace("\r\nEPD", "\r\nEND")), QrDecodeError::MissingTrailer));
+        assert!(matches!(
+            err(&payload.replacen("\r\nS\r\n", "\r\nK\r\n", 1)),
+            QrDecodeError::UnsupportedAddressType(t, 4) if t == "K"
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_content() {
+        let payload = QrBill::new(&build_bill().unwrap()).unwrap().create_qr_text().unwrap();
+        let broken = payload.replace("CH6431961000004421557", "CH6431961000004421558");
+        let Err(QrDecodeError::Bill(err)) = BillData::from_qr_text(&broken) else { panic!("expected a bill error") };
+        assert!(matches!(err.kind(), BillError::IbanError(_)));
+    }
+}
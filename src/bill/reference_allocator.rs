@@ -0,0 +1,359 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Issues QR and SCOR references that are never handed out twice.
+//!
+//! [`generate_qrr_with_checksum`] and [`generate_iso11649_with_checksum`]
+//! only add the check digits; which number to use next is up to the
+//! caller. [`ReferenceAllocator`] keeps one increasing counter per creditor
+//! IBAN in a [`ReferenceStore`], so references stay unique across runs and
+//! - with [`FileReferenceStore`] - across processes sharing a file.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use thiserror::Error;
+
+use crate::support::utils::{generate_iso11649_with_checksum, generate_qrr_with_checksum};
+use crate::{Iban, ReferenceType};
+
+#[derive(Debug, Error)]
+pub enum AllocatorError {
+    #[error("reference store I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("reference store is corrupt: {0}")]
+    CorruptStore(String),
+    #[error("reference numbers for {0} are exhausted")]
+    Exhausted(String),
+    #[error("cannot reserve an empty range")]
+    EmptyRange,
+}
+
+/// Persistent counters, one per key. Implementations must make
+/// [`reserve`](ReferenceStore::reserve) atomic: two concurrent calls never
+/// get overlapping ranges.
+pub trait ReferenceStore {
+    /// Reserves `count` consecutive sequence numbers for `key` and returns
+    /// the first one. Counters start at 1.
+    fn reserve(&self, key: &str, count: u64) -> Result<u64, AllocatorError>;
+}
+
+/// Counters kept in memory, for tests and single-process use.
+#[derive(Debug, Default)]
+pub struct MemoryReferenceStore {
+    next: Mutex<HashMap<String, u64>>,
+}
+
+impl MemoryReferenceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReferenceStore for MemoryReferenceStore {
+    fn reserve(&self, key: &str, count: u64) -> Result<u64, AllocatorError> {
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let counter = next.entry(key.to_string()).or_insert(1);
+        advance(counter, key, count)
+    }
+}
+
+/// Counters kept in a JSON file (`{"<key>": <next>}`), so several
+/// processes can share them.
+///
+/// Each reservation holds an exclusive lock on `<file>.lock` and replaces
+/// the file by writing `<file>.tmp` and renaming it over the old one, so a
+/// crash at any point leaves either the old or the new counters - never a
+/// truncated file that would restart the counters and reissue references.
+#[derive(Debug, Clone)]
+pub struct FileReferenceStore {
+    path: PathBuf,
+}
+
+impl FileReferenceStore {
+    /// Uses the file at `path`, which is created on first use.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+
+    fn sibling(&self, extension: &str) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(extension);
+        self.path.with_file_name(name)
+    }
+
+    /// The counters, empty if the file doesn't exist yet. An existing file
+    /// that is empty or unreadable is an error: starting over would reissue
+    /// references.
+    fn read_counters(&self) -> Result<HashMap<String, u64>, AllocatorError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+        if content.trim().is_empty() {
+            return Err(AllocatorError::CorruptStore(format!("{} is empty", self.path.display())));
+        }
+        serde_json::from_str(&content).map_err(|e| AllocatorError::CorruptStore(e.to_string()))
+    }
+
+    fn write_counters(&self, counters: &HashMap<String, u64>) -> Result<(), AllocatorError> {
+        let content = serde_json::to_string_pretty(counters)
+            .map_err(|e| AllocatorError::CorruptStore(e.to_string()))?;
+        let temp = self.sibling(".tmp");
+        let mut file = File::create(&temp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp, &self.path)?;
+        // Make the rename itself durable.
+        #[cfg(unix)]
+        if let Some(dir) = self.path.parent() {
+            let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl ReferenceStore for FileReferenceStore {
+    fn reserve(&self, key: &str, count: u64) -> Result<u64, AllocatorError> {
+        let lock = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.sibling(".lock"))?;
+        // Released when `lock` is dropped, also on the error paths.
+        lock.lock()?;
+
+        let mut counters = self.read_counters()?;
+        let counter = counters.entry(key.to_string()).or_insert(1);
+        let first = advance(counter, key, count)?;
+        self.write_counters(&counters)?;
+
+        Ok(first)
+    }
+}
+
+/// Moves `counter` past `count` numbers, returning the first one.
+fn advance(counter: &mut u64, key: &str, count: u64) -> Result<u64, AllocatorError> {
+    if count == 0 {
+        return Err(AllocatorError::EmptyRange);
+    }
+    let first = *counter;
+    *counter = first
+        .checked_add(count)
+        .ok_or_else(|| AllocatorError::Exhausted(key.to_string()))?;
+    Ok(first)
+}
+
+/// Hands out references for one creditor IBAN: QR references for a
+/// QR-IBAN, SCOR references otherwise, as the spec pairs them.
+///
+/// ```
+/// use swiss_qrust::{Iban, MemoryReferenceStore, ReferenceAllocator, ReferenceType};
+///
+/// let store = MemoryReferenceStore::new();
+/// let iban: Iban = "CH44 3199 9123 0008 8901 2".parse()?;
+/// let allocator = ReferenceAllocator::new(&store, &iban);
+///
+/// assert_eq!(allocator.next()?, ReferenceType::QrRef("000000000000000000000000011".into()));
+/// let batch: Vec<_> = allocator.reserve(3)?.collect();
+/// assert_eq!(batch[0], ReferenceType::QrRef("000000000000000000000000026".into()));
+/// assert_eq!(batch.len(), 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ReferenceAllocator<'a, S: ReferenceStore + ?Sized> {
+    store: &'a S,
+    iban: Iban,
+}
+
+impl<'a, S: ReferenceStore + ?Sized> ReferenceAllocator<'a, S> {
+    pub fn new(store: &'a S, iban: &Iban) -> Self {
+        Self { store, iban: iban.clone() }
+    }
+
+    /// Store key: the IBAN plus reference kind, so switching an account
+    /// between QRR and SCOR can't reuse numbers.
+    fn key(&self) -> String {
+        let kind = if self.iban.is_qr_iban() { "QRR" } else { "SCOR" };
+        format!("{}/{kind}", self.iban.as_str())
+    }
+
+    pub fn next(&self) -> Result<ReferenceType, AllocatorError> {
+        let sequence = self.store.reserve(&self.key(), 1)?;
+        Ok(reference_for(self.iban.is_qr_iban(), sequence))
+    }
+
+    /// Reserves `count` consecutive references at once, e.g. for a batch
+    /// run; unused ones are not returned to the pool.
+    pub fn reserve(&self, count: u64) -> Result<ReservedReferences, AllocatorError> {
+        let first = self.store.reserve(&self.key(), count)?;
+        Ok(ReservedReferences { qr: self.iban.is_qr_iban(), next: first, end: first + count })
+    }
+}
+
+/// A reserved range of references; iterating yields them in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservedReferences {
+    qr: bool,
+    next: u64,
+    end: u64,
+}
+
+impl ReservedReferences {
+    /// Sequence numbers still left in the range.
+    pub fn sequence_numbers(&self) -> std::ops::Range<u64> {
+        self.next..self.end
+    }
+}
+
+impl Iterator for ReservedReferences {
+    type Item = ReferenceType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.next < self.end).then(|| {
+            self.next += 1;
+            reference_for(self.qr, self.next - 1)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::try_from(self.end - self.next).unwrap_or(usize::MAX);
+        (len, Some(len))
+    }
+}
+
+fn reference_for(qr: bool, sequence: u64) -> ReferenceType {
+    let digits = sequence.to_string();
+    if qr {
+        ReferenceType::QrRef(generate_qrr_with_checksum(&digits).expect("sequence numbers start at 1"))
+    } else {
+        ReferenceType::Creditor(generate_iso11649_with_checksum(&digits).expect("sequence numbers start at 1"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::validators::{is_valid_iso11649_reference, is_valid_qr_reference};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    const QR_IBAN: &str = "CH44 3199 9123 0008 8901 2";
+    const PLAIN_IBAN: &str = "CH93 0076 2011 6238 5295 7";
+
+    fn temp_store(name: &str) -> (PathBuf, FileReferenceStore) {
+        let path = std::env::temp_dir().join(format!("swiss_qrust_refs_{}_{name}.json", std::process::id()));
+        remove_store(&path);
+        (path.clone(), FileReferenceStore::new(path))
+    }
+
+    fn remove_store(path: &Path) {
+        let store = FileReferenceStore::new(path);
+        for file in [path.to_path_buf(), store.sibling(".lock"), store.sibling(".tmp")] {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    #[test]
+    fn issues_valid_references_matching_the_iban() {
+        let store = MemoryReferenceStore::new();
+        let qr = ReferenceAllocator::new(&store, &QR_IBAN.parse().unwrap());
+        let scor = ReferenceAllocator::new(&store, &PLAIN_IBAN.parse().unwrap());
+
+        let ReferenceType::QrRef(qrr) = qr.next().unwrap() else { panic!("expected QRR") };
+        assert!(is_valid_qr_reference(&qrr).is_ok());
+
+        let ReferenceType::Creditor(rf) = scor.next().unwrap() else { panic!("expected SCOR") };
+        assert!(is_valid_iso11649_reference(&rf).is_ok());
+        assert_eq!(rf, "RF741");
+    }
+
+    #[test]
+    fn counters_are_per_iban() {
+        let store = MemoryReferenceStore::new();
+        let other: Iban = "CH64 3196 1000 0044 2155 7".parse().unwrap();
+        let a = ReferenceAllocator::new(&store, &QR_IBAN.parse().unwrap());
+        let b = ReferenceAllocator::new(&store, &other);
+
+        assert_eq!(a.next().unwrap(), b.next().unwrap());
+        assert_ne!(a.next().unwrap(), a.next().unwrap());
+    }
+
+    #[test]
+    fn reserved_ranges_do_not_overlap() {
+        let store = MemoryReferenceStore::new();
+        let allocator = ReferenceAllocator::new(&store, &QR_IBAN.parse().unwrap());
+
+        let first = allocator.reserve(10).unwrap();
+        assert_eq!(first.sequence_numbers(), 1..11);
+        assert_eq!(allocator.reserve(5).unwrap().sequence_numbers(), 11..16);
+        assert_eq!(first.count(), 10);
+        assert!(matches!(allocator.reserve(0), Err(AllocatorError::EmptyRange)));
+    }
+
+    #[test]
+    fn file_store_persists_across_instances() {
+        let (path, store) = temp_store("persist");
+        let iban: Iban = PLAIN_IBAN.parse().unwrap();
+
+        let first = ReferenceAllocator::new(&store, &iban).reserve(3).unwrap();
+        let reopened = FileReferenceStore::new(&path);
+        let next = ReferenceAllocator::new(&reopened, &iban).reserve(1).unwrap();
+
+        assert_eq!(first.sequence_numbers(), 1..4);
+        assert_eq!(next.sequence_numbers(), 4..5);
+        remove_store(&path);
+    }
+
+    #[test]
+    fn file_store_is_safe_under_concurrency() {
+        let (path, store) = temp_store("concurrent");
+        let store = Arc::new(store);
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    let allocator = ReferenceAllocator::new(store.as_ref(), &QR_IBAN.parse().unwrap());
+                    (0..25).map(|_| allocator.next().unwrap()).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let all: Vec<ReferenceType> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+        let unique: HashSet<_> = all.iter().map(|r| format!("{r:?}")).collect();
+        assert_eq!(all.len(), 200);
+        assert_eq!(unique.len(), 200);
+        remove_store(&path);
+    }
+
+    #[test]
+    fn corrupt_file_is_reported() {
+        let (path, store) = temp_store("corrupt");
+        std::fs::write(&path, "not json").unwrap();
+
+        let allocator = ReferenceAllocator::new(&store, &QR_IBAN.parse().unwrap());
+        assert!(matches!(allocator.next(), Err(AllocatorError::CorruptStore(_))));
+        remove_store(&path);
+    }
+
+    #[test]
+    fn empty_file_is_not_a_fresh_store() {
+        // What a crash between truncating and writing the file used to leave.
+        let (path, store) = temp_store("empty");
+        std::fs::write(&path, "").unwrap();
+
+        let allocator = ReferenceAllocator::new(&store, &QR_IBAN.parse().unwrap());
+        assert!(matches!(allocator.next(), Err(AllocatorError::CorruptStore(_))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+        remove_store(&path);
+    }
+}
@@ -0,0 +1,365 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Structured QR references.
+//!
+//! Billers usually don't hand out QR references at random: the bank
+//! assigns a BESR-ID prefix, and customer and invoice numbers sit at fixed
+//! positions so incoming payments can be matched without a lookup table. A
+//! [`QrReferenceTemplate`] describes that layout, e.g.
+//! `{besr:6}{customer:8}{invoice:12}`, and builds references from it
+//! ([`QrReferenceTemplate::build`]) or splits received ones back into their
+//! fields ([`QrReferenceTemplate::decompose`]).
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::support::utils::generate_qrr_with_checksum;
+use crate::support::validators::{is_valid_qr_reference, ReferenceError};
+
+/// Digits available in a QR reference before the check digit.
+pub const QR_REFERENCE_PAYLOAD_LEN: usize = 26;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ReferenceTemplateError {
+    #[error("invalid reference template: {0}")]
+    InvalidTemplate(String),
+    #[error("reference template is {0} digits long, at most 26 fit a QR reference")]
+    TemplateTooLong(usize),
+    #[error("no value for field '{0}'")]
+    MissingField(String),
+    #[error("template has no field '{0}'")]
+    UnknownField(String),
+    #[error("value for field '{field}' must be digits only")]
+    InvalidFieldValue { field: String },
+    #[error("value for field '{field}' is longer than {width} digits")]
+    FieldTooLong { field: String, width: usize },
+    #[error("reference does not match the template")]
+    TemplateMismatch,
+    #[error(transparent)]
+    Reference(#[from] ReferenceError),
+}
+
+/// Adds a segment's `width` to the template's `total`, which must stay
+/// within the 26 payload digits. Checked, as widths come from config files.
+fn add_width(total: &mut usize, width: usize) -> Result<(), ReferenceTemplateError> {
+    *total = total
+        .checked_add(width)
+        .filter(|t| *t <= QR_REFERENCE_PAYLOAD_LEN)
+        .ok_or(ReferenceTemplateError::TemplateTooLong(total.saturating_add(width)))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Field { name: String, width: usize },
+    Literal(String),
+}
+
+impl Segment {
+    fn width(&self) -> usize {
+        match self {
+            Segment::Field { width, .. } => *width,
+            Segment::Literal(digits) => digits.len(),
+        }
+    }
+}
+
+/// Layout of the 26 payload digits of a QR reference.
+///
+/// Named fields are written `{name:width}`, fixed digits as they are
+/// (`{besr:6}00{invoice:18}`). A template shorter than 26 digits is
+/// right-aligned and zero-padded on the left, like
+/// [`generate_qrr_with_checksum`].
+///
+/// ```
+/// use swiss_qrust::QrReferenceTemplate;
+///
+/// let template: QrReferenceTemplate = "{besr:6}{customer:8}{invoice:12}".parse()?;
+/// let reference = template.build(&[("besr", "210000"), ("customer", "4711"), ("invoice", "2026001")])?;
+/// assert_eq!(reference, "210000000047110000020260017");
+///
+/// let fields = template.decompose(&reference)?;
+/// assert_eq!(fields.get("customer"), Some("00004711"));
+/// assert_eq!(fields.get_number("invoice"), Some(2026001));
+/// # Ok::<(), swiss_qrust::ReferenceTemplateError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrReferenceTemplate {
+    segments: Vec<Segment>,
+}
+
+/// Field values taken from a QR reference by
+/// [`QrReferenceTemplate::decompose`], in template order and with leading
+/// zeros kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrReferenceFields {
+    fields: Vec<(String, String)>,
+}
+
+impl QrReferenceFields {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// The field as a number, leading zeros ignored.
+    pub fn get_number(&self, name: &str) -> Option<u128> {
+        self.get(name)?.parse().ok()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+}
+
+impl QrReferenceTemplate {
+    pub fn parse(template: &str) -> Result<Self, ReferenceTemplateError> {
+        let invalid = |msg: &str| ReferenceTemplateError::InvalidTemplate(msg.to_string());
+        let mut segments = Vec::new();
+        let mut total = 0;
+        let mut rest = template.trim();
+
+        while let Some(c) = rest.chars().next() {
+            if c.is_whitespace() {
+                rest = &rest[c.len_utf8()..];
+            } else if c.is_ascii_digit() {
+                let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+                add_width(&mut total, end)?;
+                match segments.last_mut() {
+                    Some(Segment::Literal(digits)) => digits.push_str(&rest[..end]),
+                    _ => segments.push(Segment::Literal(rest[..end].to_string())),
+                }
+                rest = &rest[end..];
+            } else if c == '{' {
+                let end = rest.find('}').ok_or_else(|| invalid("unclosed '{'"))?;
+                let (name, width) = rest[1..end]
+                    .split_once(':')
+                    .ok_or_else(|| invalid("fields are written {name:width}"))?;
+                let name = name.trim();
+                if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    return Err(invalid(&format!("invalid field name '{name}'")));
+                }
+                let width: usize = width
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|w| *w > 0)
+                    .ok_or_else(|| invalid(&format!("invalid width for field '{name}'")))?;
+                add_width(&mut total, width)?;
+                if segments.iter().any(|s| matches!(s, Segment::Field { name: n, .. } if n == name)) {
+                    return Err(invalid(&format!("duplicate field '{name}'")));
+                }
+                segments.push(Segment::Field { name: name.to_string(), width });
+                rest = &rest[end + 1..];
+            } else {
+                return Err(invalid(&format!("unexpected character '{c}'")));
+            }
+        }
+
+        if total == 0 {
+            return Err(invalid("template is empty"));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Number of digits the template covers (at most 26).
+    pub fn width(&self) -> usize {
+        self.segments.iter().map(Segment::width).sum()
+    }
+
+    /// Names of the template's fields, in order.
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|s| match s {
+            Segment::Field { name, .. } => Some(name.as_str()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// Builds a 27-digit QR reference, check digit included. Values are
+    /// zero-padded to their field width; every field needs a value.
+    pub fn build(&self, values: &[(&str, &str)]) -> Result<String, ReferenceTemplateError> {
+        if let Some((unknown, _)) = values.iter().find(|(n, _)| !self.field_names().any(|f| f == *n)) {
+            return Err(ReferenceTemplateError::UnknownField(unknown.to_string()));
+        }
+
+        let mut payload = String::with_capacity(QR_REFERENCE_PAYLOAD_LEN);
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(digits) => payload.push_str(digits),
+                Segment::Field { name, width } => {
+                    let value = values
+                        .iter()
+                        .find(|(n, _)| n == name)
+                        .map(|(_, v)| v.trim())
+                        .ok_or_else(|| ReferenceTemplateError::MissingField(name.clone()))?;
+                    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+                        return Err(ReferenceTemplateError::InvalidFieldValue { field: name.clone() });
+                    }
+                    if value.len() > *width {
+                        return Err(ReferenceTemplateError::FieldTooLong { field: name.clone(), width: *width });
+                    }
+                    payload.push_str(&format!("{value:0>width$}"));
+                }
+            }
+        }
+
+        Ok(generate_qrr_with_checksum(&payload)?)
+    }
+
+    /// Splits a QR reference (spaces allowed) into the template's fields
+    /// after checking its check digit, the zero padding and any fixed
+    /// digits.
+    pub fn decompose(&self, reference: &str) -> Result<QrReferenceFields, ReferenceTemplateError> {
+        let reference: String = reference.chars().filter(|c| !c.is_whitespace()).collect();
+        is_valid_qr_reference(&reference)?;
+
+        let padding = QR_REFERENCE_PAYLOAD_LEN - self.width();
+        if !reference[..padding].bytes().all(|b| b == b'0') {
+            return Err(ReferenceTemplateError::TemplateMismatch);
+        }
+
+        let mut fields = Vec::new();
+        let mut pos = padding;
+        for segment in &self.segments {
+            let digits = &reference[pos..pos + segment.width()];
+            match segment {
+                Segment::Literal(expected) if expected != digits => {
+                    return Err(ReferenceTemplateError::TemplateMismatch);
+                }
+                Segment::Literal(_) => {}
+                Segment::Field { name, .. } => fields.push((name.clone(), digits.to_string())),
+            }
+            pos += segment.width();
+        }
+
+        Ok(QrReferenceFields { fields })
+    }
+}
+
+impl FromStr for QrReferenceTemplate {
+    type Err = ReferenceTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for QrReferenceTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                Segment::Field { name, width } => write!(f, "{{{name}:{width}}}")?,
+                Segment::Literal(digits) => f.write_str(digits)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> QrReferenceTemplate {
+        "{besr:6}{customer:8}{invoice:12}".parse().unwrap()
+    }
+
+    #[test]
+    fn builds_reference_with_check_digit() {
+        let reference = template()
+            .build(&[("besr", "210000"), ("customer", "31394"), ("invoice", "714300090")])
+            .unwrap();
+        assert_eq!(reference.len(), 27);
+        assert!(is_valid_qr_reference(&reference).is_ok());
+        assert_eq!(&reference[..26], "21000000031394000714300090");
+    }
+
+    #[test]
+    fn decomposes_reference_into_fields() {
+        let reference = template()
+            .build(&[("besr", "210000"), ("customer", "31394"), ("invoice", "714300090")])
+            .unwrap();
+        let fields = template().decompose(&reference).unwrap();
+        assert_eq!(
+            fields.iter().collect::<Vec<_>>(),
+            vec![("besr", "210000"), ("customer", "00031394"), ("invoice", "000714300090")]
+        );
+        assert_eq!(fields.get_number("customer"), Some(31394));
+    }
+
+    #[test]
+    fn short_template_is_zero_padded() {
+        let template: QrReferenceTemplate = "{member:6}99{year:4}".parse().unwrap();
+        let reference = template.build(&[("member", "1234"), ("year", "2026")]).unwrap();
+        assert_eq!(&reference[..26], "00000000000000001234992026");
+
+        let fields = template.decompose(&reference).unwrap();
+        assert_eq!(fields.get("member"), Some("001234"));
+    }
+
+    #[test]
+    fn decompose_rejects_reference_from_other_layout() {
+        let template: QrReferenceTemplate = "{member:6}99{year:4}".parse().unwrap();
+        let wrong_literal = generate_qrr_with_checksum("0012349820 26").unwrap();
+        assert_eq!(template.decompose(&wrong_literal), Err(ReferenceTemplateError::TemplateMismatch));
+
+        let not_padded = generate_qrr_with_checksum("10000000000000001234992026").unwrap();
+        assert_eq!(template.decompose(&not_padded), Err(ReferenceTemplateError::TemplateMismatch));
+    }
+
+    #[test]
+    fn build_validates_values() {
+        let t = template();
+        assert_eq!(
+            t.build(&[("besr", "210000"), ("customer", "1")]),
+            Err(ReferenceTemplateError::MissingField("invoice".into()))
+        );
+        assert_eq!(
+            t.build(&[("besr", "2100000"), ("customer", "1"), ("invoice", "1")]),
+            Err(ReferenceTemplateError::FieldTooLong { field: "besr".into(), width: 6 })
+        );
+        assert_eq!(
+            t.build(&[("besr", "21000A"), ("customer", "1"), ("invoice", "1")]),
+            Err(ReferenceTemplateError::InvalidFieldValue { field: "besr".into() })
+        );
+        assert_eq!(
+            t.build(&[("besr", "1"), ("customer", "1"), ("invoice", "1"), ("year", "1")]),
+            Err(ReferenceTemplateError::UnknownField("year".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        for template in ["", "{a:0}", "{a:x}", "{a}", "{a:3", "{a:3}{a:3}", "x{a:3}", "{a b:3}"] {
+            assert!(
+                matches!(QrReferenceTemplate::parse(template), Err(ReferenceTemplateError::InvalidTemplate(_))),
+                "{template:?}"
+            );
+        }
+        assert_eq!(
+            QrReferenceTemplate::parse("{a:20}{b:7}"),
+            Err(ReferenceTemplateError::TemplateTooLong(27))
+        );
+        assert_eq!(QrReferenceTemplate::parse("{a:27}"), Err(ReferenceTemplateError::TemplateTooLong(27)));
+        assert_eq!(
+            QrReferenceTemplate::parse("{a:18446744073709551615}{b:2}"),
+            Err(ReferenceTemplateError::TemplateTooLong(usize::MAX))
+        );
+        assert_eq!(
+            QrReferenceTemplate::parse("{a:2}{b:18446744073709551615}"),
+            Err(ReferenceTemplateError::TemplateTooLong(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let template: QrReferenceTemplate = "{besr:6} 00 {invoice:18}".parse().unwrap();
+        assert_eq!(template.to_string(), "{besr:6}00{invoice:18}");
+        assert_eq!(template.to_string().parse::<QrReferenceTemplate>().unwrap(), template);
+    }
+}
@@ -10,8 +10,58 @@
 //! Annex D. Not part of the SIX standardisation itself - Swico publishes
 //! and owns the S1 syntax (<http://swiss-qr-invoice.org/downloads/qr-bill-s1-syntax-de.pdf>).
 use chrono::{Days, NaiveDate};
+use thiserror::Error;
 use crate::BillError;
-use crate::input::InputSwicoBillInformation;
+use crate::input::{InputPaymentCondition, InputRateDetail, InputSwicoBillInformation};
+
+/// A violation of the S1 syntax or its rules, with the tag it concerns
+/// where there is one.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SwicoError {
+    #[error("not Swico S1 billing information (expected a //S1/ prefix)")]
+    NotSwico,
+    #[error("'{0}' is not a Swico tag")]
+    InvalidTag(String),
+    #[error("tag /{tag}/ has no value")]
+    MissingValue { tag: u32 },
+    #[error("tag /{tag}/ must come after /{previous}/ (tags are ascending and unique)")]
+    TagOrder { tag: u32, previous: u32 },
+    #[error("tag /{tag}/: invalid date '{value}' (expected YYMMDD)")]
+    InvalidDate { tag: u32, value: String },
+    #[error("tag /{tag}/: invalid number '{value}'")]
+    InvalidNumber { tag: u32, value: String },
+    #[error("tag /{tag}/: '{value}' must consist of digits only")]
+    NotNumeric { tag: u32, value: String },
+    #[error("tag /{tag}/: {value} is not a percentage between 0 and 100")]
+    PercentageOutOfRange { tag: u32, value: f64 },
+    #[error("tag /31/: give either a VAT date or a start and end date, not both")]
+    ConflictingVatDates,
+    #[error("tag /31/: a VAT period needs both a start and an end date")]
+    IncompleteVatPeriod,
+    #[error("tag /31/: VAT period ends ({end}) before it starts ({start})")]
+    VatPeriodReversed { start: NaiveDate, end: NaiveDate },
+    #[error("tag /32/: give either a single VAT rate or rate details, not both")]
+    ConflictingVatRates,
+}
+
+impl SwicoError {
+    /// The S1 tag the error is about, if any.
+    pub fn tag(&self) -> Option<u32> {
+        match self {
+            SwicoError::NotSwico | SwicoError::InvalidTag(_) => None,
+            SwicoError::MissingValue { tag }
+            | SwicoError::TagOrder { tag, .. }
+            | SwicoError::InvalidDate { tag, .. }
+            | SwicoError::InvalidNumber { tag, .. }
+            | SwicoError::NotNumeric { tag, .. }
+            | SwicoError::PercentageOutOfRange { tag, .. } => Some(*tag),
+            SwicoError::ConflictingVatDates
+            | SwicoError::IncompleteVatPeriod
+            | SwicoError::VatPeriodReversed { .. } => Some(31),
+            SwicoError::ConflictingVatRates => Some(32),
+        }
+    }
+}
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct SwicoBillInformation {
@@ -50,7 +100,9 @@ pub struct PaymentCondition {
 
 impl SwicoBillInformation {
     /// Encodes this bill information as `StrdBkgInf` text (Swico S1
-    /// syntax), or `None` if nothing is set.
+    /// syntax), or `None` if nothing is set. Fields breaking the S1 rules
+    /// are encoded as far as possible; check them with [`Self::validate`]
+    /// first.
     pub fn encode_as_text(&self) -> Option<String> {
         let mut out = String::from("//S1");
 
@@ -122,6 +174,102 @@ impl SwicoBillInformation {
         Some(info)
     }
 
+    /// Like [`Self::decode_text`], but rejects anything the S1 syntax
+    /// doesn't allow instead of skipping it: malformed tags and values,
+    /// tags out of order or repeated, and fields breaking the rules checked
+    /// by [`Self::validate`]. Unknown tags are still ignored, per spec
+    /// Annex D.
+    ///
+    /// ```
+    /// use swiss_qrust::{SwicoBillInformation, SwicoError};
+    ///
+    /// assert!(SwicoBillInformation::decode_strict("//S1/10/10201409/11/190512/32/7.7").is_ok());
+    /// assert_eq!(
+    ///     SwicoBillInformation::decode_strict("//S1/11/190512/10/10201409"),
+    ///     Err(SwicoError::TagOrder { tag: 10, previous: 11 })
+    /// );
+    /// ```
+    pub fn decode_strict(text: &str) -> Result<Self, SwicoError> {
+        let body = text.strip_prefix("//S1/").ok_or(SwicoError::NotSwico)?;
+        let parts = split_unescaped(body);
+
+        let mut info = SwicoBillInformation::default();
+        let mut previous: Option<u32> = None;
+        for pair in parts.chunks(2) {
+            let tag = pair[0]
+                .parse::<u32>()
+                .ok()
+                .filter(|_| pair[0].bytes().all(|b| b.is_ascii_digit()))
+                .ok_or_else(|| SwicoError::InvalidTag(pair[0].clone()))?;
+            if let Some(previous) = previous.filter(|&previous| previous >= tag) {
+                return Err(SwicoError::TagOrder { tag, previous });
+            }
+            previous = Some(tag);
+
+            let value = pair.get(1).map(String::as_str).unwrap_or("");
+            if value.is_empty() {
+                return Err(SwicoError::MissingValue { tag });
+            }
+            decode_element_strict(&mut info, tag, value)?;
+        }
+
+        info.validate()?;
+        Ok(info)
+    }
+
+    /// Checks the S1 rules the field types can't express:
+    ///
+    /// - text fields are not empty, the VAT number (UID without `CHE`) is
+    ///   numeric,
+    /// - either a VAT date or a complete VAT period, which doesn't end
+    ///   before it starts,
+    /// - either a single VAT rate or rate details,
+    /// - VAT rates and discounts are percentages between 0 and 100.
+    pub fn validate(&self) -> Result<(), SwicoError> {
+        for (tag, text) in [
+            (10, &self.invoice_number),
+            (20, &self.customer_reference),
+            (30, &self.vat_number),
+        ] {
+            if text.as_deref().is_some_and(str::is_empty) {
+                return Err(SwicoError::MissingValue { tag });
+            }
+        }
+        if let Some(vat_number) = &self.vat_number
+            && !vat_number.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(SwicoError::NotNumeric { tag: 30, value: vat_number.clone() });
+        }
+
+        match (self.vat_date, self.vat_start_date, self.vat_end_date) {
+            (Some(_), None, None) | (None, None, None) => {}
+            (Some(_), _, _) => return Err(SwicoError::ConflictingVatDates),
+            (None, Some(start), Some(end)) if end < start => {
+                return Err(SwicoError::VatPeriodReversed { start, end });
+            }
+            (None, Some(_), Some(_)) => {}
+            (None, _, _) => return Err(SwicoError::IncompleteVatPeriod),
+        }
+
+        if self.vat_rate.is_some() && !self.vat_rate_details.is_empty() {
+            return Err(SwicoError::ConflictingVatRates);
+        }
+
+        let percentages = self
+            .vat_rate
+            .iter()
+            .chain(self.vat_rate_details.iter().map(|d| &d.rate))
+            .map(|rate| (32, *rate))
+            .chain(self.vat_import_taxes.iter().map(|d| (33, d.rate)))
+            .chain(self.payment_conditions.iter().map(|c| (40, c.discount)));
+        for (tag, value) in percentages {
+            if !(0.0..=100.0).contains(&value) {
+                return Err(SwicoError::PercentageOutOfRange { tag, value });
+            }
+        }
+        Ok(())
+    }
+
     /// The invoice date plus the number of days of the payment condition
     /// with a 0% discount (the "net" deadline), if both are present.
     pub fn due_date(&self) -> Option<NaiveDate> {
@@ -134,15 +282,18 @@ impl SwicoBillInformation {
 impl TryFrom<InputSwicoBillInformation> for SwicoBillInformation {
     type Error = BillError;
 
+    /// Errors are attached to the offending input field, see
+    /// [`BillError::field`].
     fn try_from(input: InputSwicoBillInformation) -> Result<Self, Self::Error> {
-        Ok(SwicoBillInformation {
+        let date = |field: &str, date: Option<String>| parse_input_date(date).map_err(|e| e.at(field));
+        let info = SwicoBillInformation {
             invoice_number: input.invoice_number,
-            invoice_date: parse_input_date(input.invoice_date)?,
+            invoice_date: date("invoice_date", input.invoice_date)?,
             customer_reference: input.customer_reference,
             vat_number: input.vat_number,
-            vat_date: parse_input_date(input.vat_date)?,
-            vat_start_date: parse_input_date(input.vat_start_date)?,
-            vat_end_date: parse_input_date(input.vat_end_date)?,
+            vat_date: date("vat_date", input.vat_date)?,
+            vat_start_date: date("vat_start_date", input.vat_start_date)?,
+            vat_end_date: date("vat_end_date", input.vat_end_date)?,
             vat_rate: input.vat_rate,
             vat_rate_details: input
                 .vat_rate_details
@@ -162,7 +313,54 @@ impl TryFrom<InputSwicoBillInformation> for SwicoBillInformation {
                 .into_iter()
                 .map(|c| PaymentCondition { discount: c.discount, days: c.days })
                 .collect(),
-        })
+        };
+        info.validate().map_err(|err| {
+            let field = match err.tag() {
+                Some(10) => "invoice_number",
+                Some(11) => "invoice_date",
+                Some(20) => "customer_reference",
+                Some(30) => "vat_number",
+                Some(31) if info.vat_date.is_some() => "vat_date",
+                Some(31) => "vat_start_date",
+                Some(32) if info.vat_rate.is_some() => "vat_rate",
+                Some(32) => "vat_rate_details",
+                Some(33) => "vat_import_taxes",
+                Some(40) => "payment_conditions",
+                _ => return BillError::from(err),
+            };
+            BillError::from(err).at(field)
+        })?;
+        Ok(info)
+    }
+}
+
+/// The reverse of the `TryFrom` above, e.g. to hand decoded billing
+/// information to a form or another language binding.
+impl From<&SwicoBillInformation> for InputSwicoBillInformation {
+    fn from(info: &SwicoBillInformation) -> Self {
+        let date = |d: Option<NaiveDate>| d.map(|d| d.format("%Y-%m-%d").to_string());
+        let details = |details: &[RateDetail]| {
+            (!details.is_empty())
+                .then(|| details.iter().map(|d| InputRateDetail { rate: d.rate, amount: d.amount }).collect())
+        };
+        InputSwicoBillInformation {
+            invoice_number: info.invoice_number.clone(),
+            invoice_date: date(info.invoice_date),
+            customer_reference: info.customer_reference.clone(),
+            vat_number: info.vat_number.clone(),
+            vat_date: date(info.vat_date),
+            vat_start_date: date(info.vat_start_date),
+            vat_end_date: date(info.vat_end_date),
+            vat_rate: info.vat_rate,
+            vat_rate_details: details(&info.vat_rate_details),
+            vat_import_taxes: details(&info.vat_import_taxes),
+            payment_conditions: (!info.payment_conditions.is_empty()).then(|| {
+                info.payment_conditions
+                    .iter()
+                    .map(|c| InputPaymentCondition { discount: c.discount, days: c.days })
+                    .collect()
+            }),
+        }
     }
 }
 
@@ -188,6 +386,72 @@ fn decode_element(info: &mut SwicoBillInformation, tag: u32, value: &str) {
     }
 }
 
+fn decode_element_strict(info: &mut SwicoBillInformation, tag: u32, value: &str) -> Result<(), SwicoError> {
+    let date = |text: &str| {
+        (text.len() == 6)
+            .then(|| parse_s1_date(text))
+            .flatten()
+            .ok_or_else(|| SwicoError::InvalidDate { tag, value: value.to_string() })
+    };
+    match tag {
+        10 => info.invoice_number = Some(value.to_string()),
+        11 => info.invoice_date = Some(date(value)?),
+        20 => info.customer_reference = Some(value.to_string()),
+        30 => info.vat_number = Some(value.to_string()),
+        // Bytes, not characters: anything non-ASCII is an invalid date anyway.
+        31 if value.len() == 12 && value.is_char_boundary(6) => {
+            let (start, end) = value.split_at(6);
+            info.vat_start_date = Some(date(start)?);
+            info.vat_end_date = Some(date(end)?);
+        }
+        31 => info.vat_date = Some(date(value)?),
+        32 if value.contains(':') => info.vat_rate_details = parse_rate_detail_list_strict(tag, value)?,
+        32 => info.vat_rate = Some(parse_decimal_strict(tag, value)?),
+        33 => info.vat_import_taxes = parse_rate_detail_list_strict(tag, value)?,
+        40 => {
+            info.payment_conditions = value
+                .split(';')
+                .map(|entry| {
+                    let (discount, days) = split_pair(tag, entry)?;
+                    let days = days
+                        .parse()
+                        .map_err(|_| SwicoError::InvalidNumber { tag, value: days.to_string() })?;
+                    Ok(PaymentCondition { discount: parse_decimal_strict(tag, discount)?, days })
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        _ => {} // unknown tags are ignored, per spec Annex D
+    }
+    Ok(())
+}
+
+fn parse_rate_detail_list_strict(tag: u32, value: &str) -> Result<Vec<RateDetail>, SwicoError> {
+    value
+        .split(';')
+        .map(|entry| {
+            let (rate, amount) = split_pair(tag, entry)?;
+            Ok(RateDetail { rate: parse_decimal_strict(tag, rate)?, amount: parse_decimal_strict(tag, amount)? })
+        })
+        .collect()
+}
+
+fn split_pair(tag: u32, entry: &str) -> Result<(&str, &str), SwicoError> {
+    entry
+        .split_once(':')
+        .ok_or_else(|| SwicoError::InvalidNumber { tag, value: entry.to_string() })
+}
+
+/// S1 numbers: digits with an optional decimal point, no sign or exponent.
+fn parse_decimal_strict(tag: u32, s: &str) -> Result<f64, SwicoError> {
+    let well_formed = !s.is_empty()
+        && s.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+        && s.bytes().filter(|&b| b == b'.').count() <= 1;
+    well_formed
+        .then(|| parse_decimal(s))
+        .flatten()
+        .ok_or_else(|| SwicoError::InvalidNumber { tag, value: s.to_string() })
+}
+
 fn set_vat_dates(info: &mut SwicoBillInformation, value: &str) {
     match value.len() {
         6 => {
@@ -197,7 +461,7 @@ fn set_vat_dates(info: &mut SwicoBillInformation, value: &str) {
                 info.vat_end_date = None;
             }
         }
-        12 => {
+        12 if value.is_char_boundary(6) => {
             let (start, end) = value.split_at(6);
             if let (Some(start), Some(end)) = (parse_s1_date(start), parse_s1_date(end)) {
                 info.vat_start_date = Some(start);
@@ -289,7 +553,7 @@ fn parse_s1_date(text: &str) -> Option<NaiveDate> {
 
 /// Formats like Java's `DecimalFormat("0.###")`: up to 3 decimal places,
 /// trailing zeros (and a bare trailing dot) dropped.
-fn s1_number(n: f64) -> String {
+pub(crate) fn s1_number(n: f64) -> String {
     let rounded = (n * 1000.0).round() / 1000.0;
     let mut s = format!("{:.3}", rounded);
     while s.ends_with('0') {
@@ -316,6 +580,18 @@ mod tests {
         NaiveDate::from_ymd_opt(y, m, d).unwrap()
     }
 
+    #[test]
+    fn converts_back_to_input() {
+        let info = SwicoBillInformation::decode_text("//S1/10/10201409/11/190512/31/190101190131/32/7.7/40/2:10;0:30")
+            .unwrap();
+        let input = InputSwicoBillInformation::from(&info);
+
+        assert_eq!(input.invoice_date.as_deref(), Some("2019-05-12"));
+        assert_eq!(input.vat_start_date.as_deref(), Some("2019-01-01"));
+        assert!(input.vat_rate_details.is_none());
+        assert_eq!(SwicoBillInformation::try_from(input).unwrap(), info);
+    }
+
     // Test vectors from Implementation Guidelines Annex D, Table 31
     // ("Billing information of Swico, examples").
 
@@ -424,4 +700,83 @@ mod tests {
     fn non_swico_text_does_not_decode() {
         assert_eq!(SwicoBillInformation::decode_text("Invoice 2026-01"), None);
     }
+
+    #[test]
+    fn strict_decoding_accepts_the_spec_examples() {
+        for text in [
+            "//S1/10/10201409/11/190512/20/1400.000-53/30/106017086/31/180508/32/7.7/40/2:10;0:30",
+            "//S1/10/10104/11/180228/30/395856455/31/180226180227/32/3.7:400.19;7.7:553.39;0:14/40/0:30",
+            "//S1/10/4031202511/11/180107/20/61257233.4/30/105493567/32/8:49.82/33/2.5:14.85/40/0:30",
+            r"//S1/10/X.66711\/8824/11/200712/20/MW-2020-04/30/107978798/32/2.5:117.22/40/3:5;1.5:20;1:40;0:60",
+        ] {
+            assert_eq!(
+                SwicoBillInformation::decode_strict(text),
+                Ok(SwicoBillInformation::decode_text(text).unwrap()),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_decoding_reports_the_offending_tag() {
+        let err = |text: &str| SwicoBillInformation::decode_strict(text).unwrap_err();
+
+        assert_eq!(err("S1/10/1"), SwicoError::NotSwico);
+        assert_eq!(err("//S1/1a/1"), SwicoError::InvalidTag("1a".into()));
+        assert_eq!(err("//S1/10/1/10/2"), SwicoError::TagOrder { tag: 10, previous: 10 });
+        assert_eq!(err("//S1/10//11/190512"), SwicoError::MissingValue { tag: 10 });
+        assert_eq!(err("//S1/10/1/11"), SwicoError::MissingValue { tag: 11 });
+        assert_eq!(err("//S1/11/190532"), SwicoError::InvalidDate { tag: 11, value: "190532".into() });
+        assert_eq!(err("//S1/11/1905120000"), SwicoError::InvalidDate { tag: 11, value: "1905120000".into() });
+        assert_eq!(
+            err("//S1/31/aaaaa\u{e9}aaaaa"),
+            SwicoError::InvalidDate { tag: 31, value: "aaaaa\u{e9}aaaaa".into() }
+        );
+        assert_eq!(err("//S1/30/CHE106017086"), SwicoError::NotNumeric { tag: 30, value: "CHE106017086".into() });
+        assert_eq!(
+            err("//S1/31/180227180226"),
+            SwicoError::VatPeriodReversed { start: date(2018, 2, 27), end: date(2018, 2, 26) }
+        );
+        assert_eq!(err("//S1/32/7,7"), SwicoError::InvalidNumber { tag: 32, value: "7,7".into() });
+        assert_eq!(err("//S1/32/-7.7"), SwicoError::InvalidNumber { tag: 32, value: "-7.7".into() });
+        assert_eq!(err("//S1/32/7.7:100;8"), SwicoError::InvalidNumber { tag: 32, value: "8".into() });
+        assert_eq!(err("//S1/40/101:10"), SwicoError::PercentageOutOfRange { tag: 40, value: 101.0 });
+        assert_eq!(err("//S1/40/2:x"), SwicoError::InvalidNumber { tag: 40, value: "x".into() });
+    }
+
+    #[test]
+    fn strict_decoding_ignores_unknown_tags() {
+        let info = SwicoBillInformation::decode_strict("//S1/10/1/15/whatever/20/2").unwrap();
+        assert_eq!(info.customer_reference.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn validate_checks_exclusive_and_ranged_fields() {
+        let vat_date = SwicoBillInformation {
+            vat_date: Some(date(2026, 1, 1)),
+            vat_end_date: Some(date(2026, 1, 31)),
+            ..Default::default()
+        };
+        assert_eq!(vat_date.validate(), Err(SwicoError::ConflictingVatDates));
+
+        let open_period = SwicoBillInformation { vat_start_date: Some(date(2026, 1, 1)), ..Default::default() };
+        assert_eq!(open_period.validate(), Err(SwicoError::IncompleteVatPeriod));
+
+        let rates = SwicoBillInformation {
+            vat_rate: Some(8.1),
+            vat_rate_details: vec![RateDetail { rate: 8.1, amount: 100.0 }],
+            ..Default::default()
+        };
+        assert_eq!(rates.validate(), Err(SwicoError::ConflictingVatRates));
+        assert_eq!(rates.validate().unwrap_err().tag(), Some(32));
+
+        let import = SwicoBillInformation {
+            vat_import_taxes: vec![RateDetail { rate: -1.0, amount: 10.0 }],
+            ..Default::default()
+        };
+        assert_eq!(import.validate(), Err(SwicoError::PercentageOutOfRange { tag: 33, value: -1.0 }));
+
+        let empty = SwicoBillInformation { invoice_number: Some(String::new()), ..Default::default() };
+        assert_eq!(empty.validate(), Err(SwicoError::MissingValue { tag: 10 }));
+    }
 }
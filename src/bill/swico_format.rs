@@ -0,0 +1,267 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Human-readable Swico billing information.
+//!
+//! The QR-bill shows `StrdBkgInf` as the raw S1 string, which means nothing
+//! to most payers. [`SwicoBillInformation::localized_lines`] spells the
+//! same data out in one of the four slip languages, for invoice documents,
+//! e-mails and previews:
+//!
+//! ```text
+//! Rechnungsnr. 10201409, Datum 12.05.2019, MWST 7.7 %, 2 % Skonto bis 22.05.2019, netto bis 11.06.2019
+//! ```
+
+use chrono::{Days, NaiveDate};
+
+use crate::bill::swico::s1_number;
+use crate::{Language, PaymentCondition, RateDetail, SwicoBillInformation};
+
+/// Wording per language; `{}` placeholders are filled in order.
+struct Wording {
+    invoice_number: &'static str,
+    invoice_date: &'static str,
+    customer_reference: &'static str,
+    vat_number: &'static str,
+    vat_date: &'static str,
+    vat_period: &'static str,
+    vat: &'static str,
+    rate_on: &'static str,
+    import_tax: &'static str,
+    discount_until: &'static str,
+    discount_within: &'static str,
+    net_until: &'static str,
+    net_within: &'static str,
+}
+
+const DE: Wording = Wording {
+    invoice_number: "Rechnungsnr. {}",
+    invoice_date: "Datum {}",
+    customer_reference: "Kundenreferenz {}",
+    vat_number: "UID {} MWST",
+    vat_date: "Leistungsdatum {}",
+    vat_period: "Leistungszeitraum {} - {}",
+    vat: "MWST {}",
+    rate_on: "{} % auf {}",
+    import_tax: "Einfuhrsteuer {}",
+    discount_until: "{} % Skonto bis {}",
+    discount_within: "{} % Skonto innert {} Tagen",
+    net_until: "netto bis {}",
+    net_within: "netto innert {} Tagen",
+};
+
+const FR: Wording = Wording {
+    invoice_number: "Facture n° {}",
+    invoice_date: "date {}",
+    customer_reference: "référence client {}",
+    vat_number: "IDE {} TVA",
+    vat_date: "date de la prestation {}",
+    vat_period: "période de prestation {} - {}",
+    vat: "TVA {}",
+    rate_on: "{} % sur {}",
+    import_tax: "impôt sur les importations {}",
+    discount_until: "escompte de {} % jusqu'au {}",
+    discount_within: "escompte de {} % dans les {} jours",
+    net_until: "net jusqu'au {}",
+    net_within: "net dans les {} jours",
+};
+
+const IT: Wording = Wording {
+    invoice_number: "Fattura n. {}",
+    invoice_date: "data {}",
+    customer_reference: "riferimento cliente {}",
+    vat_number: "IDI {} IVA",
+    vat_date: "data della prestazione {}",
+    vat_period: "periodo della prestazione {} - {}",
+    vat: "IVA {}",
+    rate_on: "{} % su {}",
+    import_tax: "imposta sull'importazione {}",
+    discount_until: "sconto del {} % fino al {}",
+    discount_within: "sconto del {} % entro {} giorni",
+    net_until: "netto fino al {}",
+    net_within: "netto entro {} giorni",
+};
+
+const EN: Wording = Wording {
+    invoice_number: "Invoice no. {}",
+    invoice_date: "date {}",
+    customer_reference: "customer reference {}",
+    vat_number: "UID {} VAT",
+    vat_date: "date of supply {}",
+    vat_period: "supply period {} - {}",
+    vat: "VAT {}",
+    rate_on: "{} % on {}",
+    import_tax: "import tax {}",
+    discount_until: "{} % discount until {}",
+    discount_within: "{} % discount within {} days",
+    net_until: "net by {}",
+    net_within: "net within {} days",
+};
+
+fn wording(language: Language) -> &'static Wording {
+    match language {
+        Language::De => &DE,
+        Language::Fr => &FR,
+        Language::It => &IT,
+        Language::En => &EN,
+    }
+}
+
+/// Fills the `{}` placeholders of `template` in order.
+fn fill(template: &str, values: &[&str]) -> String {
+    let mut parts = template.split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for (part, value) in parts.zip(values.iter().chain(std::iter::repeat(&""))) {
+        out.push_str(value);
+        out.push_str(part);
+    }
+    out
+}
+
+/// Swiss date format, the same in all four languages.
+fn date(date: NaiveDate) -> String {
+    date.format("%d.%m.%Y").to_string()
+}
+
+/// A 9-digit VAT number as UID (`CHE-106.017.086`); anything else as is.
+fn uid(vat_number: &str) -> String {
+    match vat_number.len() == 9 && vat_number.bytes().all(|b| b.is_ascii_digit()) {
+        true => format!("CHE-{}.{}.{}", &vat_number[..3], &vat_number[3..6], &vat_number[6..]),
+        false => vat_number.to_string(),
+    }
+}
+
+fn rate_list(w: &Wording, details: &[RateDetail]) -> String {
+    details
+        .iter()
+        .map(|d| fill(w.rate_on, &[&s1_number(d.rate), &format!("{:.2}", d.amount)]))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl SwicoBillInformation {
+    /// One line per piece of information, in tag order, worded for
+    /// `language`. Payment deadlines are given as dates when the invoice
+    /// date is known, the net one via [`Self::due_date`], and in days
+    /// otherwise.
+    ///
+    /// ```
+    /// use swiss_qrust::{Language, SwicoBillInformation};
+    ///
+    /// let info = SwicoBillInformation::decode_text("//S1/10/10201409/11/190512/32/7.7/40/2:10;0:30").unwrap();
+    /// assert_eq!(
+    ///     info.localized_lines(Language::Fr),
+    ///     ["Facture n° 10201409", "date 12.05.2019", "TVA 7.7 %", "escompte de 2 % jusqu'au 22.05.2019", "net jusqu'au 11.06.2019"]
+    /// );
+    /// ```
+    pub fn localized_lines(&self, language: Language) -> Vec<String> {
+        let w = wording(language);
+        let mut lines = Vec::new();
+
+        if let Some(number) = &self.invoice_number {
+            lines.push(fill(w.invoice_number, &[number]));
+        }
+        if let Some(invoice_date) = self.invoice_date {
+            lines.push(fill(w.invoice_date, &[&date(invoice_date)]));
+        }
+        if let Some(reference) = &self.customer_reference {
+            lines.push(fill(w.customer_reference, &[reference]));
+        }
+        if let Some(vat_number) = &self.vat_number {
+            lines.push(fill(w.vat_number, &[&uid(vat_number)]));
+        }
+        match (self.vat_date, self.vat_start_date, self.vat_end_date) {
+            (Some(vat_date), _, _) => lines.push(fill(w.vat_date, &[&date(vat_date)])),
+            (None, Some(start), Some(end)) => lines.push(fill(w.vat_period, &[&date(start), &date(end)])),
+            _ => {}
+        }
+        if let Some(rate) = self.vat_rate {
+            lines.push(fill(w.vat, &[&format!("{} %", s1_number(rate))]));
+        } else if !self.vat_rate_details.is_empty() {
+            lines.push(fill(w.vat, &[&rate_list(w, &self.vat_rate_details)]));
+        }
+        if !self.vat_import_taxes.is_empty() {
+            lines.push(fill(w.import_tax, &[&rate_list(w, &self.vat_import_taxes)]));
+        }
+        lines.extend(self.payment_conditions.iter().map(|c| self.describe_condition(w, c)));
+
+        lines
+    }
+
+    /// [`Self::localized_lines`] joined into one sentence-like line.
+    pub fn localized_text(&self, language: Language) -> String {
+        self.localized_lines(language).join(", ")
+    }
+
+    fn describe_condition(&self, w: &Wording, condition: &PaymentCondition) -> String {
+        let days = condition.days.to_string();
+        if condition.discount == 0.0 {
+            return match self.due_date() {
+                Some(due) => fill(w.net_until, &[&date(due)]),
+                None => fill(w.net_within, &[&days]),
+            };
+        }
+
+        let discount = s1_number(condition.discount);
+        match self.invoice_date.and_then(|d| d.checked_add_days(Days::new(condition.days.into()))) {
+            Some(until) => fill(w.discount_until, &[&discount, &date(until)]),
+            None => fill(w.discount_within, &[&discount, &days]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_EXAMPLE_1: &str =
+        "//S1/10/10201409/11/190512/20/1400.000-53/30/106017086/31/180508/32/7.7/40/2:10;0:30";
+
+    #[test]
+    fn describes_spec_example_in_german() {
+        let info = SwicoBillInformation::decode_text(SPEC_EXAMPLE_1).unwrap();
+        assert_eq!(
+            info.localized_text(Language::De),
+            "Rechnungsnr. 10201409, Datum 12.05.2019, Kundenreferenz 1400.000-53, UID CHE-106.017.086 MWST, \
+             Leistungsdatum 08.05.2018, MWST 7.7 %, 2 % Skonto bis 22.05.2019, netto bis 11.06.2019"
+        );
+    }
+
+    #[test]
+    fn describes_spec_example_in_all_languages() {
+        let info = SwicoBillInformation::decode_text(SPEC_EXAMPLE_1).unwrap();
+        let net: Vec<String> = [Language::De, Language::Fr, Language::It, Language::En]
+            .into_iter()
+            .map(|lang| info.localized_lines(lang).pop().unwrap())
+            .collect();
+        assert_eq!(net, ["netto bis 11.06.2019", "net jusqu'au 11.06.2019", "netto fino al 11.06.2019", "net by 11.06.2019"]);
+    }
+
+    #[test]
+    fn describes_rate_details_and_period() {
+        let info = SwicoBillInformation::decode_text(
+            "//S1/10/10104/11/180228/30/395856455/31/180226180227/32/3.7:400.19;7.7:553.39;0:14/40/0:30",
+        )
+        .unwrap();
+        let lines = info.localized_lines(Language::En);
+        assert_eq!(lines[3], "supply period 26.02.2018 - 27.02.2018");
+        assert_eq!(lines[4], "VAT 3.7 % on 400.19, 7.7 % on 553.39, 0 % on 14.00");
+    }
+
+    #[test]
+    fn falls_back_to_days_without_invoice_date() {
+        let info = SwicoBillInformation::decode_text("//S1/33/2.5:14.85/40/3:5;0:60").unwrap();
+        assert_eq!(
+            info.localized_lines(Language::It),
+            ["imposta sull'importazione 2.5 % su 14.85", "sconto del 3 % entro 5 giorni", "netto entro 60 giorni"]
+        );
+    }
+
+    #[test]
+    fn empty_information_has_no_lines() {
+        assert!(SwicoBillInformation::default().localized_lines(Language::De).is_empty());
+    }
+}
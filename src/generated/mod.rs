@@ -9,8 +9,11 @@ pub use shapes::*;
 
 // Generated by build.rs into OUT_DIR (Cargo's build-script scratch space),
 // not checked into git - regenerated fresh on every build from
-// assets/data/countries.json and assets/svg/*.svg.
+// assets/data/countries.json, assets/data/postal_codes.csv, the bank master
+// named by SWISS_QRUST_BANK_MASTER and assets/svg/*.svg.
 include!(concat!(env!("OUT_DIR"), "/countries.rs"));
+include!(concat!(env!("OUT_DIR"), "/postal_codes.rs"));
+include!(concat!(env!("OUT_DIR"), "/bank_master.rs"));
 include!(concat!(env!("OUT_DIR"), "/cross.rs"));
 include!(concat!(env!("OUT_DIR"), "/corner_marks_amount.rs"));
 include!(concat!(env!("OUT_DIR"), "/corner_marks_payable_by.rs"));
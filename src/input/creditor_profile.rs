@@ -0,0 +1,219 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! The creditor side of a bill - account, address and the texts that are
+//! the same on every bill - kept apart from the per-debtor data so it only
+//! has to be written down once.
+//!
+//! A bill file refers to its profile with `profile = "creditor.toml"` and
+//! only has to contain what differs:
+//!
+//! ```toml
+//! profile = "creditor.toml"
+//! amount = "250.00"
+//!
+//! [debtor_address]
+//! name = "Pia Rutschmann"
+//! # ...
+//!
+//! [variables]
+//! invoice_number = "2026-17"
+//! ```
+//!
+//! [`parse_bill_file`] merges the two: everything the bill sets wins, and
+//! tables (`creditor_address`, `variables`) are merged key by key, so a bill
+//! can override a single field of the creditor address.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::address::InputAddress;
+use crate::{parse_bill_data, InputBill, ParseBillDataError};
+
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("cannot read creditor profile {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid creditor profile {path}: {source}")]
+    Parse {
+        path: String,
+        #[source]
+        source: toml::de::Error,
+    },
+}
+
+fn default_currency() -> String {
+    "CHF".to_string()
+}
+
+/// Everything a creditor puts on each of their bills.
+///
+/// `unstructured_message` and `bill_information` may contain `{name}`
+/// placeholders, filled from the bill's `variables` (defaults can be given
+/// here) or, in a CSV import, the row's columns (see [`crate::import_csv`]).
+///
+/// ```
+/// use swiss_qrust::CreditorProfile;
+///
+/// let profile: CreditorProfile = toml::from_str(r#"
+///     iban = "CH44 3199 9123 0008 8901 2"
+///     unstructured_message = "Membership fee {Year}"
+///
+///     [creditor_address]
+///     name = "Turnverein Biel"
+///     plz = "2501"
+///     city = "Biel"
+///     country = "CH"
+/// "#)?;
+/// assert_eq!(profile.currency, "CHF");
+/// # Ok::<(), toml::de::Error>(())
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreditorProfile {
+    pub iban: String,
+    pub creditor_address: InputAddress,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    pub unstructured_message: Option<String>,
+    pub bill_information: Option<String>,
+    pub alternative_schemes: Option<[Option<String>; 2]>,
+    /// See [`crate::InputBill::sanitize`].
+    pub sanitize: Option<bool>,
+    /// See [`crate::InputBill::resolve_countries`].
+    pub resolve_countries: Option<bool>,
+    /// Default placeholder values; a bill's own `variables` take precedence.
+    pub variables: Option<BTreeMap<String, String>>,
+}
+
+impl CreditorProfile {
+    /// Reads a profile from a TOML file.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let content =
+            std::fs::read_to_string(path).map_err(|source| ProfileError::Io { path: display.clone(), source })?;
+        toml::from_str(&content).map_err(|source| ProfileError::Parse { path: display, source })
+    }
+
+    /// Fills in everything `bill` (a bill document as JSON value) doesn't
+    /// set itself.
+    pub fn merge_into(&self, bill: &mut Value) {
+        let profile = serde_json::to_value(self).expect("profile serializes to JSON");
+        merge(bill, profile);
+    }
+}
+
+/// Adds the entries of `defaults` missing (or null) in `target`, recursing
+/// into tables present in both.
+fn merge(target: &mut Value, defaults: Value) {
+    let (Value::Object(target), Value::Object(defaults)) = (target, defaults) else {
+        return;
+    };
+    for (key, default) in defaults {
+        if default.is_null() {
+            continue;
+        }
+        match target.get_mut(&key) {
+            Some(existing) if existing.is_object() && default.is_object() => merge(existing, default),
+            Some(existing) if !existing.is_null() => {}
+            _ => {
+                target.insert(key, default);
+            }
+        }
+    }
+}
+
+/// Parses a bill like [`parse_bill_data`], merging in the
+/// [`CreditorProfile`] named by its `profile` field first. The profile path
+/// is resolved relative to `base_dir`, usually the bill file's directory.
+///
+/// Bills without a profile are parsed exactly as by [`parse_bill_data`].
+pub fn parse_bill_file(content: &str, extension: &str, base_dir: &Path) -> Result<InputBill, ParseBillDataError> {
+    let mut value: Value = match extension {
+        "toml" => toml::from_str(content)?,
+        "json" => serde_json::from_str(content)?,
+        other => return Err(ParseBillDataError::UnsupportedFormat(other.to_string())),
+    };
+    let Some(profile) = value.get("profile").and_then(Value::as_str) else {
+        // Keeps the parser's error locations.
+        return parse_bill_data(content, extension);
+    };
+
+    CreditorProfile::from_path(base_dir.join(profile))?.merge_into(&mut value);
+    serde_json::from_value(value).map_err(ParseBillDataError::Merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BillData;
+
+    fn bill_with_profile(test: &str, bill: &str) -> Result<InputBill, ParseBillDataError> {
+        let dir = std::env::temp_dir().join(format!("swiss_qrust_profile_{}_{test}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("creditor.toml"),
+            r#"
+            iban = "CH93 0076 2011 6238 5295 7"
+            unstructured_message = "Invoice {invoice_number} of {invoice_date}"
+            variables = { invoice_date = "01.10.2026" }
+
+            [creditor_address]
+            name = "Robert Schneider AG"
+            street = "Rue du Lac"
+            house_num = "1268"
+            plz = "2501"
+            city = "Biel"
+            country = "CH"
+            "#,
+        )
+        .unwrap();
+        parse_bill_file(bill, "toml", &dir)
+    }
+
+    #[test]
+    fn bill_overrides_are_merged_on_top_of_the_profile() {
+        let input = bill_with_profile(
+            "overrides",
+            r#"
+            profile = "creditor.toml"
+            amount = "250.00"
+            creditor_address = { name = "Robert Schneider SA" }
+            variables = { invoice_number = "2026-17" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(input.creditor_address.name, "Robert Schneider SA");
+        assert_eq!(input.creditor_address.city, "Biel");
+        assert_eq!(input.currency, "CHF");
+
+        let bill = BillData::try_from(input).unwrap();
+        assert_eq!(bill.unstructured_message.as_deref(), Some("Invoice 2026-17 of 01.10.2026"));
+        assert_eq!(bill.amount.as_deref(), Some("250.00"));
+    }
+
+    #[test]
+    fn missing_variables_and_profiles_are_errors() {
+        let input = bill_with_profile("variables", "profile = \"creditor.toml\"").unwrap();
+        assert!(matches!(
+            BillData::try_from(input).unwrap_err().kind(),
+            crate::BillError::Template(crate::TemplateError::UnknownVariable(v)) if v == "invoice_number"
+        ));
+
+        assert!(matches!(
+            bill_with_profile("missing", "profile = \"missing.toml\""),
+            Err(ParseBillDataError::Profile(ProfileError::Io { .. }))
+        ));
+    }
+}
@@ -0,0 +1,438 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Mass billing from a spreadsheet.
+//!
+//! [`import_csv`] combines a [`CreditorProfile`] with one CSV row per
+//! debtor. A [`CsvMapping`] tells which column holds which debtor address
+//! field, the amount and the reference; QR references can also be built
+//! from a member number column. Problems with the file as a whole (unknown
+//! columns, bad templates) fail the import, problems with a single row are
+//! reported for that row with its line number:
+//!
+//! ```toml
+//! # mapping.toml
+//! amount = "Beitrag"
+//! member_number = "Mitglied-Nr"
+//! reference_template = "{member:10}"
+//!
+//! [debtor]
+//! name = "Name"
+//! street = "Strasse"
+//! house_num = "Nr"
+//! plz = "PLZ"
+//! city = "Ort"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::address::InputAddress;
+use crate::support::bank_master::split_csv_line;
+use crate::{
+    fill_template, BillData, BillError, CreditorProfile, InputBill, QrReferenceTemplate, ReferenceTemplateError,
+    TemplateError,
+};
+
+/// Name of the template field the member number is put into.
+pub const MEMBER_NUMBER_FIELD: &str = "member";
+/// Used when a member number column is mapped without a template: the
+/// number, zero-padded to the full reference.
+pub const DEFAULT_MEMBER_REFERENCE_TEMPLATE: &str = "{member:26}";
+
+/// Columns holding the debtor address. Rows where all of them are empty
+/// get no debtor.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AddressColumns {
+    pub name: String,
+    pub street: Option<String>,
+    pub house_num: Option<String>,
+    pub plz: String,
+    pub city: String,
+    pub country: Option<String>,
+}
+
+/// Binds CSV columns (by header name) to bill fields.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CsvMapping {
+    /// Detected from the header (`;` if it contains one, `,` otherwise)
+    /// when not set.
+    pub delimiter: Option<char>,
+    pub debtor: Option<AddressColumns>,
+    /// Country for debtors without a country column or value.
+    pub default_country: Option<String>,
+    pub amount: Option<String>,
+    /// Column with a complete QR or creditor reference.
+    pub reference: Option<String>,
+    /// Column with a member (or customer) number to build a QR reference
+    /// from, see `reference_template`.
+    pub member_number: Option<String>,
+    /// [`QrReferenceTemplate`] with a single field named `member`, e.g.
+    /// `{besr:6}{member:20}` written with the BESR-ID digits in place of
+    /// `{besr:6}`. Defaults to [`DEFAULT_MEMBER_REFERENCE_TEMPLATE`].
+    pub reference_template: Option<String>,
+    /// Column with a per-row message; rows where it is empty use the
+    /// profile's `unstructured_message`.
+    pub message: Option<String>,
+}
+
+/// Errors that make the whole file unusable.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CsvImportError {
+    #[error("CSV file has no header row")]
+    MissingHeader,
+    #[error("column '{0}' not found in the CSV header")]
+    UnknownColumn(String),
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+    #[error("map either a reference or a member number column, not both")]
+    ConflictingReference,
+    #[error("reference template must have exactly one field, named '{MEMBER_NUMBER_FIELD}'")]
+    InvalidMemberTemplate,
+    #[error(transparent)]
+    ReferenceTemplate(#[from] ReferenceTemplateError),
+}
+
+/// Why a single row didn't produce a bill.
+#[derive(Debug, Error)]
+pub enum CsvRowError {
+    #[error("expected {expected} fields, found {actual}")]
+    FieldCount { expected: usize, actual: usize },
+    #[error("member number: {0}")]
+    Reference(ReferenceTemplateError),
+    #[error(transparent)]
+    Bill(#[from] BillError),
+}
+
+/// The outcome for one data row.
+#[derive(Debug)]
+pub struct CsvRow {
+    /// 1-based line in the CSV file where the row starts (the header is
+    /// line 1).
+    pub line: usize,
+    pub bill: Result<BillData, CsvRowError>,
+}
+
+/// Resolved column indices.
+struct Columns {
+    debtor: Option<[Option<usize>; 6]>,
+    amount: Option<usize>,
+    reference: Option<usize>,
+    member_number: Option<usize>,
+    message: Option<usize>,
+}
+
+/// Builds one bill per data row of `content`.
+///
+/// Empty lines are skipped. `{Column}` placeholders in the profile's
+/// `unstructured_message` and `bill_information` are replaced with the
+/// row's value of that column before validation, so the 140-character
+/// budget applies to the actual text.
+///
+/// ```
+/// use swiss_qrust::{import_csv, CreditorProfile, CsvMapping};
+///
+/// let profile: CreditorProfile = toml::from_str(r#"
+///     iban = "CH44 3199 9123 0008 8901 2"
+///     unstructured_message = "Jahresbeitrag {Jahr}"
+///     [creditor_address]
+///     name = "Turnverein Biel"
+///     plz = "2501"
+///     city = "Biel"
+///     country = "CH"
+/// "#)?;
+/// let mapping: CsvMapping = toml::from_str(r#"
+///     amount = "Betrag"
+///     member_number = "Nr"
+///     default_country = "CH"
+///     [debtor]
+///     name = "Name"
+///     plz = "PLZ"
+///     city = "Ort"
+/// "#)?;
+/// let csv = "Nr;Name;PLZ;Ort;Betrag;Jahr\n\
+///            4711;Pia Rutschmann;9400;Rorschach;80.00;2026\n\
+///            4712;Max Muster;8000;Zürich;abc;2026\n";
+///
+/// let rows = import_csv(csv, &profile, &mapping)?;
+/// let bill = rows[0].bill.as_ref().unwrap();
+/// assert_eq!(bill.unstructured_message.as_deref(), Some("Jahresbeitrag 2026"));
+/// assert_eq!(bill.reference_type.to_string(), "QRR: 000000000000000000000047119");
+/// assert_eq!(rows[1].line, 3);
+/// assert!(rows[1].bill.is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn import_csv(
+    content: &str,
+    profile: &CreditorProfile,
+    mapping: &CsvMapping,
+) -> Result<Vec<CsvRow>, CsvImportError> {
+    let mut records = records(content.trim_start_matches('\u{feff}')).into_iter();
+    let (_, header) = records.next().ok_or(CsvImportError::MissingHeader)?;
+    let delimiter = mapping.delimiter.unwrap_or(if header.contains(';') { ';' } else { ',' });
+    let header: Vec<String> = split_csv_line(&header, delimiter).iter().map(|h| h.trim().to_string()).collect();
+
+    let columns = resolve_columns(&header, mapping)?;
+    for text in [&profile.unstructured_message, &profile.bill_information].into_iter().flatten() {
+        check_placeholders(text, &header)?;
+    }
+    let member_template = match columns.member_number {
+        Some(_) => Some(member_template(mapping.reference_template.as_deref())?),
+        None => None,
+    };
+
+    Ok(records
+        .map(|(line, text)| {
+            let values = split_csv_line(&text, delimiter);
+            let bill = row_to_bill(&header, &values, &columns, profile, mapping, member_template.as_ref());
+            CsvRow { line, bill }
+        })
+        .collect())
+}
+
+/// Splits `content` into records with the line each starts on, skipping
+/// blank lines. A quoted cell may span lines; its line breaks are kept.
+fn records(content: &str) -> Vec<(usize, String)> {
+    let mut records = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    let mut quoted = false;
+    for (i, line) in content.lines().enumerate() {
+        match &mut current {
+            Some((_, record)) => {
+                record.push('\n');
+                record.push_str(line);
+            }
+            None => current = Some((i + 1, line.to_string())),
+        }
+        // `""` inside a quoted cell toggles twice, so only the parity counts.
+        quoted ^= line.matches('"').count() % 2 == 1;
+        if !quoted {
+            records.extend(current.take().filter(|(_, record)| !record.trim().is_empty()));
+        }
+    }
+    // An unclosed quote runs to the end of the file.
+    records.extend(current);
+    records
+}
+
+fn resolve_columns(header: &[String], mapping: &CsvMapping) -> Result<Columns, CsvImportError> {
+    let find = |name: &Option<String>| -> Result<Option<usize>, CsvImportError> {
+        name.as_ref()
+            .map(|name| {
+                header
+                    .iter()
+                    .position(|h| h == name.trim())
+                    .ok_or_else(|| CsvImportError::UnknownColumn(name.clone()))
+            })
+            .transpose()
+    };
+
+    if mapping.reference.is_some() && mapping.member_number.is_some() {
+        return Err(CsvImportError::ConflictingReference);
+    }
+
+    let debtor = match &mapping.debtor {
+        Some(d) => Some([
+            find(&Some(d.name.clone()))?,
+            find(&d.street)?,
+            find(&d.house_num)?,
+            find(&Some(d.plz.clone()))?,
+            find(&Some(d.city.clone()))?,
+            find(&d.country)?,
+        ]),
+        None => None,
+    };
+
+    Ok(Columns {
+        debtor,
+        amount: find(&mapping.amount)?,
+        reference: find(&mapping.reference)?,
+        member_number: find(&mapping.member_number)?,
+        message: find(&mapping.message)?,
+    })
+}
+
+fn member_template(template: Option<&str>) -> Result<QrReferenceTemplate, CsvImportError> {
+    let template = QrReferenceTemplate::parse(template.unwrap_or(DEFAULT_MEMBER_REFERENCE_TEMPLATE))?;
+    match template.field_names().collect::<Vec<_>>()[..] {
+        [MEMBER_NUMBER_FIELD] => Ok(template),
+        _ => Err(CsvImportError::InvalidMemberTemplate),
+    }
+}
+
+/// Checks that every `{name}` in `text` is a column of `header`.
+fn check_placeholders(text: &str, header: &[String]) -> Result<(), TemplateError> {
+    fill_template(text, |name| header.iter().any(|h| h == name).then_some("")).map(|_| ())
+}
+
+fn row_to_bill(
+    header: &[String],
+    values: &[String],
+    columns: &Columns,
+    profile: &CreditorProfile,
+    mapping: &CsvMapping,
+    member_template: Option<&QrReferenceTemplate>,
+) -> Result<BillData, CsvRowError> {
+    // Trailing empty fields (spreadsheets like to add a delimiter or two)
+    // don't count.
+    let used = values.iter().rposition(|v| !v.trim().is_empty()).map_or(0, |i| i + 1);
+    if values.len() < header.len() || used > header.len() {
+        return Err(CsvRowError::FieldCount { expected: header.len(), actual: values.len() });
+    }
+
+    let cell = |col: Option<usize>| col.map(|i| values[i].trim()).filter(|v| !v.is_empty());
+    let column_value = |name: &str| header.iter().position(|h| h == name).map(|i| values[i].trim());
+    // Placeholders were checked against the header up front.
+    let fill = |text: &Option<String>| {
+        text.as_deref().map(|t| fill_template(t, column_value).unwrap_or_else(|_| t.to_string()))
+    };
+
+    let debtor_address = columns.debtor.and_then(|[name, street, house_num, plz, city, country]| {
+        if [name, street, house_num, plz, city, country].into_iter().all(|c| cell(c).is_none()) {
+            return None;
+        }
+        Some(InputAddress {
+            name: cell(name).unwrap_or_default().to_string(),
+            street: cell(street).map(str::to_string),
+            house_num: cell(house_num).map(str::to_string),
+            plz: cell(plz).unwrap_or_default().to_string(),
+            city: cell(city).unwrap_or_default().to_string(),
+            country: cell(country).or(mapping.default_country.as_deref()).unwrap_or_default().to_string(),
+        })
+    });
+
+    let reference = match (cell(columns.member_number), member_template) {
+        (Some(member), Some(template)) => Some(
+            template.build(&[(MEMBER_NUMBER_FIELD, member)]).map_err(CsvRowError::Reference)?,
+        ),
+        _ => cell(columns.reference).map(str::to_string),
+    };
+
+    let input = InputBill {
+        iban: profile.iban.clone(),
+        creditor_address: profile.creditor_address.clone(),
+        debtor_address,
+        currency: profile.currency.clone(),
+        amount: cell(columns.amount).map(str::to_string),
+        reference,
+        unstructured_message: cell(columns.message).map(str::to_string).or_else(|| fill(&profile.unstructured_message)),
+        bill_information: fill(&profile.bill_information),
+        swico_bill_information: None,
+        alternative_schemes: profile.alternative_schemes.clone(),
+        sanitize: profile.sanitize,
+        resolve_countries: profile.resolve_countries,
+        notification: None,
+        profile: None,
+        variables: None,
+    };
+    Ok(BillData::try_from(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILE: &str = r#"
+        iban = "CH44 3199 9123 0008 8901 2"
+        unstructured_message = "Beitrag {Jahr} für {Name}"
+
+        [creditor_address]
+        name = "Turnverein Biel"
+        plz = "2501"
+        city = "Biel"
+        country = "CH"
+    "#;
+
+    const MAPPING: &str = r#"
+        amount = "Betrag"
+        member_number = "Mitglied-Nr"
+        reference_template = "123456{member:10}"
+        default_country = "CH"
+
+        [debtor]
+        name = "Name"
+        street = "Strasse"
+        house_num = "Nr"
+        plz = "PLZ"
+        city = "Ort"
+        country = "Land"
+    "#;
+
+    const CSV: &str = "Mitglied-Nr;Name;Strasse;Nr;PLZ;Ort;Land;Betrag;Jahr\n\
+                       4711;Pia Rutschmann;Marktgasse;28;9400;Rorschach;;80.00;2026\n\
+                       \n\
+                       \"4712\";\"Muster; Max\";;;8000;Zürich;CH;120.00;2026;\n\
+                       4713;Anna Beispiel;;;1000;Lausanne;CH;12,50;2026\n\
+                       12345678901;Zu Lang;;;3000;Bern;CH;50.00;2026\n\
+                       4714;Kurz\n";
+
+    fn import(csv: &str, mapping: &str) -> Result<Vec<CsvRow>, CsvImportError> {
+        let profile: CreditorProfile = toml::from_str(PROFILE).unwrap();
+        let mapping: CsvMapping = toml::from_str(mapping).unwrap();
+        import_csv(csv, &profile, &mapping)
+    }
+
+    #[test]
+    fn builds_one_bill_per_row() {
+        let rows = import(CSV, MAPPING).unwrap();
+        assert_eq!(rows.iter().map(|r| r.line).collect::<Vec<_>>(), [2, 4, 5, 6, 7]);
+
+        let first = rows[0].bill.as_ref().unwrap();
+        assert_eq!(first.amount.as_deref(), Some("80.00"));
+        assert_eq!(first.unstructured_message.as_deref(), Some("Beitrag 2026 für Pia Rutschmann"));
+        assert_eq!(first.debtor_address.as_ref().unwrap().country.to_string(), "CH");
+        assert!(first.reference_type.to_string().starts_with("QRR: 0000000000123456000000471"));
+
+        let quoted = rows[1].bill.as_ref().unwrap();
+        assert_eq!(quoted.debtor_address.as_ref().unwrap().name, "Muster; Max");
+    }
+
+    #[test]
+    fn reports_row_errors_with_line_numbers() {
+        let rows = import(CSV, MAPPING).unwrap();
+        assert!(matches!(&rows[2].bill, Err(CsvRowError::Bill(err)) if matches!(err.kind(), BillError::InvalidAmount)));
+        assert!(matches!(
+            rows[3].bill,
+            Err(CsvRowError::Reference(ReferenceTemplateError::FieldTooLong { width: 10, .. }))
+        ));
+        assert!(matches!(rows[4].bill, Err(CsvRowError::FieldCount { expected: 9, actual: 2 })));
+        assert_eq!(rows[4].line, 7);
+    }
+
+    #[test]
+    fn quoted_cells_may_span_lines() {
+        let csv = "Mitglied-Nr;Name;Strasse;Nr;PLZ;Ort;Land;Betrag;Jahr;Bemerkung\n\
+                   4711;Pia Rutschmann;;;9400;Rorschach;CH;80.00;2026;\"zahlt\n\"\"bar\"\";\n\n\
+                   am Schalter\"\n\
+                   4712;Max Muster;;;8000;Zürich;CH;120.00;2026;\n";
+        let rows = import(csv, MAPPING).unwrap();
+
+        assert_eq!(rows.iter().map(|r| r.line).collect::<Vec<_>>(), [2, 6]);
+        assert_eq!(rows[0].bill.as_ref().unwrap().amount.as_deref(), Some("80.00"));
+        assert_eq!(rows[1].bill.as_ref().unwrap().debtor_address.as_ref().unwrap().city, "Zürich");
+        assert_eq!(records("a;\"b\r\nc\"\r\n\r\nd\n"), [(1, "a;\"b\nc\"".to_string()), (4, "d".to_string())]);
+    }
+
+    #[test]
+    fn rejects_unusable_mappings() {
+        let err = |mapping: &str| import(CSV, mapping).unwrap_err();
+
+        assert_eq!(err(&MAPPING.replace("\"Betrag\"", "\"Preis\"")), CsvImportError::UnknownColumn("Preis".into()));
+        assert_eq!(
+            err(&MAPPING.replace("123456{member:10}", "{besr:6}{member:10}")),
+            CsvImportError::InvalidMemberTemplate
+        );
+        assert_eq!(
+            err(&format!("reference = \"Betrag\"\n{MAPPING}")),
+            CsvImportError::ConflictingReference
+        );
+        assert_eq!(
+            import(&CSV.replace(";Jahr", ";Year"), MAPPING).unwrap_err(),
+            CsvImportError::Template(TemplateError::UnknownVariable("Jahr".into()))
+        );
+        assert_eq!(import("", MAPPING).unwrap_err(), CsvImportError::MissingHeader);
+    }
+}
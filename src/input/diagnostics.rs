@@ -0,0 +1,502 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Compiler-style diagnostics for bill files.
+//!
+//! [`crate::parse_bill_data`] followed by `BillData::try_from` tells you
+//! *what* is wrong and, with [`BillError::field`], in which field, but not
+//! *where* in the file. [`parse_and_validate`] does both steps and, on
+//! failure, looks up the position of that field in the source file:
+//!
+//! ```text
+//! error: Invalid city character length
+//!   --> bill.toml:12:8
+//!    |
+//! 12 | city = ""
+//!    |        ^^ debtor_address.city
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::{parse_bill_file, BillData, BillError, ParseBillDataError, SanitizedField};
+
+/// A parse or validation error, located in the source file where possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// File name as given by the caller, used for display only.
+    pub file: String,
+    pub message: String,
+    /// Dotted path of the offending input field, e.g.
+    /// `"creditor_address.city"` or `"alternative_schemes.1"`. `None` for
+    /// syntax errors and errors not tied to a field.
+    pub field: Option<String>,
+    pub location: Option<SourceLocation>,
+}
+
+/// A position in the source text. `line` and `column` are 1-based, `column`
+/// and `width` count characters (not bytes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
+    pub width: usize,
+    /// The full source line, for the snippet.
+    pub text: String,
+}
+
+/// Parses `content` (format chosen by `extension`, as in
+/// [`crate::parse_bill_data`]) and validates it into a [`BillData`], reporting
+/// failures as a [`Diagnostic`]. A creditor profile the bill refers to is
+/// resolved relative to `file`'s directory, see [`parse_bill_file`].
+///
+/// ```
+/// use swiss_qrust::diagnostics::parse_and_validate;
+///
+/// let toml = r#"
+/// iban = "CH93 0076 2011 6238 5295 7"
+/// currency = "CHF"
+///
+/// [creditor_address]
+/// name = "Robert Schneider AG"
+/// plz = "2501"
+/// city = ""
+/// country = "CH"
+/// "#;
+///
+/// let diagnostic = parse_and_validate(toml, "toml", "bill.toml").unwrap_err();
+/// assert_eq!(diagnostic.field.as_deref(), Some("creditor_address.city"));
+/// assert_eq!(diagnostic.location.unwrap().line, 8);
+/// ```
+pub fn parse_and_validate(content: &str, extension: &str, file: &str) -> Result<BillData, Diagnostic> {
+    parse_and_validate_with_warnings(content, extension, file).map(|(bill, _)| bill)
+}
+
+/// [`parse_and_validate`], also returning the text fields a bill with
+/// `sanitize = true` had changed to fit the SPS character set, e.g. to warn
+/// about them.
+///
+/// ```
+/// use swiss_qrust::diagnostics::parse_and_validate_with_warnings;
+///
+/// let toml = r#"
+/// iban = "CH93 0076 2011 6238 5295 7"
+/// currency = "CHF"
+/// sanitize = true
+///
+/// [creditor_address]
+/// name = "Иван Петров"
+/// plz = "2501"
+/// city = "Biel"
+/// country = "CH"
+/// "#;
+///
+/// let (bill, sanitized) = parse_and_validate_with_warnings(toml, "toml", "bill.toml").unwrap();
+/// assert_eq!(bill.creditor_address.name, "Ivan Petrov");
+/// assert_eq!(sanitized[0].field, "creditor_address.name");
+/// ```
+pub fn parse_and_validate_with_warnings(
+    content: &str,
+    extension: &str,
+    file: &str,
+) -> Result<(BillData, Vec<SanitizedField>), Diagnostic> {
+    let base_dir = Path::new(file).parent().unwrap_or(Path::new("."));
+    let mut input = parse_bill_file(content, extension, base_dir).map_err(|err| Diagnostic {
+        file: file.to_string(),
+        message: parse_error_message(&err),
+        field: None,
+        location: parse_error_span(content, &err).map(|span| SourceLocation::from_span(content, span)),
+    })?;
+
+    let diagnostic = |err: BillError| {
+        let field = err.field().map(str::to_string);
+        Diagnostic {
+            file: file.to_string(),
+            message: err.to_string(),
+            location: field
+                .as_deref()
+                .and_then(|f| locate_field(content, extension, f))
+                .map(|span| SourceLocation::from_span(content, span)),
+            field,
+        }
+    };
+
+    let sanitized = input.prepare().map_err(diagnostic)?;
+    let bill = BillData::from_prepared(input).map_err(diagnostic)?;
+    Ok((bill, sanitized))
+}
+
+impl SourceLocation {
+    fn from_span(content: &str, span: Range<usize>) -> Self {
+        let start = span.start.min(content.len());
+        let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = content[start..].find('\n').map_or(content.len(), |i| start + i);
+        let text = content[line_start..line_end].trim_end_matches('\r');
+
+        let end = span.end.clamp(start, line_start + text.len());
+        Self {
+            line: content[..start].matches('\n').count() + 1,
+            column: content[line_start..start].chars().count() + 1,
+            width: content[start..end].chars().count().max(1),
+            text: text.to_string(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "error: {}", self.message)?;
+        let Some(loc) = &self.location else {
+            return match &self.field {
+                Some(field) => write!(f, "  --> {} ({})", self.file, field),
+                None => write!(f, "  --> {}", self.file),
+            };
+        };
+
+        let gutter = " ".repeat(loc.line.to_string().len());
+        writeln!(f, "{gutter}--> {}:{}:{}", self.file, loc.line, loc.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", loc.line, loc.text)?;
+        write!(f, "{gutter} | {}{}", " ".repeat(loc.column - 1), "^".repeat(loc.width))?;
+        if let Some(field) = &self.field {
+            write!(f, " {field}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+fn parse_error_message(err: &ParseBillDataError) -> String {
+    match err {
+        ParseBillDataError::Toml(e) => format!("invalid TOML: {}", e.message().trim_end()),
+        ParseBillDataError::Json(e) => {
+            // serde_json appends " at line L column C" - the location is
+            // shown separately.
+            let msg = e.to_string();
+            let msg = msg.rsplit_once(" at line ").map_or(msg.as_str(), |(m, _)| m);
+            format!("invalid JSON: {msg}")
+        }
+        other => other.to_string(),
+    }
+}
+
+fn parse_error_span(content: &str, err: &ParseBillDataError) -> Option<Range<usize>> {
+    match err {
+        ParseBillDataError::Toml(e) => e.span(),
+        ParseBillDataError::Json(e) if e.line() > 0 => {
+            let line_start: usize = content
+                .split_inclusive('\n')
+                .take(e.line() - 1)
+                .map(str::len)
+                .sum();
+            let offset = content[line_start..]
+                .char_indices()
+                .nth(e.column().saturating_sub(1))
+                .map_or(content.len(), |(i, _)| line_start + i);
+            Some(offset..offset)
+        }
+        _ => None,
+    }
+}
+
+/// Byte span of the value at `field` (a dotted path) in `content`. Falls
+/// back to the closest enclosing value that exists, e.g. the
+/// `[debtor_address]` table if its `street` key is missing.
+fn locate_field(content: &str, extension: &str, field: &str) -> Option<Range<usize>> {
+    let path: Vec<&str> = field.split('.').collect();
+    (1..=path.len()).rev().find_map(|len| match extension {
+        "toml" => toml_span(content, &path[..len]),
+        "json" => JsonScanner { src: content.as_bytes(), pos: 0 }.find(&path[..len]),
+        _ => None,
+    })
+}
+
+fn toml_span(content: &str, path: &[&str]) -> Option<Range<usize>> {
+    use toml::de::{DeTable, DeValue};
+
+    let root = DeTable::parse(content).ok()?;
+    let (first, rest) = path.split_first()?;
+    let mut current = root.get_ref().get(*first)?;
+    for segment in rest {
+        current = match current.get_ref() {
+            DeValue::Table(table) => table.get(*segment)?,
+            DeValue::Array(array) => array.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(current.span())
+}
+
+/// Just enough of a JSON reader to find the byte span of a value by path.
+/// Only ever run on text `serde_json` has already accepted, so it bails out
+/// (returns `None`) on anything unexpected rather than reporting errors.
+struct JsonScanner<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn find(mut self, path: &[&str]) -> Option<Range<usize>> {
+        self.skip_ws();
+        for segment in path {
+            match self.peek()? {
+                b'{' => self.enter_object_member(segment)?,
+                b'[' => self.enter_array_element(segment.parse().ok()?)?,
+                _ => return None,
+            }
+        }
+        let start = self.pos;
+        self.skip_value()?;
+        Some(start..self.pos)
+    }
+
+    fn enter_object_member(&mut self, key: &str) -> Option<()> {
+        self.pos += 1; // '{'
+        loop {
+            self.skip_ws();
+            if self.peek()? == b'}' {
+                return None;
+            }
+            let name = self.string()?;
+            self.skip_ws();
+            self.expect(b':')?;
+            self.skip_ws();
+            if name == key.as_bytes() {
+                return Some(());
+            }
+            self.skip_value()?;
+            self.skip_ws();
+            if self.peek()? == b',' {
+                self.pos += 1;
+            }
+        }
+    }
+
+    fn enter_array_element(&mut self, index: usize) -> Option<()> {
+        self.pos += 1; // '['
+        for _ in 0..index {
+            self.skip_ws();
+            if self.peek()? == b']' {
+                return None;
+            }
+            self.skip_value()?;
+            self.skip_ws();
+            self.expect(b',')?;
+        }
+        self.skip_ws();
+        (self.peek()? != b']').then_some(())
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => self.string().map(|_| ()),
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.pos += 1;
+                loop {
+                    self.skip_ws();
+                    match self.peek()? {
+                        c if c == close => {
+                            self.pos += 1;
+                            return Some(());
+                        }
+                        b',' | b':' => self.pos += 1,
+                        _ => self.skip_value()?,
+                    }
+                }
+            }
+            _ => {
+                // number, true, false, null
+                while self.peek().is_some_and(|c| !matches!(c, b',' | b'}' | b']') && !c.is_ascii_whitespace()) {
+                    self.pos += 1;
+                }
+                Some(())
+            }
+        }
+    }
+
+    /// Returns the raw (still escaped) string contents.
+    fn string(&mut self) -> Option<&'a [u8]> {
+        self.expect(b'"')?;
+        let start = self.pos;
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        let raw = &self.src[start..self.pos];
+        self.pos += 1;
+        Some(raw)
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Option<()> {
+        (self.peek()? == c).then(|| self.pos += 1)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"iban = "CH93 0076 2011 6238 5295 7"
+currency = "CHF"
+
+[creditor_address]
+name = "Robert Schneider AG"
+plz = "2501"
+city = "Biel"
+country = "CH"
+
+[debtor_address]
+name = "Pia-Maria Rutschmann-Schnyder"
+plz = "9400"
+city = ""
+country = "CH"
+"#;
+
+    const JSON: &str = r#"{
+  "iban": "CH93 0076 2011 6238 5295 7",
+  "currency": "CHF",
+  "creditor_address": {
+    "name": "Robert Schneider AG", "plz": "2501", "city": "Biel", "country": "CH"
+  },
+  "alternative_schemes": [null, "eBill/B/🤣"]
+}"#;
+
+    #[test]
+    fn locates_debtor_city_in_toml() {
+        let diagnostic = parse_and_validate(TOML, "toml", "bill.toml").unwrap_err();
+
+        assert_eq!(diagnostic.field.as_deref(), Some("debtor_address.city"));
+        let loc = diagnostic.location.as_ref().unwrap();
+        assert_eq!((loc.line, loc.column, loc.width), (13, 8, 2));
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: Invalid city character length\n  \
+               --> bill.toml:13:8\n   \
+                |\n\
+             13 | city = \"\"\n   \
+                |        ^^ debtor_address.city"
+        );
+    }
+
+    #[test]
+    fn locates_debtor_errors_with_resolved_countries() {
+        let json = r#"{
+  "iban": "CH93 0076 2011 6238 5295 7",
+  "currency": "CHF",
+  "resolve_countries": true,
+  "creditor_address": { "name": "A", "plz": "2501", "city": "Biel", "country": "Switzerland" },
+  "debtor_address": { "name": "B", "plz": "8000", "city": "", "country": "CHE" }
+}"#;
+        let diagnostic = parse_and_validate(json, "json", "bill.json").unwrap_err();
+
+        assert_eq!(diagnostic.field.as_deref(), Some("debtor_address.city"));
+        assert_eq!(diagnostic.location.unwrap().line, 6);
+    }
+
+    #[test]
+    fn locates_charset_errors_in_the_field_that_has_them() {
+        let toml = TOML.replace("city = \"\"", "city = \"Rorschach\"\nstreet = \"Rue 🤣\"");
+        let diagnostic = parse_and_validate(&toml, "toml", "bill.toml").unwrap_err();
+
+        assert_eq!(diagnostic.field.as_deref(), Some("debtor_address.street"));
+        assert_eq!(diagnostic.location.unwrap().line, 14);
+    }
+
+    #[test]
+    fn locates_array_element_in_json() {
+        let diagnostic = parse_and_validate(JSON, "json", "bill.json").unwrap_err();
+
+        assert_eq!(diagnostic.field.as_deref(), Some("alternative_schemes.1"));
+        let loc = diagnostic.location.unwrap();
+        assert_eq!(loc.line, 7);
+        assert_eq!(loc.column, 33);
+        assert_eq!(loc.width, "\"eBill/B/🤣\"".chars().count());
+    }
+
+    #[test]
+    fn missing_field_falls_back_to_enclosing_value() {
+        let json = r#"{
+  "iban": "CH64 3196 1000 0044 2155 7",
+  "currency": "CHF",
+  "creditor_address": { "name": "A", "plz": "2501", "city": "Biel", "country": "CH" }
+}"#;
+        let diagnostic = parse_and_validate(json, "json", "bill.json").unwrap_err();
+
+        // A QR-IBAN without a reference: there is no "reference" key to
+        // point at, so the IBAN is blamed.
+        assert_eq!(diagnostic.field.as_deref(), Some("iban"));
+        assert_eq!(diagnostic.location.unwrap().line, 2);
+    }
+
+    #[test]
+    fn reports_toml_syntax_errors_with_position() {
+        let diagnostic = parse_and_validate("iban = \"CH93\ncurrency = 1", "toml", "bill.toml").unwrap_err();
+
+        assert!(diagnostic.message.starts_with("invalid TOML"));
+        assert_eq!(diagnostic.field, None);
+        assert_eq!(diagnostic.location.unwrap().line, 1);
+    }
+
+    #[test]
+    fn reports_json_syntax_errors_with_position() {
+        let diagnostic = parse_and_validate("{\n  \"iban\": \"CH93\",\n  oops\n}", "json", "bill.json").unwrap_err();
+
+        assert!(diagnostic.message.starts_with("invalid JSON"));
+        assert!(!diagnostic.message.contains(" at line "));
+        let loc = diagnostic.location.unwrap();
+        assert_eq!((loc.line, loc.column), (3, 3));
+    }
+
+    #[test]
+    fn points_at_templates_with_missing_variables() {
+        let toml = TOML
+            .replace("city = \"\"", "city = \"Rorschach\"")
+            .replace("currency = \"CHF\"\n", "currency = \"CHF\"\nunstructured_message = \"Invoice {number}\"\nvariables = { date = \"19.10.2026\" }\n");
+        let diagnostic = parse_and_validate(&toml, "toml", "bill.toml").unwrap_err();
+
+        assert_eq!(diagnostic.message, "no value for placeholder {number}");
+        assert_eq!(diagnostic.field.as_deref(), Some("unstructured_message"));
+        assert_eq!(diagnostic.location.unwrap().line, 3);
+    }
+
+    #[test]
+    fn returns_sanitized_fields_as_warnings() {
+        let toml = TOML.replace("city = \"\"", "city = \"Rorschach\"");
+        let (_, sanitized) = parse_and_validate_with_warnings(&toml, "toml", "bill.toml").unwrap();
+        assert!(sanitized.is_empty());
+
+        let toml = toml
+            .replace("currency = \"CHF\"\n", "currency = \"CHF\"\nsanitize = true\n")
+            .replace("Rorschach", "Rorschach\u{00A0}SG");
+        let (bill, sanitized) = parse_and_validate_with_warnings(&toml, "toml", "bill.toml").unwrap();
+        assert_eq!(bill.debtor_address.unwrap().city, "Rorschach SG");
+        assert_eq!(sanitized.len(), 1);
+        assert_eq!(sanitized[0].field, "debtor_address.city");
+        assert_eq!(sanitized[0].to_string(), "changed to 'Rorschach SG' to fit the SPS character set");
+    }
+
+    #[test]
+    fn valid_input_passes() {
+        let toml = TOML.replace("city = \"\"", "city = \"Rorschach\"");
+        assert!(parse_and_validate(&toml, "toml", "bill.toml").is_ok());
+    }
+}
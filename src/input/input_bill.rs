@@ -4,10 +4,13 @@
  * https://opensource.org/licenses/MIT
  */
 
+use std::collections::BTreeMap;
+use std::fmt;
 use std::str::FromStr;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use crate::address::InputAddress;
-use crate::{BillError, Currency};
+use crate::support::sanitize::{sanitize, Sanitized};
+use crate::{fill_template, BillError, Currency, TemplateError};
 
 /// Raw, unvalidated bill data as read from TOML/JSON (see [`crate::parse_bill_data`]).
 ///
@@ -16,7 +19,7 @@ use crate::{BillError, Currency};
 /// the QR-bill spec. Convert it to a [`crate::BillData`] via `TryFrom` to
 /// validate and get something renderable; that conversion is where IBAN,
 /// reference, and formatting rules are actually enforced.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InputBill {
     pub iban: String,
     pub creditor_address: InputAddress,
@@ -30,11 +33,153 @@ pub struct InputBill {
     /// text on conversion. Providing both is an error.
     pub swico_bill_information: Option<InputSwicoBillInformation>,
     pub alternative_schemes: Option<[Option<String>; 2]>,
+    /// Run [`InputBill::sanitize_text_fields`] before validation, so text
+    /// outside the SPS character set is transliterated or dropped instead
+    /// of rejected.
+    pub sanitize: Option<bool>,
+    /// Accept country names, alpha-3 and numeric codes in addresses (see
+    /// [`crate::Country::resolve`]) instead of only alpha-2 codes.
+    pub resolve_countries: Option<bool>,
+    /// Language code (`"de"`, `"fr"`, `"it"`, `"en"`) of a "do not use for
+    /// payment" notification, see [`crate::BillData::into_notification`].
+    pub notification: Option<String>,
+    /// Path of a [`crate::CreditorProfile`] the bill is based on, relative
+    /// to the bill file. Resolved by [`crate::parse_bill_file`].
+    pub profile: Option<String>,
+    /// Values for `{name}` placeholders in `unstructured_message` and
+    /// `bill_information`, see [`InputBill::apply_variables`].
+    pub variables: Option<BTreeMap<String, String>>,
+}
+
+/// A text field changed by [`InputBill::sanitize_text_fields`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizedField {
+    /// Dotted path of the field, e.g. `"debtor_address.name"`.
+    pub field: String,
+    pub result: Sanitized,
+}
+
+impl fmt::Display for SanitizedField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "changed to '{}' to fit the SPS character set", self.result.text)
+    }
+}
+
+impl InputBill {
+    /// Resolves `{name}` placeholders in `unstructured_message` and
+    /// `bill_information` from `variables` and unescapes `{{` / `}}`; a
+    /// placeholder without a value is an error.
+    ///
+    /// Only bills that opt in - with `variables` or a `profile` - are
+    /// templates. In other bills braces are ordinary text and are left
+    /// alone.
+    pub fn apply_variables(&mut self) -> Result<(), TemplateError> {
+        self.fill_variables().map_err(|(_, err)| err)
+    }
+
+    /// [`InputBill::apply_variables`], naming the field that failed.
+    fn fill_variables(&mut self) -> Result<(), (&'static str, TemplateError)> {
+        if self.variables.is_none() && self.profile.is_none() {
+            return Ok(());
+        }
+        let variables = self.variables.as_ref();
+        let lookup = |name: &str| variables.and_then(|v| v.get(name)).map(String::as_str);
+        for (field, text) in [
+            ("unstructured_message", &mut self.unstructured_message),
+            ("bill_information", &mut self.bill_information),
+        ] {
+            if let Some(text) = text {
+                *text = fill_template(text, lookup).map_err(|err| (field, err))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The steps `BillData::try_from` takes before validating: placeholders,
+    /// then `sanitize` and `resolve_countries` if requested. Returns the
+    /// fields `sanitize` changed. Not idempotent - resolving placeholders
+    /// unescapes braces.
+    pub(crate) fn prepare(&mut self) -> Result<Vec<SanitizedField>, BillError> {
+        self.fill_variables().map_err(|(field, err)| BillError::from(err).at(field))?;
+        let sanitized = match self.sanitize.unwrap_or(false) {
+            true => self.sanitize_text_fields(),
+            false => Vec::new(),
+        };
+        if self.resolve_countries.unwrap_or(false) {
+            self.resolve_address_countries();
+        }
+        Ok(sanitized)
+    }
+
+    /// Replaces country names, alpha-3 and numeric codes in both addresses
+    /// with alpha-2 codes, see [`InputAddress::resolve_country`].
+    pub fn resolve_address_countries(&mut self) {
+        self.creditor_address.resolve_country();
+        if let Some(debtor) = self.debtor_address.as_mut() {
+            debtor.resolve_country();
+        }
+    }
+
+    /// Applies [`sanitize`] to every free-text field (addresses, messages,
+    /// alternative schemes, Swico text fields) in place, returning the
+    /// fields that changed. Codes (IBAN, currency, reference, country) are
+    /// left alone - "fixing" those would only hide typos.
+    pub fn sanitize_text_fields(&mut self) -> Vec<SanitizedField> {
+        let mut changed = Vec::new();
+        let mut apply = |field: String, value: &mut String| {
+            let result = sanitize(value);
+            if result.is_changed() {
+                value.clone_from(&result.text);
+                changed.push(SanitizedField { field, result });
+            }
+        };
+
+        for (prefix, address) in [
+            ("creditor_address", Some(&mut self.creditor_address)),
+            ("debtor_address", self.debtor_address.as_mut()),
+        ] {
+            let Some(address) = address else { continue };
+            apply(format!("{prefix}.name"), &mut address.name);
+            if let Some(street) = address.street.as_mut() {
+                apply(format!("{prefix}.street"), street);
+            }
+            if let Some(house_num) = address.house_num.as_mut() {
+                apply(format!("{prefix}.house_num"), house_num);
+            }
+            apply(format!("{prefix}.plz"), &mut address.plz);
+            apply(format!("{prefix}.city"), &mut address.city);
+        }
+
+        if let Some(msg) = self.unstructured_message.as_mut() {
+            apply("unstructured_message".into(), msg);
+        }
+        if let Some(info) = self.bill_information.as_mut() {
+            apply("bill_information".into(), info);
+        }
+        if let Some(swico) = self.swico_bill_information.as_mut() {
+            for (name, value) in [
+                ("invoice_number", swico.invoice_number.as_mut()),
+                ("customer_reference", swico.customer_reference.as_mut()),
+                ("vat_number", swico.vat_number.as_mut()),
+            ] {
+                if let Some(value) = value {
+                    apply(format!("swico_bill_information.{name}"), value);
+                }
+            }
+        }
+        for (i, scheme) in self.alternative_schemes.iter_mut().flatten().enumerate() {
+            if let Some(scheme) = scheme {
+                apply(format!("alternative_schemes.{i}"), scheme);
+            }
+        }
+
+        changed
+    }
 }
 
 /// Dates are plain "YYYY-MM-DD" strings here (parsed in `TryFrom`) since
 /// `chrono` is used without its `serde` feature.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InputSwicoBillInformation {
     pub invoice_number: Option<String>,
     pub invoice_date: Option<String>,
@@ -49,13 +194,13 @@ pub struct InputSwicoBillInformation {
     pub payment_conditions: Option<Vec<InputPaymentCondition>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InputRateDetail {
     pub rate: f64,
     pub amount: f64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InputPaymentCondition {
     pub discount: f64,
     pub days: u32,
@@ -4,5 +4,14 @@
  * https://opensource.org/licenses/MIT
  */
 pub mod input_bill;
+pub mod diagnostics;
+pub mod schema;
+pub mod creditor_profile;
+pub mod csv_import;
+pub mod template;
 
-pub use input_bill::*;
\ No newline at end of file
+pub use input_bill::*;
+pub use schema::*;
+pub use creditor_profile::*;
+pub use csv_import::*;
+pub use template::*;
\ No newline at end of file
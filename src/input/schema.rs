@@ -0,0 +1,293 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! JSON Schema (draft 2020-12) for the [`InputBill`](crate::InputBill) file
+//! format, for editor autocompletion and pre-validation of hand-written bill
+//! files.
+//!
+//! The schema is assembled from the same constants the validation code uses
+//! (address length limits, [`AMOUNT_PATTERN`], [`Currency`], the generated
+//! [`Country`] enum), so it can't drift from what `BillData::try_from`
+//! actually enforces. It is deliberately a *pre*-check: checksums (IBAN,
+//! QR/SCOR reference) and cross-field rules (QR-IBAN needs a QR reference,
+//! the shared 140-character budget) can't be expressed in JSON Schema and
+//! are only caught on conversion.
+
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Country, Currency, ADDITIONAL_INFORMATION_MAX_LEN, ALTERNATIVE_PROCEDURE_MAX_LEN,
+    AMOUNT_PATTERN, CITY_MAX_LEN, HOUSE_NUM_MAX_LEN, NAME_MAX_LEN, PLZ_MAX_LEN, STREET_MAX_LEN,
+};
+
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Returns the JSON Schema describing an [`InputBill`](crate::InputBill)
+/// document.
+///
+/// ```
+/// let schema = swiss_qrust::input_bill_schema();
+/// assert_eq!(schema["title"], "InputBill");
+/// assert_eq!(schema["properties"]["currency"]["enum"][0], "CHF");
+/// ```
+pub fn input_bill_schema() -> Value {
+    json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": "InputBill",
+        "description": "Swiss QR-bill input data, as read by swiss_qrust::parse_bill_data.",
+        "type": "object",
+        "anyOf": [
+            { "required": ["iban", "creditor_address", "currency"] },
+            { "required": ["profile"] },
+        ],
+        // Free-form countries only with resolve_countries; alpha-2 codes otherwise.
+        "if": {
+            "required": ["resolve_countries"],
+            "properties": { "resolve_countries": { "const": true } },
+        },
+        "else": {
+            "properties": {
+                "creditor_address": { "properties": { "country": { "$ref": "#/$defs/CountryCode" } } },
+                "debtor_address": { "properties": { "country": { "$ref": "#/$defs/CountryCode" } } },
+            },
+        },
+        "additionalProperties": false,
+        "properties": {
+            "iban": {
+                "description": "IBAN or QR-IBAN of the creditor (CH or LI), with or without spaces.",
+                "type": "string",
+                "pattern": "^(CH|LI)[0-9]{2}( ?[0-9]){5}( ?[0-9A-Za-z]){12}$",
+            },
+            "creditor_address": { "$ref": "#/$defs/InputAddress" },
+            "debtor_address": nullable(json!({ "$ref": "#/$defs/InputAddress" })),
+            "currency": {
+                "type": "string",
+                "enum": Currency::iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+            },
+            "amount": nullable(json!({
+                "description": "Amount with two decimals, e.g. \"199.95\". Omit for a bill without amount.",
+                "type": "string",
+                "pattern": AMOUNT_PATTERN,
+            })),
+            "reference": nullable(json!({
+                "description": "QR reference (27 digits) or SCOR creditor reference (RF...). Omit for none.",
+                "type": "string",
+            })),
+            "unstructured_message": nullable(json!({
+                "type": "string",
+                "maxLength": ADDITIONAL_INFORMATION_MAX_LEN,
+            })),
+            "bill_information": nullable(json!({
+                "description": "Raw billing information, e.g. Swico S1 text. Mutually exclusive with swico_bill_information.",
+                "type": "string",
+                "maxLength": ADDITIONAL_INFORMATION_MAX_LEN,
+            })),
+            "swico_bill_information": nullable(json!({ "$ref": "#/$defs/InputSwicoBillInformation" })),
+            "alternative_schemes": nullable(json!({
+                "type": "array",
+                "minItems": 2,
+                "maxItems": 2,
+                "items": nullable(json!({
+                    "type": "string",
+                    "maxLength": ALTERNATIVE_PROCEDURE_MAX_LEN,
+                })),
+            })),
+            "sanitize": nullable(json!({
+                "description": "Transliterate or drop characters outside the SPS character set instead of rejecting them.",
+                "type": "boolean",
+            })),
+            "resolve_countries": nullable(json!({
+                "description": "Accept country names, alpha-3 and numeric codes in addresses instead of only alpha-2 codes.",
+                "type": "boolean",
+            })),
+            "notification": nullable(json!({
+                "description": "Issue a \"do not use for payment\" notification with amount 0.00; the value is the language of the notice.",
+                "type": "string",
+                "enum": ["de", "fr", "it", "en"],
+            })),
+            "profile": nullable(json!({
+                "description": "Creditor profile (TOML) to take the creditor, IBAN and default texts from, relative to this file. Fields set here take precedence.",
+                "type": "string",
+            })),
+            "variables": nullable(json!({
+                "description": "Values for {name} placeholders in unstructured_message and bill_information.",
+                "type": "object",
+                "additionalProperties": { "type": "string" },
+            })),
+        },
+        "$defs": {
+            "InputAddress": input_address_schema(),
+            "CountryCode": {
+                "description": "ISO 3166-1 alpha-2 country code.",
+                "type": "string",
+                "enum": Country::iter().map(|c| c.meta().alpha2).collect::<Vec<_>>(),
+            },
+            "InputSwicoBillInformation": input_swico_schema(),
+            "InputRateDetail": {
+                "type": "object",
+                "required": ["rate", "amount"],
+                "additionalProperties": false,
+                "properties": {
+                    "rate": { "type": "number" },
+                    "amount": { "type": "number" },
+                },
+            },
+            "InputPaymentCondition": {
+                "type": "object",
+                "required": ["discount", "days"],
+                "additionalProperties": false,
+                "properties": {
+                    "discount": { "type": "number" },
+                    "days": { "type": "integer", "minimum": 0 },
+                },
+            },
+        },
+    })
+}
+
+fn input_address_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["name", "plz", "city", "country"],
+        "additionalProperties": false,
+        "properties": {
+            "name": { "type": "string", "minLength": 1, "maxLength": NAME_MAX_LEN },
+            "street": nullable(json!({ "type": "string", "maxLength": STREET_MAX_LEN })),
+            "house_num": nullable(json!({ "type": "string", "maxLength": HOUSE_NUM_MAX_LEN })),
+            "plz": { "type": "string", "minLength": 1, "maxLength": PLZ_MAX_LEN },
+            "city": { "type": "string", "minLength": 1, "maxLength": CITY_MAX_LEN },
+            "country": {
+                "description": "ISO 3166-1 alpha-2 country code; with resolve_countries also a country name, alpha-3 or numeric code.",
+                "type": "string",
+            },
+        },
+    })
+}
+
+fn input_swico_schema() -> Value {
+    let date = || nullable(json!({ "type": "string", "format": "date" }));
+    let text = || nullable(json!({ "type": "string" }));
+    let list = |def: &str| {
+        nullable(json!({ "type": "array", "items": { "$ref": format!("#/$defs/{def}") } }))
+    };
+
+    json!({
+        "description": "Structured Swico S1 billing information, encoded to bill_information on conversion.",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "invoice_number": text(),
+            "invoice_date": date(),
+            "customer_reference": text(),
+            "vat_number": text(),
+            "vat_date": date(),
+            "vat_start_date": date(),
+            "vat_end_date": date(),
+            "vat_rate": nullable(json!({ "type": "number" })),
+            "vat_rate_details": list("InputRateDetail"),
+            "vat_import_taxes": list("InputRateDetail"),
+            "payment_conditions": list("InputPaymentCondition"),
+        },
+    })
+}
+
+/// Optional input fields may be omitted or explicitly `null`.
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::{self, Deserialize, Visitor};
+    use crate::{InputAddress, InputBill, InputPaymentCondition, InputRateDetail, InputSwicoBillInformation};
+
+    /// A deserializer that only records the field names a derived
+    /// `Deserialize` impl asks for, so the schema can be checked against the
+    /// actual Rust types rather than a hand-maintained list.
+    struct FieldNames<'a>(&'a mut Vec<&'static str>);
+
+    impl<'de> de::Deserializer<'de> for FieldNames<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            self.0.extend_from_slice(fields);
+            Err(de::Error::custom("fields recorded"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    fn fields_of<T: for<'de> Deserialize<'de>>() -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        let _ = T::deserialize(FieldNames(&mut fields));
+        fields.sort();
+        fields
+    }
+
+    fn properties_of(schema: &Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn schema_properties_match_input_types() {
+        let schema = input_bill_schema();
+        let defs = &schema["$defs"];
+
+        assert_eq!(properties_of(&schema), fields_of::<InputBill>());
+        assert_eq!(properties_of(&defs["InputAddress"]), fields_of::<InputAddress>());
+        assert_eq!(properties_of(&defs["InputSwicoBillInformation"]), fields_of::<InputSwicoBillInformation>());
+        assert_eq!(properties_of(&defs["InputRateDetail"]), fields_of::<InputRateDetail>());
+        assert_eq!(properties_of(&defs["InputPaymentCondition"]), fields_of::<InputPaymentCondition>());
+    }
+
+    #[test]
+    fn country_enum_is_generated_from_country_list() {
+        let schema = input_bill_schema();
+        let countries = schema["$defs"]["CountryCode"]["enum"].as_array().unwrap();
+
+        assert_eq!(countries.len(), Country::iter().count());
+        assert!(countries.contains(&json!("CH")));
+        assert!(countries.contains(&json!("LI")));
+    }
+
+    #[test]
+    fn country_names_are_only_allowed_with_resolve_countries() {
+        let schema = input_bill_schema();
+        let code = json!({ "$ref": "#/$defs/CountryCode" });
+
+        assert_eq!(schema["if"]["properties"]["resolve_countries"]["const"], true);
+        assert_eq!(schema["else"]["properties"]["creditor_address"]["properties"]["country"], code);
+        assert_eq!(schema["else"]["properties"]["debtor_address"]["properties"]["country"], code);
+    }
+
+    #[test]
+    fn amount_pattern_matches_validation_regex() {
+        let schema = input_bill_schema();
+        assert_eq!(schema["properties"]["amount"]["anyOf"][0]["pattern"], AMOUNT_PATTERN);
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! `{name}` placeholders in message texts, e.g.
+//! `Invoice {invoice_number} of {invoice_date}`.
+//!
+//! Used for the `variables` of a bill file (see [`crate::InputBill`]) and
+//! the columns of a CSV import ([`crate::import_csv`]). Placeholders are
+//! resolved before validation, so the SPS character set and the
+//! 140-character budget apply to the final text. `{{` and `}}` stand for
+//! literal braces. Bills without `variables` or a `profile` aren't
+//! templates; their braces are plain text.
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum TemplateError {
+    #[error("unclosed placeholder in '{0}'")]
+    Unclosed(String),
+    #[error("no value for placeholder {{{0}}}")]
+    UnknownVariable(String),
+}
+
+/// Replaces every `{name}` in `template` with `value(name)`.
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use swiss_qrust::{fill_template, TemplateError};
+///
+/// let variables = BTreeMap::from([("invoice_number", "2026-17"), ("invoice_date", "19.10.2026")]);
+/// let lookup = |name: &str| variables.get(name).copied();
+///
+/// let text = fill_template("Invoice {invoice_number} of {invoice_date}", lookup)?;
+/// assert_eq!(text, "Invoice 2026-17 of 19.10.2026");
+/// assert_eq!(fill_template("{{literal}}", lookup)?, "{literal}");
+/// assert_eq!(
+///     fill_template("Order {order}", lookup),
+///     Err(TemplateError::UnknownVariable("order".into()))
+/// );
+/// # Ok::<(), TemplateError>(())
+/// ```
+pub fn fill_template<'a>(
+    template: &str,
+    value: impl Fn(&str) -> Option<&'a str>,
+) -> Result<String, TemplateError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        // A lone `}` is kept as is.
+        if let Some(after) = tail.strip_prefix('}') {
+            out.push('}');
+            rest = after;
+            continue;
+        }
+
+        let end = tail.find('}').ok_or_else(|| TemplateError::Unclosed(template.to_string()))?;
+        let name = tail[1..end].trim();
+        out.push_str(value(name).ok_or_else(|| TemplateError::UnknownVariable(name.to_string()))?);
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_unclosed_placeholders() {
+        assert_eq!(
+            fill_template("Invoice {number", |_| Some("1")),
+            Err(TemplateError::Unclosed("Invoice {number".into()))
+        );
+    }
+}
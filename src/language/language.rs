@@ -1,4 +1,5 @@
 use std::fmt;
+use std::str::FromStr;
 use thiserror::Error;
 
 /// Language for the fixed labels printed on the slip ("Payable to", "Currency",
@@ -26,6 +27,33 @@ impl fmt::Display for Language {
     }
 }
 
+impl Language {
+    /// ISO 639-1 code, as accepted by [`Language::from_str`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::De => "de",
+            Language::Fr => "fr",
+            Language::It => "it",
+            Language::En => "en",
+        }
+    }
+}
+
+/// Parses an ISO 639-1 code (`"de"`, `"FR"`, ...).
+impl FromStr for Language {
+    type Err = LanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "de" => Ok(Language::De),
+            "fr" => Ok(Language::Fr),
+            "it" => Ok(Language::It),
+            "en" => Ok(Language::En),
+            _ => Err(LanguageError::UnknownLanguage),
+        }
+    }
+}
+
 
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -44,12 +72,18 @@ pub enum LabelKey {
     InFavourOf,
     BillingInformation,
     AlternativeProcedures,
+    /// Notice for notification bills, see [`crate::BillData::into_notification`].
+    DoNotUseForPayment,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
 pub enum LanguageError {
     #[error("No Label Found for key: {0:?}")]
     LabelNotFound(LabelKey),
+    /// The code isn't kept, so the error stays `Copy`; callers that show it
+    /// add the code themselves.
+    #[error("Unknown language (expected de, fr, it or en)")]
+    UnknownLanguage,
 }
 
 pub struct LabelEntry {
@@ -129,6 +163,11 @@ const LABELS: &[(LabelKey, Language, &str)] = &[
     (LabelKey::AlternativeProcedures, Language::It, "Procedure alternative"),
     (LabelKey::AlternativeProcedures, Language::En, "Alternative procedures"),
 
+    (LabelKey::DoNotUseForPayment, Language::De, "NICHT ZUR ZAHLUNG VERWENDEN"),
+    (LabelKey::DoNotUseForPayment, Language::Fr, "NE PAS UTILISER POUR LE PAIEMENT"),
+    (LabelKey::DoNotUseForPayment, Language::It, "NON UTILIZZARE PER IL PAGAMENTO"),
+    (LabelKey::DoNotUseForPayment, Language::En, "DO NOT USE FOR PAYMENT"),
+
 ];
 
 pub fn label(key: LabelKey, lang: Language) -> Result<&'static str, LanguageError> {
@@ -192,6 +231,14 @@ mod tests {
         assert_eq!(label!(PayableByNameAddress, Language::En), "Payable by (name/address)");
     }
 
+    #[test]
+    fn parses_language_codes() {
+        assert_eq!("fr".parse::<Language>(), Ok(Language::Fr));
+        assert_eq!(" EN ".parse::<Language>(), Ok(Language::En));
+        assert_eq!("rm".parse::<Language>(), Err(LanguageError::UnknownLanguage));
+        assert_eq!(Language::It.code(), "it");
+    }
+
     #[test]
     fn test_default() {
         assert_eq!(label!(InFavourOf), "Zugunsten");
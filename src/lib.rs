@@ -105,6 +105,10 @@ pub enum ParseBillDataError {
     Json(#[from] serde_json::Error),
     #[error("unsupported input format {0:?}; expected \"toml\" or \"json\"")]
     UnsupportedFormat(String),
+    #[error(transparent)]
+    Profile(#[from] ProfileError),
+    #[error("invalid bill after merging the creditor profile: {0}")]
+    Merged(serde_json::Error),
 }
 
 
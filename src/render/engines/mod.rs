@@ -10,6 +10,7 @@ pub mod pdf;
 pub mod svg;
 
 pub mod png_renderers;
+pub mod text_summary;
 pub mod qr_renderers;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -61,11 +61,20 @@ impl PDFBuilder{
      }
 
     pub fn setup_pdf(&mut self) -> Result<(), RenderError> {
+        let content_id = self.content_id;
+        self.setup_pages(&[content_id])?;
+        self.draw_perforations();
+        Ok(())
+     }
+
+    /// Sets up the document with one A4 page per content stream in
+    /// `content_ids`, all sharing the embedded fonts. The streams themselves
+    /// are written by the caller.
+    pub fn setup_pages(&mut self, content_ids: &[Ref]) -> Result<(), RenderError> {
 
         let catalog_id = self.next_id.bump();
         let page_tree_id = self.next_id.bump();
-        let page_id = self.next_id.bump();
-        //let content_id = self.next_id.bump();
+        let page_ids: Vec<Ref> = content_ids.iter().map(|_| self.next_id.bump()).collect();
 
         // I will have to use Zapf Dingbats for Scissors Symbol
         let zapf_id = self.next_id.bump();
@@ -75,31 +84,37 @@ impl PDFBuilder{
         self.pdf.type1_font(courier_id).base_font(pdf_writer::Name(b"Courier-Bold"));
 
         self.pdf.catalog(catalog_id).pages(page_tree_id);
-        self.pdf.pages(page_tree_id).kids([page_id]).count(1);
-        let mut page = self.pdf.page(page_id);
-
-
-        // Setup the resources.
-        let mut res = page.resources();
-        let mut f_dict = res.fonts();
-        f_dict.pair(pdf_writer::Name(b"Zapf"), zapf_id);
-        f_dict.pair(pdf_writer::Name(b"Courier"), courier_id);
-        f_dict.pair(name(FontStyle::Regular), self.fonts.regular.type0_ref);
-        f_dict.pair(name(FontStyle::Bold), self.fonts.bold.type0_ref);
-        f_dict.finish();
-        res.finish();
-
-        // Create A4 Page
-        page.media_box(Rect::new(0.0, 0.0, 595.28, 842.89)); // A4
-        page.parent(page_tree_id);
-        page.contents(self.content_id);
-        page.finish();
+        let count = i32::try_from(page_ids.len()).map_err(|_| RenderError::SetupPdfError)?;
+        self.pdf.pages(page_tree_id).kids(page_ids.iter().copied()).count(count);
+
+        for (&page_id, &content_id) in page_ids.iter().zip(content_ids) {
+            let mut page = self.pdf.page(page_id);
+
+            // Setup the resources.
+            let mut res = page.resources();
+            let mut f_dict = res.fonts();
+            f_dict.pair(pdf_writer::Name(b"Zapf"), zapf_id);
+            f_dict.pair(pdf_writer::Name(b"Courier"), courier_id);
+            f_dict.pair(name(FontStyle::Regular), self.fonts.regular.type0_ref);
+            f_dict.pair(name(FontStyle::Bold), self.fonts.bold.type0_ref);
+            f_dict.finish();
+            res.finish();
+
+            // Create A4 Page
+            page.media_box(Rect::new(0.0, 0.0, 595.28, 842.89)); // A4
+            page.parent(page_tree_id);
+            page.contents(content_id);
+            page.finish();
+        }
 
+        Ok(())
+    }
+
+    /// Draws the perforation lines and scissors into the current content.
+    pub fn draw_perforations(&mut self) {
         self.draw_perforation_horizonal();
         self.draw_perforation_vertical();
-
-        Ok(())
-     }
+    }
 
     fn draw_perforation_horizonal(&mut self) {
        self.content.save_state();
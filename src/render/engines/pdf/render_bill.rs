@@ -8,6 +8,7 @@ use thiserror::Error;
 use crate::{BillData, Language, PaymentPartLayout, ReceiptLayout};
 use crate::render::layout::bill_layout::LayoutStrategy;
 use crate::pdf::{execute_bill_ops, PDFBuilder};
+use pdf_writer::{Content, Ref};
 use crate::qr_bill::qr_code;
 
 #[cfg(feature = "pdf-debug")]
@@ -29,6 +30,10 @@ pub enum RenderError {
     BillOpsExecutionError,
     #[error("Failed to generate QR code")]
     QrCodeGenerationError,
+    #[error("Failed to rasterize bill")]
+    RasterizationError,
+    #[error("No bills to render")]
+    NoBillsError,
 }
 
 /// Renders `bill` to a complete, single-page A4 PDF document (receipt and
@@ -63,11 +68,49 @@ pub fn render_bill_to_pdf(bill: &BillData, language: Language) -> Result<Vec<u8>
     let mut builder = PDFBuilder::new();
     builder.setup_pdf()?;
 
-    // --- 2. Layout: Payment Part --
+    // --- 2. Layout and draw the bill ---
+    render_page(&mut builder, bill, language);
+
+    // --- 3. Attach content stream ---
+    builder
+        .pdf
+        .stream(builder.content_id, &builder.content.finish());
+
+    // --- 4. Write PDF -
+    Ok(builder.pdf.finish())
+}
+
+/// Renders `bills` into one PDF document, one A4 page per bill in the
+/// given order, e.g. to print a whole batch at once. The fonts are
+/// embedded only once. An empty slice is an error, since a PDF needs at
+/// least one page.
+pub fn render_bills_to_pdf(bills: &[BillData], language: Language) -> Result<Vec<u8>, RenderError> {
+    if bills.is_empty() {
+        return Err(RenderError::NoBillsError);
+    }
+    let mut builder = PDFBuilder::new();
+
+    let content_ids: Vec<Ref> = (0..bills.len())
+        .map(|i| if i == 0 { builder.content_id } else { builder.next_id.bump() })
+        .collect();
+    builder.setup_pages(&content_ids)?;
+
+    for (bill, content_id) in bills.iter().zip(content_ids) {
+        builder.draw_perforations();
+        render_page(&mut builder, bill, language);
+        let content = std::mem::replace(&mut builder.content, Content::new());
+        builder.pdf.stream(content_id, &content.finish());
+    }
+
+    Ok(builder.pdf.finish())
+}
+
+/// Lays out payment part and receipt of `bill` and draws them into the
+/// builder's current content stream.
+fn render_page(builder: &mut PDFBuilder, bill: &BillData, language: Language) {
     PaymentPartLayout::new()
         .render(bill, language, &builder.fonts, &mut builder.ops);
 
-    // --- 3. Layout: Receipt --
     ReceiptLayout::new()
         .render(bill, language, &builder.fonts, &mut builder.ops);
 
@@ -75,18 +118,32 @@ pub fn render_bill_to_pdf(bill: &BillData, language: Language) -> Result<Vec<u8>
         &mut builder.content,
         &builder.fonts,
         std::mem::take(&mut builder.ops),
-        qr_code(&bill).ok().as_ref(),
+        qr_code(bill).ok().as_ref(),
     );
 
     /// Draw a visual overlay grid 5x5 mm and positions and sizes of blocks.
     #[cfg(feature = "pdf-debug")]
-    
-    draw_debug_overlay(&mut builder);
-    // --- 6. Attach content stream ---
-    builder
-        .pdf
-        .stream(builder.content_id, &builder.content.finish());
+    draw_debug_overlay(builder);
+}
 
-    // --- 7. Write PDF -
-    Ok(builder.pdf.finish())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_bill;
+
+    #[test]
+    fn renders_one_page_per_bill() {
+        let bill = build_bill().unwrap();
+        let pdf = render_bills_to_pdf(&[bill.clone(), bill.clone(), bill], Language::De).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF"));
+        assert!(text.contains("/Count 3"));
+        assert_eq!(text.matches("/Type /Page").count() - text.matches("/Type /Pages").count(), 3);
+    }
+
+    #[test]
+    fn rejects_an_empty_batch() {
+        assert!(matches!(render_bills_to_pdf(&[], Language::De), Err(RenderError::NoBillsError)));
+    }
 }
@@ -10,10 +10,17 @@ use std::path::Path;
 use usvg::{Options, Tree};
 use resvg::tiny_skia::Pixmap;
 
+use crate::pdf::render_bill::RenderError;
+use crate::svg::render_bill_to_svg;
+use crate::{BillData, Language, LIBERATION_SANS_BOLD_TTF, LIBERATION_SANS_REGULAR_TTF};
+
 const QR_MM: f64 = 46.0;
 const DPI: f64 = 300.0;
 const MM_PER_INCH: f64 = 25.4;
 
+/// Width and height of the bill (receipt and payment part) in mm.
+const BILL_MM: (f64, f64) = (210.0, 105.0);
+
 /// Rasterize an `svg::Document` to PNG at exact physical size.
 pub fn render_svg_to_png(
     doc: &svg::Document,
@@ -38,3 +45,42 @@ pub fn render_svg_to_png(
     fs::write(out_path, pixmap.encode_png()?)?;
     Ok(())
 }
+
+/// Renders `bill` to a PNG of the slip (210 x 105 mm) at `dpi`, e.g. for
+/// e-mail or chat where a PDF attachment is unwelcome. Rasterizes the
+/// output of [`render_bill_to_svg`] with the bundled fonts, so the result
+/// doesn't depend on the fonts installed on the machine.
+pub fn render_bill_to_png(bill: &BillData, language: Language, dpi: f64) -> Result<Vec<u8>, RenderError> {
+    let svg = render_bill_to_svg(bill, language)?;
+
+    let mut opt = Options::default();
+    opt.fontdb_mut().load_font_data(LIBERATION_SANS_REGULAR_TTF.to_vec());
+    opt.fontdb_mut().load_font_data(LIBERATION_SANS_BOLD_TTF.to_vec());
+    let tree = Tree::from_str(&svg, &opt).map_err(|_| RenderError::RasterizationError)?;
+
+    let px_per_mm = dpi / MM_PER_INCH;
+    let (width, height) = ((BILL_MM.0 * px_per_mm).round() as u32, (BILL_MM.1 * px_per_mm).round() as u32);
+    let mut pixmap = Pixmap::new(width, height).ok_or(RenderError::RasterizationError)?;
+    pixmap.fill(resvg::tiny_skia::Color::WHITE);
+
+    let scale = (width as f32 / tree.size().width(), height as f32 / tree.size().height());
+    resvg::render(&tree, usvg::Transform::from_scale(scale.0, scale.1), &mut pixmap.as_mut());
+
+    pixmap.encode_png().map_err(|_| RenderError::RasterizationError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_bill;
+
+    #[test]
+    fn renders_bill_at_requested_resolution() {
+        let png = render_bill_to_png(&build_bill().unwrap(), Language::De, 150.0).unwrap();
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+
+        assert_eq!((pixmap.width(), pixmap.height()), (1240, 620));
+        // Not blank: the QR code and text leave dark pixels.
+        assert!(pixmap.pixels().iter().any(|p| p.red() < 64));
+    }
+}
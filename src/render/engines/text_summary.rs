@@ -0,0 +1,204 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! The payment part as text, for screen readers, plain-text e-mails and
+//! `alt` attributes.
+//!
+//! The fields follow the slip's reading order with the slip's headings and
+//! formatting (grouped IBAN and reference, `1 949.75`, address lines as on
+//! the slip):
+//!
+//! ```text
+//! Payment part
+//!
+//! Account / Payable to
+//! CH64 3196 1000 0044 2155 7
+//! Robert Schneider AG
+//! Rue du Lac 1268
+//! 2501 Biel
+//!
+//! Reference
+//! 21 00000 00003 13947 14300 09017
+//! ...
+//! ```
+//!
+//! Fields that are empty on the slip are left out rather than announced as
+//! blank boxes. Swico billing information is spelled out in an extra
+//! "Billing information" section (see
+//! [`crate::SwicoBillInformation::localized_lines`]), as the raw `//S1/...`
+//! string means nothing when read aloud.
+
+use crate::support::traits::{SliceExt, SwissQRFormatter};
+use crate::{label, BillData, LabelKey, Language, ReferenceType, SwicoBillInformation};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum TextFormat {
+    /// Headings and values on lines of their own, sections separated by a
+    /// blank line.
+    #[default]
+    Plain,
+    /// A level-2 heading, bold field labels and hard line breaks; values are
+    /// escaped.
+    Markdown,
+}
+
+fn text(key: LabelKey, language: Language) -> &'static str {
+    label(key, language).unwrap_or_default()
+}
+
+/// Backslash-escapes what Markdown would otherwise interpret: emphasis,
+/// links and HTML anywhere, headings, quotes and list markers at the start
+/// of a line. Amounts and dates stay readable (`1 949.75`, not `1 949\.75`).
+fn escape_markdown(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let digits = value.chars().take_while(char::is_ascii_digit).count();
+    // `1. ` starts an ordered list, `12.05.2019` doesn't.
+    let numbered = digits > 0 && value[digits..].starts_with(['.', ')']) && value[digits + 1..].starts_with([' ', '\t']);
+    for (i, c) in value.chars().enumerate() {
+        let line_start = i == 0 && "#>-+".contains(c);
+        let ordered_list = numbered && i == digits;
+        if line_start || ordered_list || "\\`*_[]<>|~&".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// The sections of the payment part, as (heading, lines).
+fn sections(bill: &BillData, language: Language) -> Vec<(&'static str, Vec<String>)> {
+    let mut sections = Vec::new();
+
+    let mut account = vec![bill.iban.as_str().format_iban()];
+    account.extend(bill.creditor_address.to_lines().all_but_last().iter().cloned());
+    sections.push((text(LabelKey::AccountPayableTo, language), account));
+
+    match &bill.reference_type {
+        ReferenceType::QrRef(reference) => {
+            sections.push((text(LabelKey::Reference, language), vec![reference.format_qr_reference()]));
+        }
+        ReferenceType::Creditor(reference) => {
+            sections.push((text(LabelKey::Reference, language), vec![reference.format_scor_reference()]));
+        }
+        ReferenceType::NoRef => {}
+    }
+
+    let additional: Vec<String> =
+        [&bill.unstructured_message, &bill.bill_information].into_iter().flatten().cloned().collect();
+    if !additional.is_empty() {
+        sections.push((text(LabelKey::AdditionalInformation, language), additional));
+    }
+    if let Some(info) = bill.bill_information.as_deref().and_then(SwicoBillInformation::decode_text) {
+        let lines = info.localized_lines(language);
+        if !lines.is_empty() {
+            sections.push((text(LabelKey::BillingInformation, language), lines));
+        }
+    }
+
+    sections.push((text(LabelKey::Currency, language), vec![bill.currency.to_string()]));
+    if let Some(amount) = &bill.amount {
+        sections.push((text(LabelKey::Amount, language), vec![amount.format_amount()]));
+    }
+
+    if let Some(debtor) = &bill.debtor_address {
+        sections.push((text(LabelKey::PayableBy, language), debtor.to_lines().all_but_last().to_vec()));
+    }
+
+    let procedures: Vec<String> = bill.alternative_schemes.iter().flatten().map(ToString::to_string).collect();
+    if !procedures.is_empty() {
+        sections.push((text(LabelKey::AlternativeProcedures, language), procedures));
+    }
+
+    sections
+}
+
+/// Renders the payment part of `bill` as plain text or Markdown, with the
+/// labels of `language`.
+///
+/// ```
+/// use swiss_qrust::text_summary::{render_bill_to_text, TextFormat};
+/// use swiss_qrust::{build_bill, Language};
+///
+/// let bill = build_bill()?;
+/// let text = render_bill_to_text(&bill, Language::En, TextFormat::Plain);
+/// assert!(text.starts_with("Payment part\n\nAccount / Payable to\nCH64 3196 1000 0044 2155 7\n"));
+///
+/// let markdown = render_bill_to_text(&bill, Language::En, TextFormat::Markdown);
+/// assert!(markdown.starts_with("## Payment part\n\n**Account / Payable to**  \nCH64 3196 1000 0044 2155 7  \n"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn render_bill_to_text(bill: &BillData, language: Language, format: TextFormat) -> String {
+    let title = text(LabelKey::PaymentPart, language);
+    let sections = sections(bill, language);
+
+    let blocks: Vec<String> = match format {
+        TextFormat::Plain => std::iter::once(title.to_string())
+            .chain(sections.into_iter().map(|(heading, lines)| format!("{heading}\n{}", lines.join("\n"))))
+            .collect(),
+        TextFormat::Markdown => std::iter::once(format!("## {title}"))
+            .chain(sections.into_iter().map(|(heading, lines)| {
+                let lines: Vec<String> = lines.iter().map(|l| escape_markdown(l)).collect();
+                format!("**{heading}**  \n{}", lines.join("  \n"))
+            }))
+            .collect(),
+    };
+
+    let mut out = blocks.join("\n\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_bill, AlternativeProcedure};
+
+    #[test]
+    fn lists_the_slip_fields_in_reading_order() {
+        let mut bill = build_bill().unwrap();
+        bill.amount = Some("1949.75".into());
+        bill.bill_information = Some("//S1/10/10201409/11/190512".into());
+        bill.alternative_schemes[0] = Some("eBill/B/peter@sample.ch".parse::<AlternativeProcedure>().unwrap());
+
+        let text = render_bill_to_text(&bill, Language::De, TextFormat::Plain);
+        let headings = ["Zahlteil", "Konto / Zahlbar an", "Referenz", "Zusätzliche Informationen",
+            "Rechnungsinformationen", "Währung", "Betrag", "Zahlbar durch", "Alternative Verfahren"];
+        let positions: Vec<usize> = headings.iter().map(|h| text.find(&format!("{h}\n")).unwrap()).collect();
+        assert!(positions.is_sorted(), "{text}");
+
+        assert!(text.contains("Rechnungsinformationen\nRechnungsnr. 10201409\nDatum 12.05.2019\n"));
+        assert!(text.contains("Betrag\n1 949.75\n"));
+        assert!(text.contains("eBill/B/peter@sample.ch"));
+    }
+
+    #[test]
+    fn leaves_out_empty_fields_and_escapes_markdown() {
+        let mut bill = build_bill().unwrap();
+        bill.debtor_address = None;
+        bill.reference_type = ReferenceType::NoRef;
+
+        let text = render_bill_to_text(&bill, Language::En, TextFormat::Markdown);
+        assert!(!text.contains("**Amount**"));
+        assert!(!text.contains("**Reference**"));
+        assert!(!text.contains("**Payable by"));
+        assert!(text.contains("fit\\&kicking"));
+        assert!(text.contains("Premium calculation July 2020"));
+
+        assert_eq!(escape_markdown("1. Mahnung"), "1\\. Mahnung");
+        assert_eq!(escape_markdown("- 5 %"), "\\- 5 %");
+        assert_eq!(escape_markdown("12.05.2019"), "12.05.2019");
+    }
+
+    #[test]
+    fn survives_malformed_swico_dates() {
+        // Valid SPS text, but 12 bytes that don't split into two dates.
+        let mut bill = build_bill().unwrap();
+        bill.bill_information = Some("//S1/31/aaaaa\u{e9}aaaaa".into());
+
+        let text = render_bill_to_text(&bill, Language::De, TextFormat::Plain);
+        assert!(text.contains("Premium calculation July 2020"), "{text}");
+    }
+}
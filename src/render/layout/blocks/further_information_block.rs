@@ -29,7 +29,7 @@ impl<T: FontMetrics> LayoutBlock<T> for FurtherInformationBlock {
 
         for (i, scheme) in ctx.bill_data.alternative_schemes.iter().flatten().enumerate() {
             let y = row_top + Mm(i as f32) * PP_FURTHER_INFO_LINE_SPACING.to_mm();
-            draw_alt_procedure_line(ops, ctx, scheme, x, y, available_width);
+            draw_alt_procedure_line(ops, ctx, &scheme.to_string(), x, y, available_width);
         }
     }
 }
@@ -5,7 +5,7 @@
  */
 use crate::support::traits::SwissQRFormatter;
 use crate::block_elements::{Column, ColumnCursor, LayoutBlock};
-use crate::constants::{A4_PAGE_WIDTH, MARGIN};
+use crate::constants::{A4_PAGE_WIDTH, MARGIN, RECEIPT_WIDTH};
 use crate::{draw_corner_marks, draw_label, draw_single_line, draw_text_lines, label, truncate_to_width, DrawOp, FontStyle, Mm, QRBillLayoutRect, ReferenceType, RenderContext, SlipPart, CORNER_MARKS_PAYABLE_BY_POLYLINES, CORNER_MARKS_PAYABLE_BY_VIEWBOX};
 use crate::render::FontMetrics;
 use crate::support::traits::SliceExt;
@@ -32,7 +32,7 @@ impl <T: FontMetrics> LayoutBlock<T> for InformationBlock {
         // Account / Payable to
         draw_label(ops, label!(AccountPayableTo, ctx.language), cursor.x, &mut cursor.y, ctx.label_size);
         cursor.advance(ctx.line_spacing);
-        draw_single_line(ops, &ctx.bill_data.iban.format_iban(), cursor.x, &mut cursor.y, ctx.text_size);
+        draw_single_line(ops, &ctx.bill_data.iban.as_str().format_iban(), cursor.x, &mut cursor.y, ctx.text_size);
         cursor.advance(ctx.line_spacing);
         draw_text_lines(ops, &ctx.bill_data.creditor_address.to_lines().all_but_last(), cursor.x, &mut cursor.y, ctx.text_size, ctx.line_spacing);
         cursor.advance(ctx.line_spacing); // blank line before the next block
@@ -51,6 +51,20 @@ impl <T: FontMetrics> LayoutBlock<T> for InformationBlock {
             cursor.advance(ctx.line_spacing); // blank line before the next block
         }
 
+        // Notification notice - the receipt has no additional information
+        // field, so the "do not use for payment" notice that the payment
+        // part shows there is repeated here, in bold.
+        if self.part == SlipPart::Receipt
+            && ctx.bill_data.is_notification()
+            && let Some(notice) = ctx.bill_data.unstructured_message.as_deref()
+        {
+            let available_width = RECEIPT_WIDTH - cursor.x - MARGIN;
+            let notice = truncate_to_width(ctx.metrics, notice, FontStyle::Bold, ctx.text_size, available_width);
+            draw_label(ops, &notice, cursor.x, &mut cursor.y, ctx.text_size);
+            cursor.advance(ctx.line_spacing); // move past the notice line
+            cursor.advance(ctx.line_spacing); // blank line before the next block
+        }
+
         // Additional information - payment part only; omitted on the
         // receipt (which has no such field) and when there is nothing to
         // show. Style Guide 3.5.4: Ustrd and StrdBkgInf (which may hold
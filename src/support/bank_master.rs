@@ -0,0 +1,428 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Offline lookup of Swiss and Liechtenstein financial institutions by IID
+//! (the five digits after the IBAN check digits), based on the SIX bank
+//! master file.
+//!
+//! [`is_qr_iban`](crate::is_qr_iban) only checks that the IID lies in the
+//! QR-IID range 30000-31999. With a bank master, [`BankMaster::lookup_iban`]
+//! also tells whether the IID exists, whether it was discontinued (merged
+//! into another IID) and whether a QR-IID really belongs to an institution
+//! participating in the QR-IBAN scheme - and returns the bank's name, BIC
+//! and address.
+//!
+//! The file can be loaded at runtime ([`BankMaster::from_path`]) or embedded
+//! at build time by setting `SWISS_QRUST_BANK_MASTER` to its path
+//! ([`BankMaster::embedded`]). Without that variable nothing is embedded and
+//! lookups against the embedded master fail with
+//! [`BankLookupError::NoBankMaster`], see [`BANK_MASTER_SNAPSHOT_IS_SAMPLE`].
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::support::country::normalize;
+use crate::{
+    Iban, IbanError, BANK_MASTER_SNAPSHOT, BANK_MASTER_SNAPSHOT_FORMAT, BANK_MASTER_SNAPSHOT_IS_SAMPLE,
+};
+
+#[derive(Debug, Error)]
+pub enum BankMasterError {
+    #[error("could not read bank master: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid bank master JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("bank master has no '{0}' column")]
+    MissingColumn(&'static str),
+    #[error("bank master entry {entry}: invalid IID '{value}'")]
+    InvalidIid { entry: usize, value: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum BankLookupError {
+    #[error(transparent)]
+    Iban(#[from] IbanError),
+    #[error("no bank master loaded")]
+    NoBankMaster,
+    #[error("IID {0} is not in the bank master")]
+    UnknownIid(u32),
+    #[error("IID {iid} is discontinued{}", successor.map(|s| format!(", replaced by IID {s}")).unwrap_or_default())]
+    DiscontinuedIid { iid: u32, successor: Option<u32> },
+    #[error("QR-IID {0} does not belong to a QR-IBAN participant")]
+    NotQrParticipant(u32),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BankMasterFormat {
+    Csv,
+    Json,
+}
+
+/// One IID from the bank master.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankEntry {
+    pub iid: u32,
+    /// Set when the IID was discontinued and payments go to another IID.
+    pub new_iid: Option<u32>,
+    /// The entry is a QR-IID, i.e. the institution accepts QR-IBANs.
+    pub qr_iid: bool,
+    pub name: String,
+    pub street: Option<String>,
+    pub building_number: Option<String>,
+    pub post_code: Option<String>,
+    pub town: Option<String>,
+    pub country: Option<String>,
+    pub bic: Option<String>,
+}
+
+impl BankEntry {
+    pub fn is_discontinued(&self) -> bool {
+        self.new_iid.is_some_and(|new| new != self.iid)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BankMaster {
+    entries: BTreeMap<u32, BankEntry>,
+}
+
+/// Field -> accepted column/key names, after [`normalize`]. Covers the
+/// column headers of the SIX CSV export and the keys of its JSON variant.
+const FIELDS: &[(&str, &[&str])] = &[
+    ("iid", &["iid"]),
+    ("new_iid", &["newiid"]),
+    ("iid_type", &["iidtype", "entrytype"]),
+    ("name", &["bankinstitutionname", "bankorinstitutionname", "name"]),
+    ("street", &["streetname", "street"]),
+    ("building_number", &["buildingnumber"]),
+    ("post_code", &["postcode", "zipcode"]),
+    ("town", &["town", "townname"]),
+    ("country", &["country", "countrycode"]),
+    ("bic", &["bic"]),
+];
+
+static EMBEDDED: Lazy<BankMaster> = Lazy::new(|| {
+    if BANK_MASTER_SNAPSHOT_IS_SAMPLE {
+        return BankMaster::default();
+    }
+    BankMaster::parse(BANK_MASTER_SNAPSHOT, BANK_MASTER_SNAPSHOT_FORMAT)
+        .expect("embedded bank master is malformed")
+});
+
+impl BankMaster {
+    /// The bank master embedded at build time; empty unless
+    /// `SWISS_QRUST_BANK_MASTER` was set.
+    ///
+    /// # Panics
+    /// If the embedded file can't be parsed; the file is fixed at build
+    /// time, so this either always or never happens for a given build.
+    pub fn embedded() -> &'static BankMaster {
+        &EMBEDDED
+    }
+
+    /// Loads a bank master file; `.json` files are read as JSON, anything
+    /// else as CSV.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, BankMasterError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => BankMasterFormat::Json,
+            _ => BankMasterFormat::Csv,
+        };
+        Self::parse(&content, format)
+    }
+
+    pub fn parse(content: &str, format: BankMasterFormat) -> Result<Self, BankMasterError> {
+        match format {
+            BankMasterFormat::Csv => Self::from_csv(content),
+            BankMasterFormat::Json => Self::from_json(content),
+        }
+    }
+
+    /// Parses the CSV export: a header row naming the columns, `;` or `,`
+    /// separated, double-quoted fields allowed. Lines starting with `#` are
+    /// ignored.
+    pub fn from_csv(content: &str) -> Result<Self, BankMasterError> {
+        let mut lines = content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.starts_with('#'));
+        let Some(header) = lines.next() else {
+            return Ok(Self::default());
+        };
+        let delimiter = if header.contains(';') { ';' } else { ',' };
+        let header: Vec<String> = split_csv_line(header, delimiter).iter().map(|h| normalize(h)).collect();
+
+        let columns: Vec<(&str, Option<usize>)> = FIELDS
+            .iter()
+            .map(|(field, names)| (*field, header.iter().position(|h| names.contains(&h.as_str()))))
+            .collect();
+
+        let records = lines.map(|line| {
+            let values = split_csv_line(line, delimiter);
+            columns
+                .iter()
+                .filter_map(|(field, col)| Some((*field, values.get((*col)?)?.clone())))
+                .collect::<Vec<_>>()
+        });
+        Self::from_records(records, columns.iter().filter(|(_, c)| c.is_some()).map(|(f, _)| *f))
+    }
+
+    /// Parses the JSON variant: an array of entry objects, or an object
+    /// with such an array under `entries`.
+    pub fn from_json(content: &str) -> Result<Self, BankMasterError> {
+        let value: Value = serde_json::from_str(content)?;
+        let empty = Vec::new();
+        let items = match &value {
+            Value::Array(items) => items,
+            Value::Object(map) => map.get("entries").and_then(Value::as_array).unwrap_or(&empty),
+            _ => &empty,
+        };
+
+        let mut present = Vec::new();
+        let records: Vec<Vec<(&str, String)>> = items
+            .iter()
+            .filter_map(Value::as_object)
+            .map(|object| {
+                object
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let key = normalize(key);
+                        let (field, _) = FIELDS.iter().find(|(_, names)| names.contains(&key.as_str()))?;
+                        let value = match value {
+                            Value::String(s) => s.clone(),
+                            Value::Number(n) => n.to_string(),
+                            _ => return None,
+                        };
+                        if !present.contains(field) {
+                            present.push(*field);
+                        }
+                        Some((*field, value))
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_records(records, present.into_iter())
+    }
+
+    fn from_records<'a>(
+        records: impl IntoIterator<Item = Vec<(&'a str, String)>>,
+        mut present: impl Iterator<Item = &'a str>,
+    ) -> Result<Self, BankMasterError> {
+        let mut entries = BTreeMap::new();
+        let mut records = records.into_iter().peekable();
+        if records.peek().is_some() && !present.any(|f| f == "iid") {
+            return Err(BankMasterError::MissingColumn("IID"));
+        }
+
+        for (index, record) in records.enumerate() {
+            let get = |field: &str| {
+                record
+                    .iter()
+                    .find(|(f, _)| *f == field)
+                    .map(|(_, v)| v.trim())
+                    .filter(|v| !v.is_empty())
+            };
+            let iid_value = get("iid").unwrap_or_default();
+            let iid: u32 = iid_value.parse().map_err(|_| BankMasterError::InvalidIid {
+                entry: index + 1,
+                value: iid_value.to_string(),
+            })?;
+
+            // From the IID type, not the range: a QR-IID nobody registered
+            // isn't a participant.
+            let qr_iid = get("iid_type").is_some_and(|t| normalize(t).contains("qr"));
+            let entry = BankEntry {
+                iid,
+                new_iid: get("new_iid").and_then(|v| v.parse().ok()),
+                qr_iid,
+                name: get("name").unwrap_or_default().to_string(),
+                street: get("street").map(str::to_string),
+                building_number: get("building_number").map(str::to_string),
+                post_code: get("post_code").map(str::to_string),
+                town: get("town").map(str::to_string),
+                country: get("country").map(str::to_string),
+                bic: get("bic").map(str::to_string),
+            };
+
+            // Branches share their IID with the head office in some
+            // exports; keep the first (head office) entry.
+            entries.entry(iid).or_insert(entry);
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, iid: u32) -> Option<&BankEntry> {
+        self.entries.get(&iid)
+    }
+
+    /// Resolves the institution an IBAN belongs to.
+    ///
+    /// Fails if the IBAN is invalid, the bank master is empty, the IID is
+    /// unknown or discontinued, or it is a QR-IBAN whose QR-IID isn't
+    /// registered as one.
+    ///
+    /// ```
+    /// use swiss_qrust::BankMaster;
+    ///
+    /// let master = BankMaster::from_csv(
+    ///     "IID;IID type;Bank/Institution name;BIC\n31999;QR-IID;Exempel Privatbank AG;EXMPCHZZXXX\n",
+    /// )?;
+    /// let bank = master.lookup_iban("CH44 3199 9123 0008 8901 2")?;
+    /// assert!(bank.qr_iid);
+    /// assert_eq!(bank.bic.as_deref(), Some("EXMPCHZZXXX"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn lookup_iban(&self, iban: &str) -> Result<&BankEntry, BankLookupError> {
+        let iban = Iban::parse(iban)?;
+        if self.is_empty() {
+            return Err(BankLookupError::NoBankMaster);
+        }
+        let iid: u32 = iban.iid().parse().map_err(|_| IbanError::InvalidIid)?;
+        let qr = iban.is_qr_iban();
+
+        let Some(entry) = self.get(iid) else {
+            return Err(if qr { BankLookupError::NotQrParticipant(iid) } else { BankLookupError::UnknownIid(iid) });
+        };
+        if entry.is_discontinued() {
+            return Err(BankLookupError::DiscontinuedIid { iid, successor: entry.new_iid });
+        }
+        if qr && !entry.qr_iid {
+            return Err(BankLookupError::NotQrParticipant(iid));
+        }
+        Ok(entry)
+    }
+}
+
+/// Splits one CSV line; `""` inside a quoted field is a literal quote.
+pub(crate) fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BANK_MASTER_SNAPSHOT_IS_SAMPLE;
+
+    const FIXTURE: &str = include_str!("../../tests/data/bank_master_sample.csv");
+
+    #[test]
+    fn embedded_snapshot_parses() {
+        let master = BankMaster::embedded();
+        if BANK_MASTER_SNAPSHOT_IS_SAMPLE {
+            assert!(master.is_empty());
+            assert_eq!(master.lookup_iban("CH93 0076 2011 6238 5295 7"), Err(BankLookupError::NoBankMaster));
+        } else {
+            assert!(!master.is_empty());
+        }
+    }
+
+    #[test]
+    fn parses_six_csv_layout() {
+        let master = BankMaster::from_csv(FIXTURE).unwrap();
+        assert_eq!(master.len(), 7);
+        assert_eq!(master.lookup_iban("CH44 3199 9123 0008 8901 2").unwrap().name, "Exempel Privatbank AG");
+        assert!(!master.get(8710).unwrap().qr_iid);
+    }
+
+    #[test]
+    fn resolves_iban_to_institution() {
+        let master = BankMaster::from_csv(
+            "IID;New IID;IID type;Bank/Institution name;Street name;Building number;Post code;Town;Country;BIC\n\
+             762;;Headquarters;Musterbank AG;Bundesplatz;1;3011;Bern;CH;MUSTCHBBXXX\n",
+        )
+        .unwrap();
+
+        let bank = master.lookup_iban("CH93 0076 2011 6238 5295 7").unwrap();
+        assert_eq!(bank.name, "Musterbank AG");
+        assert_eq!(bank.bic.as_deref(), Some("MUSTCHBBXXX"));
+        assert_eq!(bank.town.as_deref(), Some("Bern"));
+        assert!(!bank.qr_iid);
+    }
+
+    #[test]
+    fn reports_unknown_and_discontinued_iids() {
+        let master = BankMaster::from_csv("IID,New IID,Bank/Institution name\n762,,Musterbank AG\n4999,762,Alte Kasse\n").unwrap();
+
+        let unknown = crate::generate_iban_with_checksum("CH", "08710000000000001").unwrap();
+        assert_eq!(master.lookup_iban(&unknown), Err(BankLookupError::UnknownIid(8710)));
+        let discontinued = crate::generate_iban_with_checksum("CH", "04999000000000001").unwrap();
+        assert_eq!(
+            master.lookup_iban(&discontinued),
+            Err(BankLookupError::DiscontinuedIid { iid: 4999, successor: Some(762) })
+        );
+    }
+
+    #[test]
+    fn rejects_qr_iid_without_participant() {
+        let master = BankMaster::from_csv(
+            "IID;IID type;Bank/Institution name\n31999;QR-IID;Exempel Privatbank AG\n31961;Headquarters;Musterbank AG\n",
+        )
+        .unwrap();
+
+        assert!(master.lookup_iban("CH44 3199 9123 0008 8901 2").unwrap().qr_iid);
+        // In the QR-IID range, but not registered as a QR-IID.
+        assert_eq!(
+            master.lookup_iban("CH64 3196 1000 0044 2155 7"),
+            Err(BankLookupError::NotQrParticipant(31961))
+        );
+    }
+
+    #[test]
+    fn parses_json_variant() {
+        let json = r#"{"entries": [
+            {"iid": 762, "entryType": "BankMaster", "bankOrInstitutionName": "Musterbank AG", "townName": "Bern", "bic": "MUSTCHBBXXX"},
+            {"iid": 31961, "newIid": null, "iidType": "QR-IID", "bankOrInstitutionName": "Musterbank AG"}
+        ]}"#;
+        let master = BankMaster::from_json(json).unwrap();
+
+        assert_eq!(master.len(), 2);
+        assert_eq!(master.get(762).unwrap().town.as_deref(), Some("Bern"));
+        assert!(master.get(31961).unwrap().qr_iid);
+    }
+
+    #[test]
+    fn handles_quoted_csv_fields() {
+        let master = BankMaster::from_csv("\"IID\";\"Bank/Institution name\"\n\"762\";\"Muster; \"\"Bank\"\" AG\"\n").unwrap();
+        assert_eq!(master.get(762).unwrap().name, "Muster; \"Bank\" AG");
+    }
+
+    #[test]
+    fn rejects_file_without_iid_column() {
+        let err = BankMaster::from_csv("Name;BIC\nMusterbank;MUSTCHBBXXX\n").unwrap_err();
+        assert!(matches!(err, BankMasterError::MissingColumn("IID")));
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Lenient country lookup for data that doesn't use ISO alpha-2 codes.
+//!
+//! The QR-bill itself only carries alpha-2 codes, and [`Address::new`]
+//! insists on them. ERP exports often store "CHE", "756" or "Schweiz"
+//! instead; [`Country::resolve`] maps those onto the generated [`Country`]
+//! enum using the names from `assets/data/countries.json`.
+//!
+//! [`Address::new`]: crate::Address::new
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use strum::IntoEnumIterator;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{Country, COUNTRY_NAMES};
+
+/// Normalized name -> country. Names shared by several countries map to
+/// `None` so they are rejected rather than resolved arbitrarily.
+static NAME_INDEX: Lazy<HashMap<String, Option<Country>>> = Lazy::new(|| {
+    let mut index = HashMap::new();
+    for (name, country) in COUNTRY_NAMES {
+        index
+            .entry(normalize(name))
+            .and_modify(|entry: &mut Option<Country>| {
+                if *entry != Some(*country) {
+                    *entry = None;
+                }
+            })
+            .or_insert(Some(*country));
+    }
+    index
+});
+
+impl Country {
+    /// Resolves an alpha-2 code, alpha-3 code ("CHE"), numeric code ("756")
+    /// or a country name in English, German, French, Italian or a native
+    /// language ("Switzerland", "Schweiz", "Suisse", "Svizzera").
+    ///
+    /// Case, diacritics, punctuation and spacing are ignored, so "cote
+    /// d'ivoire" finds "Côte d'Ivoire". Returns `None` for unknown input and
+    /// for names that are ambiguous between countries.
+    ///
+    /// ```
+    /// use swiss_qrust::Country;
+    ///
+    /// assert_eq!(Country::resolve("CHE"), Some(Country::CH));
+    /// assert_eq!(Country::resolve("756"), Some(Country::CH));
+    /// assert_eq!(Country::resolve("svizzera"), Some(Country::CH));
+    /// assert_eq!(Country::resolve("Österreich"), Some(Country::AT));
+    /// assert_eq!(Country::resolve("Atlantis"), None);
+    /// ```
+    pub fn resolve(input: &str) -> Option<Country> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+
+        if input.len() == 2
+            && let Ok(country) = input.parse()
+        {
+            return Some(country);
+        }
+
+        if input.bytes().all(|b| b.is_ascii_digit()) {
+            let numeric: u16 = input.parse().ok()?;
+            return Country::iter().find(|c| {
+                c.meta().numeric.and_then(|n| n.parse::<u16>().ok()) == Some(numeric)
+            });
+        }
+
+        if input.len() == 3
+            && let Some(country) = Country::iter().find(|c| c.meta().alpha3.eq_ignore_ascii_case(input))
+        {
+            return Some(country);
+        }
+
+        NAME_INDEX.get(&normalize(input)).copied().flatten()
+    }
+}
+
+/// Lower case, diacritics stripped, only letters and digits kept.
+pub(crate) fn normalize(name: &str) -> String {
+    name.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_codes() {
+        assert_eq!(Country::resolve("ch"), Some(Country::CH));
+        assert_eq!(Country::resolve("CHE"), Some(Country::CH));
+        assert_eq!(Country::resolve("lie"), Some(Country::LI));
+        assert_eq!(Country::resolve("756"), Some(Country::CH));
+        assert_eq!(Country::resolve("40"), Some(Country::AT));
+        assert_eq!(Country::resolve("040"), Some(Country::AT));
+    }
+
+    #[test]
+    fn resolves_names_in_national_languages() {
+        for name in ["Schweiz", "Suisse", "Svizzera", "Switzerland", "Svizra", "Swiss Confederation"] {
+            assert_eq!(Country::resolve(name), Some(Country::CH), "{name}");
+        }
+        assert_eq!(Country::resolve("Deutschland"), Some(Country::DE));
+        assert_eq!(Country::resolve("Allemagne"), Some(Country::DE));
+        assert_eq!(Country::resolve("Germania"), Some(Country::DE));
+        assert_eq!(Country::resolve("Fürstentum Liechtenstein"), Some(Country::LI));
+    }
+
+    #[test]
+    fn ignores_case_diacritics_and_punctuation() {
+        assert_eq!(Country::resolve("  SCHWEIZ "), Some(Country::CH));
+        assert_eq!(Country::resolve("osterreich"), Some(Country::AT));
+        assert_eq!(Country::resolve("cote d ivoire"), Some(Country::CI));
+        assert_eq!(Country::resolve("Etats-Unis"), Some(Country::US));
+    }
+
+    #[test]
+    fn rejects_unknown_input() {
+        assert_eq!(Country::resolve(""), None);
+        assert_eq!(Country::resolve("XX"), None);
+        assert_eq!(Country::resolve("999"), None);
+        assert_eq!(Country::resolve("Atlantis"), None);
+    }
+}
@@ -7,7 +7,14 @@
 pub mod utils;
 pub mod validators;
 pub mod traits;
+pub mod sanitize;
+pub mod country;
+pub mod postal_codes;
+pub mod bank_master;
 
 pub use utils::*;
 pub use validators::*;
-pub use traits::*;
\ No newline at end of file
+pub use traits::*;
+pub use sanitize::*;
+pub use postal_codes::*;
+pub use bank_master::*;
\ No newline at end of file
@@ -0,0 +1,255 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Opt-in plausibility checks for postal codes.
+//!
+//! [`Address::new`] only enforces the spec's 16-character limit on `plz`;
+//! anything more would reject valid addresses whenever the rules here are
+//! incomplete. These checks therefore produce [`PostalCodeWarning`]s for
+//! the caller to show, never errors.
+//!
+//! Swiss and Liechtenstein postal codes are looked up in
+//! [`POSTAL_DIRECTORY`], generated at build time from
+//! `assets/data/postal_codes.csv` (or the full Swiss Post export named by
+//! `SWISS_QRUST_POSTAL_DIRECTORY`). Foreign postal codes only get a format
+//! check.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use thiserror::Error;
+
+use crate::support::country::normalize;
+use crate::{Address, BillData, Country, POSTAL_DIRECTORY, POSTAL_DIRECTORY_COMPLETE};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PostalCodeWarning {
+    #[error("postal code '{plz}' does not match the format for {country} ({expected})")]
+    InvalidFormat { plz: String, country: Country, expected: &'static str },
+    #[error("postal code '{plz}' is not in the Swiss postal directory")]
+    UnknownPostalCode { plz: String },
+    #[error("postal code '{plz}' belongs to {expected}, not {country}")]
+    CountryMismatch { plz: String, country: Country, expected: Country },
+    #[error("'{city}' does not match postal code '{plz}' (expected {})", suggestions.join(", "))]
+    CityMismatch { plz: String, city: String, suggestions: Vec<&'static str> },
+}
+
+/// Postal code formats: `(country, pattern, description)`.
+const FORMATS: &[(Country, &str, &str)] = &[
+    (Country::CH, r"^\d{4}$", "4 digits"),
+    (Country::LI, r"^94(8[5-9]|9[0-8])$", "9485-9498"),
+    (Country::DE, r"^\d{5}$", "5 digits"),
+    (Country::FR, r"^\d{5}$", "5 digits"),
+    (Country::IT, r"^\d{5}$", "5 digits"),
+    (Country::ES, r"^\d{5}$", "5 digits"),
+    (Country::US, r"^\d{5}(-\d{4})?$", "12345 or 12345-6789"),
+    (Country::AT, r"^\d{4}$", "4 digits"),
+    (Country::BE, r"^\d{4}$", "4 digits"),
+    (Country::LU, r"^\d{4}$", "4 digits"),
+    (Country::DK, r"^\d{4}$", "4 digits"),
+    (Country::NO, r"^\d{4}$", "4 digits"),
+    (Country::HU, r"^\d{4}$", "4 digits"),
+    (Country::NL, r"^\d{4} ?[A-Z]{2}$", "1234 AB"),
+    (Country::PL, r"^\d{2}-\d{3}$", "12-345"),
+    (Country::PT, r"^\d{4}-\d{3}$", "1234-567"),
+    (Country::SE, r"^\d{3} ?\d{2}$", "123 45"),
+    (Country::CZ, r"^\d{3} ?\d{2}$", "123 45"),
+    (Country::SK, r"^\d{3} ?\d{2}$", "123 45"),
+    (Country::GB, r"^[A-Z]{1,2}\d[A-Z\d]? ?\d[A-Z]{2}$", "e.g. SW1A 1AA"),
+];
+
+static FORMAT_REGEXES: Lazy<Vec<(Country, Regex, &'static str)>> = Lazy::new(|| {
+    FORMATS
+        .iter()
+        .map(|(country, pattern, expected)| {
+            (*country, Regex::new(&format!("(?i){pattern}")).unwrap(), *expected)
+        })
+        .collect()
+});
+
+/// Localities served by a Swiss or Liechtenstein postal code, as spelled
+/// by Swiss Post ("Biel/Bienne", "Genève").
+///
+/// ```
+/// use swiss_qrust::localities_for_postal_code;
+///
+/// assert_eq!(localities_for_postal_code("3000"), vec!["Bern"]);
+/// assert!(localities_for_postal_code("0000").is_empty());
+/// ```
+pub fn localities_for_postal_code(plz: &str) -> Vec<&'static str> {
+    directory_entries(plz).map(|(_, city, _)| *city).collect()
+}
+
+fn directory_entries(plz: &str) -> impl Iterator<Item = &'static (u16, &'static str, &'static str)> {
+    let plz: Option<u16> = plz.trim().parse().ok();
+    let start = plz.map_or(POSTAL_DIRECTORY.len(), |plz| {
+        POSTAL_DIRECTORY.partition_point(|(p, _, _)| *p < plz)
+    });
+    POSTAL_DIRECTORY[start..]
+        .iter()
+        .take_while(move |(p, _, _)| Some(*p) == plz)
+}
+
+/// Checks `plz` against the format rules for `country` and, for
+/// Switzerland and Liechtenstein, against the postal directory.
+///
+/// Unknown Swiss postal codes are only reported when the directory is
+/// complete; the bundled snapshot covers the main localities only.
+///
+/// ```
+/// use swiss_qrust::{check_postal_code, Country, PostalCodeWarning};
+///
+/// assert_eq!(check_postal_code("3000", "Bern", Country::CH), None);
+/// assert!(matches!(
+///     check_postal_code("3000", "Zürich", Country::CH),
+///     Some(PostalCodeWarning::CityMismatch { .. })
+/// ));
+/// assert!(matches!(
+///     check_postal_code("7953", "Lörrach", Country::DE),
+///     Some(PostalCodeWarning::InvalidFormat { .. })
+/// ));
+/// ```
+pub fn check_postal_code(plz: &str, city: &str, country: Country) -> Option<PostalCodeWarning> {
+    let plz = plz.trim();
+
+    if let Some((_, _, expected)) = FORMAT_REGEXES
+        .iter()
+        .find(|(c, regex, _)| *c == country && !regex.is_match(plz))
+    {
+        return Some(PostalCodeWarning::InvalidFormat { plz: plz.to_string(), country, expected });
+    }
+
+    if !matches!(country, Country::CH | Country::LI) {
+        return None;
+    }
+
+    let entries: Vec<_> = directory_entries(plz).collect();
+    let Some((_, _, canton)) = entries.first() else {
+        return POSTAL_DIRECTORY_COMPLETE.then(|| PostalCodeWarning::UnknownPostalCode { plz: plz.to_string() });
+    };
+
+    let expected = if *canton == "FL" { Country::LI } else { Country::CH };
+    if expected != country {
+        return Some(PostalCodeWarning::CountryMismatch { plz: plz.to_string(), country, expected });
+    }
+
+    let city_key = locality_key(city);
+    let matches = entries.iter().any(|(_, locality, _)| {
+        locality.split('/').any(|variant| locality_key(variant) == city_key)
+    });
+    if matches {
+        return None;
+    }
+
+    Some(PostalCodeWarning::CityMismatch {
+        plz: plz.to_string(),
+        city: city.trim().to_string(),
+        suggestions: entries.iter().map(|(_, locality, _)| *locality).collect(),
+    })
+}
+
+/// Comparison key for locality names: ignores case, diacritics and
+/// punctuation, a trailing canton code ("Carouge GE") and a trailing
+/// delivery district number ("Genève 3").
+fn locality_key(name: &str) -> String {
+    let mut name = name.trim();
+    if let Some((head, tail)) = name.rsplit_once(' ')
+        && (tail.bytes().all(|b| b.is_ascii_digit())
+            || (tail.len() == 2 && tail.bytes().all(|b| b.is_ascii_uppercase())))
+    {
+        name = head;
+    }
+    normalize(name)
+}
+
+impl Address {
+    /// [`check_postal_code`] for this address.
+    pub fn check_postal_code(&self) -> Option<PostalCodeWarning> {
+        check_postal_code(&self.plz, &self.city, self.country)
+    }
+}
+
+impl BillData {
+    /// Postal code warnings for creditor and debtor address, with the
+    /// field they refer to (`"creditor_address.plz"`, ...).
+    pub fn postal_code_warnings(&self) -> Vec<(&'static str, PostalCodeWarning)> {
+        [
+            ("creditor_address.plz", Some(&self.creditor_address)),
+            ("debtor_address.plz", self.debtor_address.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(field, address)| Some((field, address?.check_postal_code()?)))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_matching_localities() {
+        assert_eq!(check_postal_code("8001", "Zürich", Country::CH), None);
+        assert_eq!(check_postal_code("8001", "Zurich", Country::CH), None);
+        assert_eq!(check_postal_code("2501", "Biel", Country::CH), None);
+        assert_eq!(check_postal_code("2501", "Bienne", Country::CH), None);
+        assert_eq!(check_postal_code("1211", "Genève 3", Country::CH), None);
+        assert_eq!(check_postal_code("1227", "Carouge", Country::CH), None);
+        assert_eq!(check_postal_code("9490", "Vaduz", Country::LI), None);
+    }
+
+    #[test]
+    fn reports_wrong_city_with_suggestions() {
+        assert_eq!(
+            check_postal_code("3000", "Zürich", Country::CH),
+            Some(PostalCodeWarning::CityMismatch {
+                plz: "3000".into(),
+                city: "Zürich".into(),
+                suggestions: vec!["Bern"],
+            })
+        );
+    }
+
+    #[test]
+    fn reports_liechtenstein_postal_code_with_swiss_country() {
+        assert!(matches!(
+            check_postal_code("9490", "Vaduz", Country::CH),
+            Some(PostalCodeWarning::CountryMismatch { expected: Country::LI, .. })
+        ));
+        assert!(matches!(
+            check_postal_code("8001", "Zürich", Country::LI),
+            Some(PostalCodeWarning::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_swiss_postal_codes_are_only_reported_with_complete_directory() {
+        let expected = POSTAL_DIRECTORY_COMPLETE.then(|| PostalCodeWarning::UnknownPostalCode { plz: "9999".into() });
+        assert_eq!(check_postal_code("9999", "Nirgendwo", Country::CH), expected);
+        assert!(matches!(
+            check_postal_code("30001", "Bern", Country::CH),
+            Some(PostalCodeWarning::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn checks_foreign_formats() {
+        assert_eq!(check_postal_code("79539", "Lörrach", Country::DE), None);
+        assert_eq!(check_postal_code("6800", "Feldkirch", Country::AT), None);
+        assert_eq!(check_postal_code("1012 AB", "Amsterdam", Country::NL), None);
+        assert_eq!(check_postal_code("SW1A 1AA", "London", Country::GB), None);
+        assert!(check_postal_code("680", "Feldkirch", Country::AT).is_some());
+        assert!(check_postal_code("7500", "Paris", Country::FR).is_some());
+        // No rules for this country: anything goes.
+        assert_eq!(check_postal_code("X", "Reykjavík", Country::IS), None);
+    }
+
+    #[test]
+    fn lists_localities() {
+        assert_eq!(localities_for_postal_code("2501"), vec!["Biel/Bienne"]);
+        assert_eq!(localities_for_postal_code(" 9490 "), vec!["Vaduz"]);
+        assert!(localities_for_postal_code("abc").is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! Best-effort conversion of arbitrary text into the SPS character set
+//! (spec 4.1.1, see [`is_in_extended_sps_charset`]).
+//!
+//! Data from CRMs and foreign debtors routinely contains typographic quotes,
+//! dashes, non-breaking spaces, decomposed umlauts or names in Cyrillic or
+//! Greek script, all of which [`is_valid_sps_charset`](crate::is_valid_sps_charset)
+//! rejects. [`sanitize`] fixes what can be fixed and reports every change,
+//! so callers can show the user what was altered.
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::support::utils::is_in_extended_sps_charset;
+
+/// Result of [`sanitize`]: the cleaned text plus a log of what was changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitized {
+    pub text: String,
+    /// Whether Unicode NFC normalization changed the input (e.g. "u" +
+    /// combining diaeresis composed to "ü"). Not listed in `changes`.
+    pub normalized: bool,
+    pub changes: Vec<SanitizeChange>,
+}
+
+impl Sanitized {
+    /// `true` if the output differs from the input in any way.
+    pub fn is_changed(&self) -> bool {
+        self.normalized || !self.changes.is_empty()
+    }
+}
+
+/// One character that was outside the SPS character set. `index` is the
+/// character position in the NFC-normalized input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SanitizeChange {
+    Replaced { index: usize, original: char, replacement: String },
+    Dropped { index: usize, original: char },
+}
+
+/// Converts `text` into the SPS character set.
+///
+/// 1. NFC normalization, so decomposed characters become the precomposed
+///    ones the character set contains.
+/// 2. Characters already in the set are kept; a few that are technically
+///    permitted but cause trouble downstream (non-breaking space) are
+///    replaced anyway.
+/// 3. Common typographic characters are mapped to plain equivalents
+///    (’ → ', – → -, … → ...), Cyrillic and Greek letters are
+///    transliterated.
+/// 4. Anything else is compatibility-decomposed with diacritics stripped
+///    ("ǎ" → "a", "ﬁ" → "fi") if that yields permitted characters, and
+///    dropped otherwise.
+///
+/// ```
+/// use swiss_qrust::sanitize;
+///
+/// let result = sanitize("“Café” – Москва 🤣");
+/// assert_eq!(result.text, "\"Café\" - Moskva ");
+/// assert_eq!(result.changes.len(), 10);
+/// ```
+pub fn sanitize(text: &str) -> Sanitized {
+    let nfc: String = text.nfc().collect();
+    let normalized = nfc != text;
+
+    let mut out = String::with_capacity(nfc.len());
+    let mut changes = Vec::new();
+
+    for (index, ch) in nfc.chars().enumerate() {
+        if is_permitted(ch) {
+            out.push(ch);
+            continue;
+        }
+        match replacement(ch) {
+            Some(replacement) => {
+                out.push_str(&replacement);
+                changes.push(SanitizeChange::Replaced { index, original: ch, replacement });
+            }
+            None => changes.push(SanitizeChange::Dropped { index, original: ch }),
+        }
+    }
+
+    Sanitized { text: out, normalized, changes }
+}
+
+fn is_permitted(ch: char) -> bool {
+    ch != '\u{00A0}' && is_in_extended_sps_charset(ch as u32)
+}
+
+fn replacement(ch: char) -> Option<String> {
+    if let Some(s) = punctuation(ch) {
+        return Some(s.to_string());
+    }
+    if let Some(s) = transliterate(ch) {
+        return Some(s);
+    }
+
+    // Compatibility decomposition with combining marks removed: covers
+    // letters with diacritics outside Latin Extended-A, ligatures,
+    // full-width forms, superscripts, accented Greek/Cyrillic, ...
+    let mut decomposed = String::new();
+    for c in ch.to_string().nfkd().filter(|c| !is_combining_mark(*c)) {
+        if is_permitted(c) {
+            decomposed.push(c);
+        } else {
+            decomposed.push_str(&transliterate(c)?);
+        }
+    }
+    (!decomposed.is_empty()).then_some(decomposed)
+}
+
+fn punctuation(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' | '\u{02BC}' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => "\"",
+        '\u{2039}' => "<",
+        '\u{203A}' => ">",
+        '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}' | '\u{2212}' => "-",
+        '\u{2022}' | '\u{2023}' | '\u{2043}' => "-",
+        '\u{2026}' => "...",
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => " ",
+        '\t' | '\n' | '\r' => " ",
+        '\u{1E9E}' => "SS",
+        '\u{2122}' => "TM",
+        _ => return None,
+    })
+}
+
+/// Transliterates a Cyrillic or Greek letter into Latin script, keeping
+/// upper case on the first letter ("Ж" → "Zh").
+fn transliterate(ch: char) -> Option<String> {
+    let lower = ch.to_lowercase().next()?;
+    let latin = cyrillic(lower).or_else(|| greek(lower))?;
+
+    if lower == ch {
+        return Some(latin.to_string());
+    }
+    let mut chars = latin.chars();
+    Some(match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    })
+}
+
+/// Russian, Ukrainian, Belarusian, Bulgarian and Serbian/Macedonian letters;
+/// Serbian letters with a Latin-script counterpart in Latin Extended-A map
+/// to that (ђ → đ, ћ → ć).
+fn cyrillic(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'а' => "a", 'б' => "b", 'в' => "v", 'г' => "g", 'д' => "d", 'е' => "e",
+        'ё' => "e", 'ж' => "zh", 'з' => "z", 'и' => "i", 'й' => "y", 'к' => "k",
+        'л' => "l", 'м' => "m", 'н' => "n", 'о' => "o", 'п' => "p", 'р' => "r",
+        'с' => "s", 'т' => "t", 'у' => "u", 'ф' => "f", 'х' => "kh", 'ц' => "ts",
+        'ч' => "ch", 'ш' => "sh", 'щ' => "shch", 'ъ' => "", 'ы' => "y", 'ь' => "",
+        'э' => "e", 'ю' => "yu", 'я' => "ya",
+        'і' => "i", 'ї' => "yi", 'є' => "ye", 'ґ' => "g", 'ў' => "u",
+        'ђ' => "đ", 'ј' => "j", 'љ' => "lj", 'њ' => "nj", 'ћ' => "ć", 'џ' => "dž",
+        'ѓ' => "gj", 'ќ' => "kj", 'ѕ' => "dz",
+        _ => return None,
+    })
+}
+
+/// Modern Greek, after ELOT 743 (without the digraph context rules).
+fn greek(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'α' => "a", 'β' => "v", 'γ' => "g", 'δ' => "d", 'ε' => "e", 'ζ' => "z",
+        'η' => "i", 'θ' => "th", 'ι' => "i", 'κ' => "k", 'λ' => "l", 'μ' => "m",
+        'ν' => "n", 'ξ' => "x", 'ο' => "o", 'π' => "p", 'ρ' => "r", 'σ' | 'ς' => "s",
+        'τ' => "t", 'υ' => "y", 'φ' => "f", 'χ' => "ch", 'ψ' => "ps", 'ω' => "o",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::is_valid_sps_charset;
+
+    #[test]
+    fn permitted_text_is_unchanged() {
+        let result = sanitize("Șipi Müller € AG, Rue du Lac 1268");
+        assert_eq!(result.text, "Șipi Müller € AG, Rue du Lac 1268");
+        assert!(!result.is_changed());
+    }
+
+    #[test]
+    fn decomposed_umlaut_is_composed() {
+        let result = sanitize("Mu\u{0308}ller");
+        assert_eq!(result.text, "Müller");
+        assert!(result.normalized);
+        assert!(result.changes.is_empty());
+    }
+
+    #[test]
+    fn typographic_punctuation_is_mapped() {
+        let result = sanitize("l’Hôpital – 10\u{00A0}000 CHF…");
+        assert_eq!(result.text, "l'Hôpital - 10 000 CHF...");
+        assert_eq!(
+            result.changes[0],
+            SanitizeChange::Replaced { index: 1, original: '’', replacement: "'".to_string() }
+        );
+    }
+
+    #[test]
+    fn cyrillic_and_greek_are_transliterated() {
+        assert_eq!(sanitize("Жанна Щербакова").text, "Zhanna Shcherbakova");
+        assert_eq!(sanitize("Ђорђе Јовановић").text, "Đorđe Jovanović");
+        assert_eq!(sanitize("Αθήνα").text, "Athina");
+        assert_eq!(sanitize("ẞTRASSE").text, "SSTRASSE");
+    }
+
+    #[test]
+    fn diacritics_outside_the_charset_are_stripped() {
+        assert_eq!(sanitize("Nguyễn").text, "Nguyen");
+        assert_eq!(sanitize("ﬁnance").text, "finance");
+    }
+
+    #[test]
+    fn unmappable_characters_are_dropped_and_logged() {
+        let result = sanitize("Hi 🤣 北京");
+        assert_eq!(result.text, "Hi  ");
+        assert_eq!(result.changes[0], SanitizeChange::Dropped { index: 3, original: '🤣' });
+        assert_eq!(result.changes.len(), 3);
+    }
+
+    #[test]
+    fn output_is_always_valid_sps_charset() {
+        let inputs = ["“Café” – Москва 🤣", "Ελληνικά\tтекст\r\n", "Ωμέγα ǅ ǆ ﬀ ½ ℃"];
+        for input in inputs {
+            let result = sanitize(input);
+            assert!(is_valid_sps_charset(&result.text).is_ok(), "{input:?} -> {:?}", result.text);
+        }
+    }
+}
@@ -5,6 +5,8 @@
  */
 use std::num::ParseIntError;
 
+use crate::support::validators::{is_valid_iso11649_reference, is_valid_qr_reference, ReferenceError};
+
 /// Cyclic Mod-10 Vector for validating and calculating ESR Checksum
 const MOD_10: [u8; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
 
@@ -230,7 +232,9 @@ pub fn generate_iban_with_checksum(cty: &str, raw: &str) -> Result<String, Parse
 ///
 /// # Returns
 ///
-/// * QRR (ESR) String of 27 chars, last being the checksum
+/// * QRR (ESR) String of 27 chars, last being the checksum. Input that
+///   doesn't give a valid reference (non-digits, too long, all zeros) is an
+///   error.
 ///
 /// # Example
 /// ```
@@ -241,12 +245,14 @@ pub fn generate_iban_with_checksum(cty: &str, raw: &str) -> Result<String, Parse
 /// ```
 ///
 /// ```
-/// # use swiss_qrust::generate_qrr_with_checksum;
+/// # use swiss_qrust::{generate_qrr_with_checksum, ReferenceError};
 /// let with_checksum = generate_qrr_with_checksum("18 7858");
 /// assert_eq!(with_checksum.as_ref().unwrap().len(), 27);
 /// assert_eq!(with_checksum.unwrap(), "000000000000000000001878583");
+/// assert!(generate_qrr_with_checksum("12ab").is_err());
+/// assert_eq!(generate_qrr_with_checksum("000"), Err(ReferenceError::AllZeroQrReference));
 /// ```
-pub fn generate_qrr_with_checksum(raw: &str) -> Result<String, ParseIntError> {
+pub fn generate_qrr_with_checksum(raw: &str) -> Result<String, ReferenceError> {
 
     let raw = raw.chars().filter(|ch| !ch.is_whitespace()).collect::<String>();
 
@@ -260,7 +266,9 @@ pub fn generate_qrr_with_checksum(raw: &str) -> Result<String, ParseIntError> {
 
     let checksum = (10 - carry) % 10;
 
-    Ok(format!("{:0>26}{}", raw, checksum))
+    let reference = format!("{:0>26}{}", raw, checksum);
+    is_valid_qr_reference(&reference)?;
+    Ok(reference)
 }
 
 /// Generate SCOR (ISO 11649) Reference with Checksum
@@ -272,6 +280,7 @@ pub fn generate_qrr_with_checksum(raw: &str) -> Result<String, ParseIntError> {
 /// # Returns
 ///
 /// * SCOR Reference number of up to 25 chars, first 4 being 'RF' + XX, where XX is a two-digit checksum.
+///   Input that doesn't give a valid reference is an error.
 ///
 /// # Examples
 ///
@@ -287,8 +296,9 @@ pub fn generate_qrr_with_checksum(raw: &str) -> Result<String, ParseIntError> {
 /// let input = "1234 5";
 /// let with_checksum = generate_iso11649_with_checksum(input);
 /// assert_eq!(with_checksum.unwrap(), "RF7812345");
+/// assert!(generate_iso11649_with_checksum("12-45").is_err());
 /// ```
-pub fn generate_iso11649_with_checksum(raw: &str) -> Result<String, ParseIntError> {
+pub fn generate_iso11649_with_checksum(raw: &str) -> Result<String, ReferenceError> {
 
     let raw = raw.chars().filter(|ch| !ch.is_whitespace()).collect::<String>();
 
@@ -310,5 +320,7 @@ pub fn generate_iso11649_with_checksum(raw: &str) -> Result<String, ParseIntErro
 
     let check_digit = 98 - remainder;
 
-    Ok(format!("RF{:02}{}", check_digit, raw))
+    let reference = format!("RF{:02}{}", check_digit, raw);
+    is_valid_iso11649_reference(&reference)?;
+    Ok(reference)
 }
@@ -5,7 +5,7 @@
  */
 
 use thiserror::Error;
-use crate::Country;
+use crate::{Country, Iban};
 use crate::support::utils;
 use crate::support::utils::{is_in_extended_sps_charset,
                             qr_ref_is_numeric,
@@ -29,6 +29,8 @@ pub enum IbanError {
     IncorrectCountryCode,
     #[error("Invalid character")]
     InvalidCharacter,
+    #[error("Invalid institution id (IID). Must be 5 digits")]
+    InvalidIid,
     #[error("Invalid Iban")]
     InvalidIban,
 }
@@ -54,6 +56,8 @@ pub enum ReferenceError {
     InvalidQrChar,
     #[error("Invalid QR checksum.")]
     InvalidQrChecksum,
+    #[error("QR reference must not be all zeros")]
+    AllZeroQrReference,
     #[error("Invalid QR length. Expected: {expected}, Actual: {actual}")]
     InvalidQrLength { expected: usize, actual: usize },
     #[error("Invalid ISO11649 length. Must be 5-25 characters long")]
@@ -68,7 +72,8 @@ pub enum ReferenceError {
     InvalidReference,
 }
 
-/// Validates an IBAN
+/// Validates a Swiss or Liechtenstein IBAN; see [`Iban::parse`] for the
+/// rules and to keep the parsed value.
 ///
 /// Valid IBAN:
 ///```
@@ -95,57 +100,7 @@ pub enum ReferenceError {
 /// assert_eq!(err, IbanError::IncorrectCountryCode );
 /// ```
 pub fn is_valid_iban(iban: &str) -> Result<(), IbanError>  {
-
-    let iban: String = iban.chars()
-        .filter(|c| !c.is_whitespace())
-        .collect();
-
-    let first_two: String = iban
-        .to_ascii_uppercase()
-        .chars()
-        .take(2)
-        .collect();
-
-    if !(first_two == "CH" || first_two == "LI") {
-        return Err(IbanError::IncorrectCountryCode);
-    }
-
-    if iban.len() < 15 || iban.len() > 34 {
-        return Err(IbanError::IncorrectLength{
-            expected: 21,
-            actual: iban.len(),
-        })}
-
-    // Byte-slicing (`iban[4..]`) would panic if a multi-byte character
-    // straddles the 4-byte mark - the country-code check above only
-    // rejects the *first two* characters, so anything after "CH"/"LI" is
-    // still caller-controlled and unchecked at this point. Rotate by char
-    // instead so arbitrary Unicode input can only ever fail validation,
-    // never panic.
-    let mut chars: Vec<char> = iban.chars().collect();
-    let head: Vec<char> = chars.drain(..chars.len().min(4)).collect();
-    let rearranged = chars.into_iter().chain(head);
-
-    let mut remainder: u32 = 0;
-
-    for ch in rearranged {
-        match ch.to_ascii_uppercase() {
-            '0'..='9' => {
-                remainder = (remainder * 10 + (ch as u32 - '0' as u32)) % 97;
-            }
-            'A'..='Z' => {
-                let value = ch.to_ascii_uppercase() as u32 - 'A' as u32 + 10;
-                remainder = (remainder * 100 + value) % 97;
-            }
-            _ => return Err(IbanError::InvalidCharacter),
-        }
-    }
-
-    if remainder != 1 {
-        return Err(IbanError::InvalidIban)
-    }
-
-    Ok(())
+    Iban::parse(iban).map(|_| ())
 }
 
 /// QR Reference Number
@@ -172,7 +127,7 @@ pub fn is_valid_qr_reference(reference: &str) -> Result<(), ReferenceError> {
     }
 
     if reference == "000000000000000000000000000" {
-        return Err(ReferenceError::InvalidQrChecksum);
+        return Err(ReferenceError::AllZeroQrReference);
     }
 
     if !utils::mod10(&reference) {
@@ -252,6 +207,12 @@ mod tests {
         assert_eq!(is_valid_qr_reference("0").unwrap_err(), ReferenceError::InvalidQrLength{expected: 27, actual: 1});
     }
 
+    #[test]
+    fn all_zero_qr_ref_is_its_own_error(){
+        let zeros = "00 00000 00000 00000 00000 00000";
+        assert_eq!(is_valid_qr_reference(zeros).unwrap_err(), ReferenceError::AllZeroQrReference);
+    }
+
     #[test]
     fn valid_iso11649_references() {
         let valid = [
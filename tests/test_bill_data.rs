@@ -80,7 +80,7 @@ fn test_try_from_input_bill() {
     let bill = BillData::try_from(input).unwrap();
 
     assert_eq!(bill.currency.to_string(), "CHF");
-    assert_eq!(bill.iban.to_string(), "CH6431961000004421557");
+    assert_eq!(bill.iban.as_str(), "CH6431961000004421557");
     assert_eq!(bill.creditor_address.name.to_string(), "Robert Schneider AG");
 }
 
@@ -0,0 +1,101 @@
+/*
+ * Copyright (c) 2026 Thomas Prosser
+ * Licensed under MIT License
+ * https://opensource.org/licenses/MIT
+ */
+
+//! "Do not use for payment" notifications: amount 0.00 is only valid in
+//! notification mode, the notice ends up in the QR code (Ustrd) and is
+//! visible on both receipt and payment part.
+
+use std::convert::TryFrom;
+use swiss_qrust::bill::qr_bill::QrBill;
+use swiss_qrust::constants::*;
+use swiss_qrust::information_block::InformationBlock;
+use swiss_qrust::receipt_part::ReceiptLayout;
+use swiss_qrust::svg::SvgFontLibrary;
+use swiss_qrust::{
+    BillData, BillError, ColumnCursor, DrawOp, InputBill, Language, LayoutBlock, Mm, RenderContext, SlipPart,
+};
+
+const TOML: &str = r#"
+iban = "CH93 0076 2011 6238 5295 7"
+currency = "CHF"
+amount = "0.00"
+
+[creditor_address]
+name = "Robert Schneider AG"
+street = "Rue du Lac"
+house_num = "1268"
+plz = "2501"
+city = "Biel"
+country = "CH"
+"#;
+
+fn input(notification: Option<&str>) -> InputBill {
+    let mut input: InputBill = toml::from_str(TOML).unwrap();
+    input.alternative_schemes = Some([Some("eBill/B/peter@sample.ch".into()), None]);
+    input.notification = notification.map(str::to_string);
+    input
+}
+
+#[test]
+fn zero_amount_is_only_accepted_for_notifications() {
+    assert!(matches!(BillData::try_from(input(None)).unwrap_err().kind(), BillError::InvalidAmount));
+
+    let bill = BillData::try_from(input(Some("de"))).unwrap();
+    assert_eq!(bill.notification, Some(Language::De));
+    assert_eq!(bill.amount.as_deref(), Some("0.00"));
+    assert_eq!(bill.unstructured_message.as_deref(), Some("NICHT ZUR ZAHLUNG VERWENDEN"));
+}
+
+#[test]
+fn notification_rejects_real_amounts_messages_and_unknown_languages() {
+    let mut with_amount = input(Some("fr"));
+    with_amount.amount = Some("10.00".into());
+    let err = BillData::try_from(with_amount).unwrap_err();
+    assert!(matches!(err.kind(), BillError::NotificationWithAmount));
+    assert_eq!(err.field(), Some("amount"));
+
+    let mut with_message = input(Some("fr"));
+    with_message.unstructured_message = Some("Rechnung 42".into());
+    let err = BillData::try_from(with_message).unwrap_err();
+    assert!(matches!(err.kind(), BillError::NotificationWithMessage));
+    assert_eq!(err.field(), Some("unstructured_message"));
+
+    let err = BillData::try_from(input(Some("rm"))).unwrap_err();
+    assert!(matches!(err.kind(), BillError::Language(_)));
+    assert_eq!(err.field(), Some("notification"));
+}
+
+#[test]
+fn notice_is_encoded_in_the_qr_code() {
+    let bill = BillData::try_from(input(Some("it"))).unwrap();
+    let text = QrBill::new(&bill).unwrap().create_qr_text().unwrap();
+    let fields: Vec<&str> = text.split("\r\n").collect();
+
+    assert!(fields.contains(&"0.00"));
+    let epd = fields.iter().position(|&f| f == "EPD").unwrap();
+    assert_eq!(fields[epd - 1], "NON UTILIZZARE PER IL PAGAMENTO");
+}
+
+#[test]
+fn receipt_shows_the_notice_in_bold() {
+    let bill = BillData::try_from(input(Some("en"))).unwrap();
+    let fonts = SvgFontLibrary::new();
+    let ctx = RenderContext::for_strategy::<ReceiptLayout<SvgFontLibrary>>(&bill, Language::De, &fonts);
+    let block = InformationBlock {
+        part: SlipPart::Receipt,
+        offset: Mm(0.0),
+        payable_box_width: DEBTOR_BOX_WIDTH_RC,
+        payable_box_height: DEBTOR_BOX_HEIGHT_RC,
+    };
+
+    let mut ops = Vec::new();
+    block.render(&ctx, &mut ops, &mut ColumnCursor::new(MARGIN, Mm(0.0)));
+
+    assert!(ops.iter().any(|op| matches!(
+        op,
+        DrawOp::Text { text, bold: true, .. } if text == "DO NOT USE FOR PAYMENT"
+    )));
+}
@@ -18,7 +18,7 @@
 //! must still be able to produce Swico billing info without a /32/ tag.
 
 use std::convert::TryFrom;
-use swiss_qrust::{BillData, BillError, InputBill};
+use swiss_qrust::{BillData, BillError, InputBill, SwicoError};
 
 fn bill_json(swico_bill_information: &str) -> String {
     format!(
@@ -165,5 +165,32 @@ fn providing_both_bill_information_and_swico_is_rejected() {
     let input: InputBill = serde_json::from_str(&json).unwrap();
     let err = BillData::try_from(input).unwrap_err();
 
-    assert!(matches!(err, BillError::AmbiguousBillingInformation));
+    assert!(matches!(err.kind(), BillError::AmbiguousBillingInformation));
+}
+
+#[test]
+fn swico_rule_violations_are_reported_with_their_tag() {
+    let json = bill_json(
+        r#"{
+            "invoice_number": "10201409",
+            "invoice_date": null,
+            "customer_reference": null,
+            "vat_number": null,
+            "vat_date": null,
+            "vat_start_date": "2026-03-31",
+            "vat_end_date": "2026-03-01",
+            "vat_rate": null,
+            "vat_rate_details": null,
+            "vat_import_taxes": null,
+            "payment_conditions": null
+        }"#,
+    );
+
+    let input: InputBill = serde_json::from_str(&json).unwrap();
+    let err = BillData::try_from(input).unwrap_err();
+
+    assert_eq!(err.field(), Some("swico_bill_information.vat_start_date"));
+    let BillError::Swico(swico) = err.kind() else { panic!("expected a Swico error, got {err:?}") };
+    assert!(matches!(swico, SwicoError::VatPeriodReversed { .. }));
+    assert_eq!(swico.tag(), Some(31));
 }